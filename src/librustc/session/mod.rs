@@ -809,6 +809,8 @@ pub fn build_session_with_codemap(sopts: config::Options,
 
     let external_macro_backtrace = sopts.debugging_opts.external_macro_backtrace;
 
+    let collect_applicable_suggestions = sopts.debugging_opts.apply_suggestions;
+
     let emitter: Box<Emitter> = match (sopts.error_format, emitter_dest) {
         (config::ErrorOutputType::HumanReadable(color_config), None) => {
             Box::new(EmitterWriter::stderr(color_config, Some(codemap.clone()), false))
@@ -837,6 +839,7 @@ pub fn build_session_with_codemap(sopts: config::Options,
                 can_emit_warnings,
                 treat_err_as_bug,
                 external_macro_backtrace,
+                collect_applicable_suggestions,
                 .. Default::default()
             });
 
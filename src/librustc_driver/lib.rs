@@ -108,6 +108,7 @@ pub mod driver;
 pub mod pretty;
 pub mod target_features;
 mod derive_registrar;
+mod suggestions;
 
 const BUG_REPORT_URL: &'static str = "https://github.com/rust-lang/rust/blob/master/CONTRIBUTING.\
                                       md#bug-reports";
@@ -250,14 +251,23 @@ pub fn run_compiler<'a>(args: &[String],
 
     let plugins = sess.opts.debugging_opts.extra_plugins.clone();
     let control = callbacks.build_controller(&sess, &matches);
-    (driver::compile_input(&sess,
-                           &cstore,
-                           &input,
-                           &odir,
-                           &ofile,
-                           Some(plugins),
-                           &control),
-     Some(sess))
+    let result = driver::compile_input(&sess,
+                                       &cstore,
+                                       &input,
+                                       &odir,
+                                       &ofile,
+                                       Some(plugins),
+                                       &control);
+    if sess.opts.debugging_opts.apply_suggestions {
+        // Suggestions made for code which doesn't compile may well not make
+        // any sense once the errors are fixed, so leave the files alone then
+        if sess.has_errors() {
+            sess.note_without_error("suggestions were not applied because of the errors");
+        } else {
+            suggestions::apply_suggestions(&sess);
+        }
+    }
+    (result, Some(sess))
 }
 
 // Extract output directory and file from matches.
@@ -0,0 +1,87 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rewrites the source files with the machine applicable suggestions of the
+//! emitted diagnostics, for `-Z apply-suggestions`.
+
+use rustc::session::Session;
+use syntax_pos::{DUMMY_SP, FileName, NO_EXPANSION};
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub fn apply_suggestions(sess: &Session) {
+    let codemap = sess.codemap();
+
+    // Collect the replacements for each file, as byte ranges of its source
+    let mut files: BTreeMap<PathBuf, (Rc<String>, Vec<(usize, usize, String)>)> =
+        BTreeMap::new();
+    for part in sess.diagnostic().take_applicable_suggestions() {
+        // Code expanded from macros doesn't appear as is in any file
+        if part.span == DUMMY_SP || part.span.ctxt() != NO_EXPANSION {
+            continue;
+        }
+        let lo = codemap.lookup_byte_offset(part.span.lo());
+        let hi = codemap.lookup_byte_offset(part.span.hi());
+        if !Rc::ptr_eq(&lo.fm, &hi.fm) || lo.fm.name_was_remapped {
+            continue;
+        }
+        let (path, src) = match (&lo.fm.name, &lo.fm.src) {
+            (&FileName::Real(ref path), &Some(ref src)) => (path.clone(), src.clone()),
+            _ => continue,
+        };
+        files.entry(path)
+             .or_insert_with(|| (src, Vec::new()))
+             .1.push((lo.pos.0 as usize, hi.pos.0 as usize, part.snippet));
+    }
+
+    for (path, (src, mut replacements)) in files {
+        replacements.sort();
+        replacements.dedup();
+
+        let mut fixed = String::with_capacity(src.len());
+        let mut pos = 0;
+        let mut applied = 0;
+        for (lo, hi, snippet) in replacements {
+            // Skip suggestions overlapping one which has already been applied
+            if lo < pos {
+                continue;
+            }
+            fixed.push_str(&src[pos..lo]);
+            fixed.push_str(&snippet);
+            pos = hi;
+            applied += 1;
+        }
+        fixed.push_str(&src[pos..]);
+
+        // The codemap strips the byte order mark, put it back if there was one
+        let mut bom = [0; 3];
+        let has_bom = File::open(&path).and_then(|mut file| file.read_exact(&mut bom)).is_ok() &&
+                      bom == *b"\xef\xbb\xbf";
+        if has_bom {
+            fixed.insert(0, '\u{feff}');
+        }
+
+        match File::create(&path).and_then(|mut file| file.write_all(fixed.as_bytes())) {
+            Ok(()) => {
+                sess.note_without_error(&format!("applied {} suggestion{} to `{}`",
+                                                 applied,
+                                                 if applied == 1 { "" } else { "s" },
+                                                 path.display()));
+            }
+            Err(e) => {
+                sess.err(&format!("failed to apply suggestions to `{}`: {}", path.display(), e));
+            }
+        }
+    }
+}
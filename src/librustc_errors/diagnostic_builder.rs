@@ -8,6 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use Applicability;
 use Diagnostic;
 use DiagnosticId;
 use DiagnosticStyledString;
@@ -192,6 +193,24 @@ impl<'a> DiagnosticBuilder<'a> {
                                      msg: &str,
                                      suggestions: Vec<String>)
                                      -> &mut Self);
+    forward!(pub fn span_suggestion_short_with_applicability(&mut self,
+                                                             sp: Span,
+                                                             msg: &str,
+                                                             suggestion: String,
+                                                             applicability: Applicability)
+                                                             -> &mut Self);
+    forward!(pub fn span_suggestion_with_applicability(&mut self,
+                                                       sp: Span,
+                                                       msg: &str,
+                                                       suggestion: String,
+                                                       applicability: Applicability)
+                                                       -> &mut Self);
+    forward!(pub fn span_suggestions_with_applicability(&mut self,
+                                                        sp: Span,
+                                                        msg: &str,
+                                                        suggestions: Vec<String>,
+                                                        applicability: Applicability)
+                                                        -> &mut Self);
     forward!(pub fn set_span<S: Into<MultiSpan>>(&mut self, sp: S) -> &mut Self);
     forward!(pub fn code(&mut self, s: DiagnosticId) -> &mut Self);
 
@@ -79,6 +79,35 @@ pub struct CodeSuggestion {
     pub substitutions: Vec<Substitution>,
     pub msg: String,
     pub show_code_when_inline: bool,
+    /// How confident we are that the suggestion is what the user intended,
+    /// and so whether tools like rustfix can apply it without asking. Only
+    /// `MachineApplicable` suggestions are applied automatically, the ones
+    /// which `HasPlaceholders` are not even valid code as they are.
+    pub applicability: Applicability,
+}
+
+/// Indicates the confidence in the correctness of a suggestion.
+///
+/// All suggestions are marked with an `Applicability`. Tools use the applicability of a
+/// suggestion to determine whether it should be automatically applied or if the user should be
+/// consulted before applying the suggestion.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended. This suggestion should be
+    /// automatically applied.
+    MachineApplicable,
+
+    /// The suggestion contains placeholders like `(...)` or `{ /* fields */ }`. The suggestion
+    /// cannot be applied automatically because it will not result in valid Rust code. The user
+    /// will need to fill in the placeholders.
+    HasPlaceholders,
+
+    /// The suggestion may be what the user intended, but it is uncertain. The suggestion should
+    /// result in valid Rust code if it is applied.
+    MaybeIncorrect,
+
+    /// The suggestion's applicability is unknown.
+    Unspecified,
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, RustcEncodable, RustcDecodable)]
@@ -100,6 +129,8 @@ pub trait CodeMapper {
     fn span_to_filename(&self, sp: Span) -> FileName;
     fn merge_spans(&self, sp_lhs: Span, sp_rhs: Span) -> Option<Span>;
     fn call_span_if_macro(&self, sp: Span) -> Span;
+    /// Maps a line of a compiled doctest back to the line of its doc comment.
+    fn doctest_offset_line(&self, file: &FileName, line: usize) -> usize;
     fn ensure_filemap_source_present(&self, file_map: Rc<FileMap>) -> bool;
 }
 
@@ -246,6 +277,10 @@ pub struct Handler {
     // this handler. These hashes is used to avoid emitting the same error
     // twice.
     emitted_diagnostics: RefCell<FxHashSet<u128>>,
+
+    // The machine applicable suggestions of all emitted diagnostics, if
+    // `HandlerFlags::collect_applicable_suggestions` is set.
+    applicable_suggestions: RefCell<Vec<SubstitutionPart>>,
 }
 
 #[derive(Default)]
@@ -253,6 +288,7 @@ pub struct HandlerFlags {
     pub can_emit_warnings: bool,
     pub treat_err_as_bug: bool,
     pub external_macro_backtrace: bool,
+    pub collect_applicable_suggestions: bool,
 }
 
 impl Handler {
@@ -301,6 +337,7 @@ impl Handler {
             delayed_span_bug: RefCell::new(None),
             tracked_diagnostics: RefCell::new(None),
             emitted_diagnostics: RefCell::new(FxHashSet()),
+            applicable_suggestions: RefCell::new(Vec::new()),
         }
     }
 
@@ -568,6 +605,13 @@ impl Handler {
         (ret, diagnostics)
     }
 
+    /// Takes the machine applicable suggestions emitted so far. Suggestions
+    /// are only collected if `HandlerFlags::collect_applicable_suggestions`
+    /// is set.
+    pub fn take_applicable_suggestions(&self) -> Vec<SubstitutionPart> {
+        mem::replace(&mut *self.applicable_suggestions.borrow_mut(), Vec::new())
+    }
+
     fn emit_db(&self, db: &DiagnosticBuilder) {
         let diagnostic = &**db;
 
@@ -586,6 +630,17 @@ impl Handler {
         // one:
         if self.emitted_diagnostics.borrow_mut().insert(diagnostic_hash) {
             self.emitter.borrow_mut().emit(db);
+
+            if self.flags.collect_applicable_suggestions {
+                let mut applicable = self.applicable_suggestions.borrow_mut();
+                for sugg in &diagnostic.suggestions {
+                    // Only a single substitution says unambiguously what to do
+                    if sugg.applicability == Applicability::MachineApplicable &&
+                       sugg.substitutions.len() == 1 {
+                        applicable.extend(sugg.substitutions[0].parts.iter().cloned());
+                    }
+                }
+            }
         }
     }
 }
@@ -1317,6 +1317,8 @@ pub struct Resolver<'a> {
     crate_loader: &'a mut CrateLoader,
     macro_names: FxHashSet<Ident>,
     global_macros: FxHashMap<Name, &'a NameBinding<'a>>,
+    /// All the `macro_rules!` macros defined in the crate, by name.
+    pub all_macros: FxHashMap<Name, Def>,
     lexical_macro_resolutions: Vec<(Ident, &'a Cell<LegacyScope<'a>>)>,
     macro_map: FxHashMap<DefId, Rc<SyntaxExtension>>,
     macro_defs: FxHashMap<Mark, DefId>,
@@ -1534,6 +1536,7 @@ impl<'a> Resolver<'a> {
 
             crate_loader,
             macro_names: FxHashSet(),
+            all_macros: FxHashMap(),
             global_macros: FxHashMap(),
             lexical_macro_resolutions: Vec::new(),
             macro_map: FxHashMap(),
@@ -1843,6 +1846,60 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Resolves a path written out as a string in namespace `ns` of the
+    /// module `scope`, once the crate has been resolved, without reporting
+    /// any errors. Rustdoc uses this for the paths in intra-doc links.
+    ///
+    /// Returns the definition along with the number of segments at the end
+    /// of the path which were left unresolved, since they name associated
+    /// items or fields.
+    pub fn resolve_str_path(&mut self, scope: NodeId, path_str: &str, ns: Namespace)
+                            -> Option<(Def, usize)> {
+        let scope = self.definitions.local_def_id(scope);
+        let module = match self.module_map.get(&scope).cloned() {
+            Some(module) => module,
+            None => return None,
+        };
+
+        let mut path = Vec::new();
+        let mut segments = path_str.split("::");
+        if path_str.starts_with("::") {
+            segments.next();
+            path.push(dummy_spanned(keywords::CrateRoot.ident()));
+        }
+        for segment in segments {
+            if segment.is_empty() {
+                return None;
+            }
+            path.push(dummy_spanned(Ident::from_str(segment)));
+        }
+
+        // There are no macro ribs to resolve a lone macro name lexically in,
+        // so look it up in the module directly.
+        if ns == MacroNS && path.len() == 1 {
+            let binding = self.resolve_ident_in_module(module, path[0].node, MacroNS,
+                                                       false, false, DUMMY_SP);
+            return binding.ok().map(|binding| (binding.def(), 0));
+        }
+
+        let orig_module = replace(&mut self.current_module, module);
+        self.ribs[ValueNS].push(Rib::new(ModuleRibKind(module)));
+        self.ribs[TypeNS].push(Rib::new(ModuleRibKind(module)));
+        let result = self.resolve_path(&path, Some(ns), false, DUMMY_SP);
+        self.current_module = orig_module;
+        self.ribs[ValueNS].pop();
+        self.ribs[TypeNS].pop();
+
+        match result {
+            PathResult::Module(module) => module.def().map(|def| (def, 0)),
+            PathResult::NonModule(path_res) => match path_res.base_def() {
+                Def::Err => None,
+                def => Some((def, path_res.unresolved_segments())),
+            },
+            PathResult::Indeterminate | PathResult::Failed(..) => None,
+        }
+    }
+
     /// Searches the current set of local scopes for labels. Returns the first non-None label that
     /// is returned by the given predicate function
     ///
@@ -0,0 +1,164 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Working out whether a type implements the auto traits `Send` and `Sync`,
+//! and under which conditions, so that it can be shown on its page even
+//! though there is no impl written for it.
+
+use rustc::hir;
+use rustc::hir::def_id::DefId;
+use rustc::traits::{Obligation, ObligationCause, Reveal, SelectionContext};
+use rustc::ty::{self, Ty, TyCtxt};
+use rustc::util::nodemap::FxHashSet;
+
+use core::DocContext;
+use clean;
+use super::Clean;
+
+/// What the trait selection found out about a type implementing an auto trait.
+enum AutoTraitResult<'tcx> {
+    /// The type implements the trait as long as these predicates on its type
+    /// parameters hold.
+    Positive(Vec<ty::Predicate<'tcx>>),
+    /// The type never implements the trait.
+    Negative,
+    /// The selection was ambiguous, so nothing can be said.
+    Unknown,
+}
+
+/// Builds the impls of `Send` and `Sync` for the struct, enum or union `did`
+/// which the compiler provides.
+///
+/// Types with an explicit impl of the trait, positive or negative, are left
+/// alone, as that impl is documented already.
+pub fn get_auto_trait_impls(cx: &DocContext, did: DefId) -> Vec<clean::Item> {
+    let tcx = cx.tcx;
+    let lang_items = tcx.lang_items();
+    let auto_traits = [lang_items.send_trait(), lang_items.sync_trait()];
+
+    let mut impls = Vec::new();
+    for &trait_did in auto_traits.iter().filter_map(|did| did.as_ref()) {
+        if has_explicit_impl(tcx, trait_did, did) {
+            continue;
+        }
+
+        let ty = tcx.type_of(did);
+        let (polarity, predicates) = match find_auto_trait_bounds(tcx, ty, trait_did) {
+            AutoTraitResult::Positive(bounds) => {
+                let mut predicates = tcx.predicates_of(did);
+                predicates.predicates.extend(bounds);
+                (clean::ImplPolarity::Positive, predicates)
+            }
+            AutoTraitResult::Negative => {
+                (clean::ImplPolarity::Negative, tcx.predicates_of(did))
+            }
+            AutoTraitResult::Unknown => continue,
+        };
+
+        let trait_ref = ty::TraitRef {
+            def_id: trait_did,
+            substs: tcx.mk_substs_trait(ty, &[]),
+        };
+        let trait_ = match trait_ref.clean(cx) {
+            clean::TraitBound(polyt, _) => polyt.trait_,
+            clean::RegionBound(..) => unreachable!(),
+        };
+
+        impls.push(clean::Item {
+            source: tcx.def_span(did).clean(cx),
+            name: None,
+            attrs: Default::default(),
+            visibility: None,
+            stability: None,
+            deprecation: None,
+            def_id: trait_did,
+            inner: clean::ImplItem(clean::Impl {
+                unsafety: hir::Unsafety::Normal,
+                generics: (tcx.generics_of(did), &predicates).clean(cx),
+                provided_trait_methods: FxHashSet(),
+                trait_: Some(trait_),
+                for_: ty.clean(cx),
+                items: Vec::new(),
+                polarity: Some(polarity),
+                synthetic: true,
+                blanket_impl: None,
+            }),
+        });
+    }
+    impls
+}
+
+fn has_explicit_impl(tcx: TyCtxt, trait_did: DefId, did: DefId) -> bool {
+    let mut found = false;
+    tcx.for_each_impl(trait_did, |impl_did| {
+        if let ty::TyAdt(def, _) = tcx.type_of(impl_did).sty {
+            found |= def.did == did;
+        }
+    });
+    found
+}
+
+/// Selects `ty: trait_did` and recursively the obligations it needs, keeping
+/// the ones which are about type parameters or associated types as the bounds
+/// needed for the trait to be implemented.
+fn find_auto_trait_bounds<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, ty: Ty<'tcx>, trait_did: DefId)
+                                    -> AutoTraitResult<'tcx> {
+    tcx.infer_ctxt().enter(|infcx| {
+        let mut selcx = SelectionContext::new(&infcx);
+        let param_env = ty::ParamEnv::empty(Reveal::UserFacing);
+        let trait_ref = ty::TraitRef {
+            def_id: trait_did,
+            substs: tcx.mk_substs_trait(ty, &[]),
+        };
+
+        let mut bounds = Vec::new();
+        let mut seen = FxHashSet();
+        let mut stack = vec![ty::Binder(trait_ref).to_poly_trait_predicate()];
+        while let Some(predicate) = stack.pop() {
+            let predicate = infcx.resolve_type_vars_if_possible(&predicate);
+            if !seen.insert(predicate) {
+                // Auto traits are coinductive, so a type which contains itself
+                // implements them if everything else it contains does.
+                continue;
+            }
+
+            match predicate.skip_binder().self_ty().sty {
+                ty::TyParam(..) | ty::TyProjection(..) => {
+                    let bound = ty::Predicate::Trait(predicate);
+                    if !bounds.contains(&bound) {
+                        bounds.push(bound);
+                    }
+                    continue;
+                }
+                ty::TyInfer(..) => return AutoTraitResult::Unknown,
+                _ => {}
+            }
+
+            let obligation = Obligation::new(ObligationCause::dummy(), param_env, predicate);
+            match selcx.select(&obligation) {
+                Ok(Some(vtable)) => {
+                    for nested in vtable.nested_obligations() {
+                        if let ty::Predicate::Trait(predicate) = nested.predicate {
+                            stack.push(predicate);
+                        }
+                    }
+                }
+                Ok(None) => return AutoTraitResult::Unknown,
+                Err(_) => return AutoTraitResult::Negative,
+            }
+        }
+        // The bounds only mention the type's parameters, so they outlive the
+        // inference context.
+        match tcx.lift_to_global(&bounds) {
+            Some(bounds) => AutoTraitResult::Positive(bounds),
+            None => AutoTraitResult::Unknown,
+        }
+    })
+}
@@ -0,0 +1,119 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Finding the blanket impls, like `impl<T: Display> ToString for T`, which
+//! apply to a type, so that they can be shown on its page.
+
+use rustc::hir::def_id::DefId;
+use rustc::traits::{Obligation, ObligationCause, Reveal, SelectionContext};
+use rustc::ty::{self, TyCtxt};
+use rustc::ty::subst::Subst;
+use syntax_pos::DUMMY_SP;
+
+use core::{DocContext, DocAccessLevels};
+use clean;
+use super::Clean;
+
+/// Builds the blanket impls of documented traits which may apply to the
+/// struct, enum or union `did`.
+///
+/// The impls are kept as they are written, with `blanket_impl` holding their
+/// generic self type, but their `for_` is `did` so that they end up on its
+/// page.
+pub fn get_blanket_impls(cx: &DocContext, did: DefId) -> Vec<clean::Item> {
+    let tcx = cx.tcx;
+    let impl_dids = cx.blanket_impls.borrow_mut()
+                                    .get_or_insert_with(|| all_blanket_impls(tcx))
+                                    .clone();
+    let mut impls = Vec::new();
+    for impl_did in impl_dids {
+        let trait_ref = match tcx.impl_trait_ref(impl_did) {
+            Some(trait_ref) => trait_ref,
+            None => continue,
+        };
+        if !cx.access_levels.borrow().is_doc_reachable(trait_ref.def_id) ||
+           !may_apply(tcx, impl_did, did) {
+            continue;
+        }
+
+        let predicates = tcx.predicates_of(impl_did);
+        let trait_ = match trait_ref.clean(cx) {
+            clean::TraitBound(polyt, _) => polyt.trait_,
+            clean::RegionBound(..) => unreachable!(),
+        };
+        let provided = tcx.provided_trait_methods(trait_ref.def_id)
+                          .into_iter()
+                          .map(|meth| meth.name.to_string())
+                          .collect();
+
+        impls.push(clean::Item {
+            source: tcx.def_span(impl_did).clean(cx),
+            name: None,
+            attrs: Default::default(),
+            visibility: None,
+            stability: None,
+            deprecation: None,
+            def_id: impl_did,
+            inner: clean::ImplItem(clean::Impl {
+                unsafety: tcx.trait_def(trait_ref.def_id).unsafety,
+                generics: (tcx.generics_of(impl_did), &predicates).clean(cx),
+                provided_trait_methods: provided,
+                trait_: Some(trait_),
+                for_: tcx.type_of(did).clean(cx),
+                items: tcx.associated_items(impl_did).map(|item| item.clean(cx)).collect(),
+                polarity: Some(tcx.impl_polarity(impl_did).clean(cx)),
+                synthetic: false,
+                blanket_impl: Some(tcx.type_of(impl_did).clean(cx)),
+            }),
+        });
+    }
+    impls
+}
+
+/// The trait impls of all the crates whose self type is a type parameter.
+fn all_blanket_impls(tcx: TyCtxt) -> Vec<DefId> {
+    let mut impl_dids = Vec::new();
+    for &cnum in tcx.crates().iter() {
+        impl_dids.extend(tcx.all_trait_implementations(cnum).iter().cloned());
+    }
+    for impls in tcx.hir.krate().trait_impls.values() {
+        impl_dids.extend(impls.iter().map(|&id| tcx.hir.local_def_id(id)));
+    }
+    impl_dids.retain(|&impl_did| {
+        !tcx.is_auto_impl(impl_did) && match tcx.type_of(impl_did).sty {
+            ty::TyParam(..) => true,
+            _ => false,
+        }
+    });
+    impl_dids
+}
+
+/// Whether the impl `impl_did` applies to the type `did` for some choice of
+/// its type parameters.
+fn may_apply<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, impl_did: DefId, did: DefId) -> bool {
+    tcx.infer_ctxt().enter(|infcx| {
+        let mut selcx = SelectionContext::new(&infcx);
+        let param_env = ty::ParamEnv::empty(Reveal::UserFacing);
+        let cause = ObligationCause::dummy();
+
+        let ty = tcx.type_of(did).subst(infcx.tcx, infcx.fresh_substs_for_item(DUMMY_SP, did));
+        let impl_substs = infcx.fresh_substs_for_item(DUMMY_SP, impl_did);
+        let impl_ty = tcx.type_of(impl_did).subst(infcx.tcx, impl_substs);
+        if infcx.at(&cause, param_env).eq(impl_ty, ty).is_err() {
+            return false;
+        }
+
+        let predicates = tcx.predicates_of(impl_did).instantiate(infcx.tcx, impl_substs);
+        predicates.predicates.into_iter().all(|predicate| {
+            let predicate = infcx.resolve_type_vars_if_possible(&predicate);
+            selcx.evaluate_obligation(&Obligation::new(cause.clone(), param_env, predicate))
+        })
+    })
+}
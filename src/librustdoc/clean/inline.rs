@@ -60,11 +60,13 @@ pub fn try_inline(cx: &DocContext, def: Def, name: ast::Name)
         Def::Struct(did) => {
             record_extern_fqn(cx, did, clean::TypeKind::Struct);
             ret.extend(build_impls(cx, did));
+            ret.extend(clean::get_synthetic_impls(cx, did));
             clean::StructItem(build_struct(cx, did))
         }
         Def::Union(did) => {
             record_extern_fqn(cx, did, clean::TypeKind::Union);
             ret.extend(build_impls(cx, did));
+            ret.extend(clean::get_synthetic_impls(cx, did));
             clean::UnionItem(build_union(cx, did))
         }
         Def::TyAlias(did) => {
@@ -75,6 +77,7 @@ pub fn try_inline(cx: &DocContext, def: Def, name: ast::Name)
         Def::Enum(did) => {
             record_extern_fqn(cx, did, clean::TypeKind::Enum);
             ret.extend(build_impls(cx, did));
+            ret.extend(clean::get_synthetic_impls(cx, did));
             clean::EnumItem(build_enum(cx, did))
         }
         Def::TyForeign(did) => {
@@ -366,6 +369,8 @@ pub fn build_impl(cx: &DocContext, did: DefId, ret: &mut Vec<clean::Item>) {
             generics: (tcx.generics_of(did), &predicates).clean(cx),
             items: trait_items,
             polarity: Some(polarity.clean(cx)),
+            synthetic: false,
+            blanket_impl: None,
         }),
         source: tcx.def_span(did).clean(cx),
         name: None,
@@ -380,23 +385,38 @@ pub fn build_impl(cx: &DocContext, did: DefId, ret: &mut Vec<clean::Item>) {
 fn build_module(cx: &DocContext, did: DefId) -> clean::Module {
     let mut items = Vec::new();
     fill_in(cx, did, &mut items);
-    return clean::Module {
+    clean::Module {
         items,
         is_crate: false,
-    };
+    }
+}
+
+/// Inlines the public items of a module from another crate which is glob
+/// re-exported, as in `pub use other::module::*`.
+///
+/// Returns `None` if `def` isn't a module of another crate.
+pub fn try_inline_glob(cx: &DocContext, def: Def) -> Option<Vec<clean::Item>> {
+    match def {
+        Def::Mod(did) if !did.is_local() => {
+            let mut items = Vec::new();
+            fill_in(cx, did, &mut items);
+            Some(items)
+        }
+        _ => None,
+    }
+}
 
-    fn fill_in(cx: &DocContext, did: DefId, items: &mut Vec<clean::Item>) {
-        // If we're reexporting a reexport it may actually reexport something in
-        // two namespaces, so the target may be listed twice. Make sure we only
-        // visit each node at most once.
-        let mut visited = FxHashSet();
-        for &item in cx.tcx.item_children(did).iter() {
-            let def_id = item.def.def_id();
-            if item.vis == ty::Visibility::Public {
-                if !visited.insert(def_id) { continue }
-                if let Some(i) = try_inline(cx, item.def, item.ident.name) {
-                    items.extend(i)
-                }
+fn fill_in(cx: &DocContext, did: DefId, items: &mut Vec<clean::Item>) {
+    // If we're reexporting a reexport it may actually reexport something in
+    // two namespaces, so the target may be listed twice. Make sure we only
+    // visit each node at most once.
+    let mut visited = FxHashSet();
+    for &item in cx.tcx.item_children(did).iter() {
+        let def_id = item.def.def_id();
+        if item.vis == ty::Visibility::Public {
+            if !visited.insert(def_id) { continue }
+            if let Some(i) = try_inline(cx, item.def, item.ident.name) {
+                items.extend(i)
             }
         }
     }
@@ -0,0 +1,351 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Intra-doc links: links in doc comments whose target is the path to an
+//! item, like `[Vec]`, `[`Vec::push`]` or `[the macro](macro@vec)`.
+//!
+//! Paths are resolved relative to the module the docs are written in, which
+//! needs the resolver. That is gone once the crate is analyzed, so all the
+//! links of the crate are resolved right after expansion, and looked up by
+//! the span of the docs while the crate gets cleaned.
+
+use rustc::hir::def::Def;
+use rustc::hir::def_id::DefId;
+use rustc::session::Session;
+use rustc::ty;
+use rustc::util::nodemap::FxHashMap;
+use rustc_resolve::{Namespace, Resolver};
+use rustc_resolve::Namespace::*;
+use syntax::ast::{self, NodeId, CRATE_NODE_ID};
+use syntax::symbol::Symbol;
+use syntax::visit::{self, Visitor};
+use syntax_pos::Span;
+
+use core::DocContext;
+use html::item_type::ItemType;
+use html::markdown::markdown_links;
+
+use super::{inline, register_def, TypeKind};
+
+/// An intra-doc link which resolved to an item.
+#[derive(Clone, Debug)]
+pub struct DocLink {
+    /// The target of the link as written in the docs.
+    pub target: String,
+    pub def: Def,
+    /// The associated item or field of `def` the link points to, if any.
+    pub assoc: Option<ast::Name>,
+}
+
+/// The intra-doc links of a crate, keyed by the span of the first `doc`
+/// attribute of the docs they are in.
+pub type DocLinks = FxHashMap<Span, Vec<DocLink>>;
+
+/// Resolves the intra-doc links in all the docs of the crate, warning about
+/// links which look like paths but can't be resolved.
+pub fn resolve_doc_links(sess: &Session, krate: &ast::Crate, resolver: &mut Resolver)
+                         -> DocLinks {
+    let mut collector = LinkCollector {
+        sess,
+        resolver,
+        modules: vec![CRATE_NODE_ID],
+        links: FxHashMap(),
+    };
+    collector.collect(&krate.attrs, CRATE_NODE_ID);
+    visit::walk_crate(&mut collector, krate);
+    collector.links
+}
+
+/// Finds the page and the anchor on it which a resolved link points to,
+/// recording the paths of items from other crates on the way.
+pub fn link_target(cx: &DocContext, link: &DocLink) -> Option<(DefId, Option<String>)> {
+    let tcx = cx.tcx;
+    if let Some(name) = link.assoc {
+        return assoc_target(cx, link.def, name);
+    }
+
+    match link.def {
+        Def::Variant(did) | Def::VariantCtor(did, _) => {
+            let enum_did = register_def(cx, Def::Enum(tcx.parent_def_id(did)?));
+            Some((enum_did, Some(format!("{}.{}", ItemType::Variant, tcx.item_name(did)))))
+        }
+        Def::StructCtor(did, _) => {
+            Some((register_def(cx, Def::Struct(tcx.parent_def_id(did)?)), None))
+        }
+        Def::Method(did) | Def::AssociatedConst(did) | Def::AssociatedTy(did) => {
+            let item = tcx.associated_item(did);
+            match item.container {
+                ty::TraitContainer(trait_did) => {
+                    Some((register_def(cx, Def::Trait(trait_did)), Some(assoc_fragment(&item))))
+                }
+                ty::ImplContainer(_) => None,
+            }
+        }
+        Def::Const(did) => {
+            if !did.is_local() {
+                inline::record_extern_fqn(cx, did, TypeKind::Const);
+            }
+            Some((did, None))
+        }
+        Def::Macro(did, _) => Some((did, None)),
+        Def::Mod(..) | Def::Struct(..) | Def::Union(..) | Def::Enum(..) | Def::Trait(..) |
+        Def::TyAlias(..) | Def::TyForeign(..) | Def::Fn(..) | Def::Static(..) => {
+            Some((register_def(cx, link.def), None))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the associated item or field `name` of a trait or type.
+fn assoc_target(cx: &DocContext, def: Def, name: ast::Name)
+                -> Option<(DefId, Option<String>)> {
+    let tcx = cx.tcx;
+    match def {
+        Def::Trait(did) => {
+            let item = tcx.associated_items(did).find(|item| item.name == name)?;
+            Some((register_def(cx, def), Some(assoc_fragment(&item))))
+        }
+        Def::Struct(did) | Def::Union(did) | Def::Enum(did) => {
+            let item = tcx.inherent_impls(did).iter()
+                          .flat_map(|&impl_did| tcx.associated_items(impl_did))
+                          .find(|item| item.name == name);
+            if let Some(item) = item {
+                return Some((register_def(cx, def), Some(assoc_fragment(&item))));
+            }
+            if let Def::Enum(_) = def {
+                return None;
+            }
+            let field = tcx.adt_def(did).struct_variant().fields.iter()
+                           .find(|field| field.name == name)?;
+            Some((register_def(cx, def),
+                  Some(format!("{}.{}", ItemType::StructField, field.name))))
+        }
+        _ => None,
+    }
+}
+
+/// The anchor of an associated item on the page of its trait or type.
+fn assoc_fragment(item: &ty::AssociatedItem) -> String {
+    let item_type = match item.kind {
+        ty::AssociatedKind::Method => match item.container {
+            ty::TraitContainer(_) if !item.defaultness.has_value() => ItemType::TyMethod,
+            _ => ItemType::Method,
+        },
+        ty::AssociatedKind::Const => ItemType::AssociatedConst,
+        ty::AssociatedKind::Type => ItemType::AssociatedType,
+    };
+    format!("{}.{}", item_type, item.name)
+}
+
+struct LinkCollector<'a, 'b: 'a> {
+    sess: &'a Session,
+    resolver: &'a mut Resolver<'b>,
+    /// The modules the visitor is in, innermost last.
+    modules: Vec<NodeId>,
+    links: DocLinks,
+}
+
+impl<'a, 'b> LinkCollector<'a, 'b> {
+    fn current_module(&self) -> NodeId {
+        *self.modules.last().unwrap()
+    }
+
+    /// Resolves the links in the docs of `attrs`, relative to the module
+    /// `scope`.
+    fn collect(&mut self, attrs: &[ast::Attribute], scope: NodeId) {
+        let mut span = None;
+        let mut docs = String::new();
+        for attr in attrs {
+            attr.with_desugared_doc(|attr| {
+                if !attr.check_name("doc") {
+                    return;
+                }
+                if let Some(value) = attr.value_str() {
+                    span = span.or(Some(attr.span));
+                    docs.push_str(&value.as_str());
+                    docs.push('\n');
+                }
+            });
+        }
+        let span = match span {
+            Some(span) => span,
+            None => return,
+        };
+
+        let mut links = vec![];
+        for link in markdown_links(&docs) {
+            let (path, disambiguator, ns) = match parse_link(link.target) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+            let namespaces = match ns {
+                Some(ns) => vec![ns],
+                None => vec![TypeNS, ValueNS, MacroNS],
+            };
+            let resolved = namespaces.into_iter().filter_map(|ns| {
+                self.resolve(scope, path, ns)
+            }).find(|&(def, assoc)| {
+                assoc.is_some() || disambiguator.map_or(true, |kind| kind_matches(kind, def))
+            });
+
+            match resolved {
+                Some((def, assoc)) => {
+                    links.push(DocLink { target: link.target.to_string(), def, assoc });
+                }
+                None if !link.shortcut || looks_like_code(link.target) => {
+                    self.sess.span_warn(span, &format!("`[{}]` cannot be resolved, ignoring it",
+                                                       link.target));
+                }
+                None => {}
+            }
+        }
+        if !links.is_empty() {
+            self.links.insert(span, links);
+        }
+    }
+
+    /// Resolves `path` in namespace `ns`, allowing one trailing segment to
+    /// name an associated item or field.
+    fn resolve(&mut self, scope: NodeId, path: &str, ns: Namespace)
+               -> Option<(Def, Option<ast::Name>)> {
+        let resolved = match self.resolver.resolve_str_path(scope, path, ns) {
+            Some(resolved) => resolved,
+            // `macro_rules!` macros aren't in any module
+            None if ns == MacroNS && !path.contains("::") => {
+                (*self.resolver.all_macros.get(&Symbol::intern(path))?, 0)
+            }
+            None => return None,
+        };
+        match resolved {
+            (def, 0) => Some((def, None)),
+            (def, 1) => Some((def, Some(Symbol::intern(path.rsplit("::").next().unwrap())))),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'b, 'ast> Visitor<'ast> for LinkCollector<'a, 'b> {
+    fn visit_item(&mut self, item: &'ast ast::Item) {
+        if let ast::ItemKind::Mod(..) = item.node {
+            // Inner docs are written inside the module, outer docs outside
+            // of it; let inner docs decide where paths are resolved from.
+            let inner = item.attrs.iter().any(|attr| {
+                attr.style == ast::AttrStyle::Inner && attr.check_name("doc")
+            });
+            let scope = if inner { item.id } else { self.current_module() };
+            self.collect(&item.attrs, scope);
+
+            self.modules.push(item.id);
+            visit::walk_item(self, item);
+            self.modules.pop();
+            return;
+        }
+
+        let scope = self.current_module();
+        self.collect(&item.attrs, scope);
+        match item.node {
+            // Items in function bodies aren't documented
+            ast::ItemKind::Fn(..) | ast::ItemKind::Const(..) | ast::ItemKind::Static(..) => {}
+            _ => visit::walk_item(self, item),
+        }
+    }
+
+    fn visit_foreign_item(&mut self, item: &'ast ast::ForeignItem) {
+        let scope = self.current_module();
+        self.collect(&item.attrs, scope);
+    }
+
+    fn visit_trait_item(&mut self, item: &'ast ast::TraitItem) {
+        let scope = self.current_module();
+        self.collect(&item.attrs, scope);
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast ast::ImplItem) {
+        let scope = self.current_module();
+        self.collect(&item.attrs, scope);
+    }
+
+    fn visit_variant(&mut self, variant: &'ast ast::Variant, generics: &'ast ast::Generics,
+                     item_id: NodeId) {
+        let scope = self.current_module();
+        self.collect(&variant.node.attrs, scope);
+        visit::walk_variant(self, variant, generics, item_id);
+    }
+
+    fn visit_struct_field(&mut self, field: &'ast ast::StructField) {
+        let scope = self.current_module();
+        self.collect(&field.attrs, scope);
+    }
+
+    fn visit_mac(&mut self, _mac: &'ast ast::Mac) {
+        // Any macro invocations left after expansion have nothing to document
+    }
+}
+
+/// Splits a link target into a path, the kind of item it was disambiguated
+/// with and the namespace it should be resolved in. Returns `None` if the
+/// target isn't a path at all, like URLs.
+fn parse_link(target: &str) -> Option<(&str, Option<&str>, Option<Namespace>)> {
+    let mut path = target.trim_matches('`');
+    let mut disambiguator = None;
+    let mut ns = None;
+
+    if let Some(at) = path.find('@') {
+        let kind = &path[..at];
+        ns = Some(match kind {
+            "struct" | "enum" | "trait" | "union" | "mod" | "module" | "type" => TypeNS,
+            "fn" | "function" | "method" | "const" | "constant" | "static" | "value" => ValueNS,
+            "macro" => MacroNS,
+            _ => return None,
+        });
+        disambiguator = Some(kind);
+        path = &path[at + 1..];
+    }
+    if path.ends_with("()") {
+        path = &path[..path.len() - 2];
+        ns = ns.or(Some(ValueNS));
+    } else if path.ends_with('!') {
+        path = &path[..path.len() - 1];
+        ns = ns.or(Some(MacroNS));
+    }
+
+    let valid = path.trim_left_matches("::").split("::").all(|segment| {
+        segment.chars().next().map_or(false, |c| c.is_alphabetic() || c == '_') &&
+            segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    });
+    if valid { Some((path, disambiguator, ns)) } else { None }
+}
+
+/// Whether a shortcut link label looks like it was meant to be a path, so
+/// failing to resolve it is worth a warning.
+fn looks_like_code(target: &str) -> bool {
+    target.contains('`') || target.contains("::") || target.contains('@') ||
+        target.ends_with("()") || target.ends_with('!')
+}
+
+fn kind_matches(kind: &str, def: Def) -> bool {
+    match (kind, def) {
+        ("struct", Def::Struct(..)) |
+        ("enum", Def::Enum(..)) |
+        ("trait", Def::Trait(..)) |
+        ("union", Def::Union(..)) |
+        ("mod", Def::Mod(..)) | ("module", Def::Mod(..)) |
+        ("type", Def::TyAlias(..)) | ("type", Def::TyForeign(..)) |
+        ("type", Def::AssociatedTy(..)) |
+        ("fn", Def::Fn(..)) | ("function", Def::Fn(..)) | ("fn", Def::Method(..)) |
+        ("method", Def::Method(..)) |
+        ("const", Def::Const(..)) | ("constant", Def::Const(..)) |
+        ("const", Def::AssociatedConst(..)) | ("constant", Def::AssociatedConst(..)) |
+        ("static", Def::Static(..)) |
+        ("value", _) |
+        ("macro", Def::Macro(..)) => true,
+        _ => false,
+    }
+}
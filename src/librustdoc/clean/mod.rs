@@ -56,6 +56,9 @@ use html::item_type::ItemType;
 
 pub mod inline;
 pub mod cfg;
+pub mod links;
+mod auto_trait;
+mod blanket_impl;
 mod simplify;
 
 use self::cfg::Cfg;
@@ -305,6 +308,10 @@ impl Item {
     pub fn collapsed_doc_value(&self) -> Option<String> {
         self.attrs.collapsed_doc_value()
     }
+    /// Gets the intra-doc links of this item's docs, see `Attributes::links`.
+    pub fn links(&self) -> Vec<(String, String)> {
+        self.attrs.links()
+    }
     pub fn is_crate(&self) -> bool {
         match self.inner {
             StrippedItem(box ModuleItem(Module { is_crate: true, ..})) |
@@ -472,6 +479,12 @@ impl Clean<Item> for doctree::Module {
         items.extend(self.structs.iter().map(|x| x.clean(cx)));
         items.extend(self.unions.iter().map(|x| x.clean(cx)));
         items.extend(self.enums.iter().map(|x| x.clean(cx)));
+        let type_ids = self.structs.iter().map(|x| x.id)
+                           .chain(self.unions.iter().map(|x| x.id))
+                           .chain(self.enums.iter().map(|x| x.id));
+        for id in type_ids {
+            items.extend(get_synthetic_impls(cx, cx.tcx.hir.local_def_id(id)));
+        }
         items.extend(self.fns.iter().map(|x| x.clean(cx)));
         items.extend(self.foreigns.iter().flat_map(|x| x.clean(cx)));
         items.extend(self.mods.iter().map(|x| x.clean(cx)));
@@ -635,6 +648,12 @@ pub struct Attributes {
     pub other_attrs: Vec<ast::Attribute>,
     pub cfg: Option<Rc<Cfg>>,
     pub span: Option<syntax_pos::Span>,
+    /// Where the docs start in the source, for diagnostics once the compiler
+    /// is gone, like those of `--check-links`.
+    pub doc_span: Option<Span>,
+    /// The resolved intra-doc links, as the link target in the docs, the item
+    /// it points to and the anchor of an associated item or field on its page.
+    pub links: Vec<(String, DefId, Option<String>)>,
 }
 
 impl Attributes {
@@ -769,6 +788,8 @@ impl Attributes {
             other_attrs,
             cfg: if cfg == Cfg::True { None } else { Some(Rc::new(cfg)) },
             span: sp,
+            doc_span: None,
+            links: vec![],
         }
     }
 
@@ -787,6 +808,22 @@ impl Attributes {
             None
         }
     }
+
+    /// Gets the intra-doc links of these docs as pairs of the link target and
+    /// the URL it points to. Links to items which aren't documented anywhere
+    /// are left out.
+    pub fn links(&self) -> Vec<(String, String)> {
+        use html::format::href;
+
+        self.links.iter().filter_map(|&(ref target, did, ref fragment)| {
+            let (mut url, _, _) = href(did)?;
+            if let Some(ref fragment) = *fragment {
+                url.push('#');
+                url.push_str(fragment);
+            }
+            Some((target.clone(), url))
+        }).collect()
+    }
 }
 
 impl AttributesExt for Attributes {
@@ -797,7 +834,15 @@ impl AttributesExt for Attributes {
 
 impl Clean<Attributes> for [ast::Attribute] {
     fn clean(&self, cx: &DocContext) -> Attributes {
-        Attributes::from_ast(cx.sess().diagnostic(), self)
+        let mut attrs = Attributes::from_ast(cx.sess().diagnostic(), self);
+        attrs.doc_span = attrs.span.map(|span| span.clean(cx));
+        if let Some(doc_links) = attrs.span.and_then(|span| cx.doc_links.get(&span)) {
+            attrs.links = doc_links.iter().filter_map(|link| {
+                let (did, fragment) = links::link_target(cx, link)?;
+                Some((link.target.clone(), did, fragment))
+            }).collect();
+        }
+        attrs
     }
 }
 
@@ -2535,7 +2580,7 @@ impl Clean<VariantKind> for hir::VariantData {
     }
 }
 
-#[derive(Clone, RustcEncodable, RustcDecodable, Debug)]
+#[derive(Clone, RustcEncodable, RustcDecodable, PartialEq, Debug)]
 pub struct Span {
     pub filename: FileName,
     pub loline: usize,
@@ -2826,6 +2871,12 @@ pub struct Impl {
     pub for_: Type,
     pub items: Vec<Item>,
     pub polarity: Option<ImplPolarity>,
+    /// Whether this is an impl of an auto trait which the compiler provides
+    /// rather than one written in the source
+    pub synthetic: bool,
+    /// For a blanket impl shown on the page of a type it applies to, the
+    /// generic type the impl is written for
+    pub blanket_impl: Option<Type>,
 }
 
 impl Clean<Vec<Item>> for doctree::Impl {
@@ -2863,12 +2914,22 @@ impl Clean<Vec<Item>> for doctree::Impl {
                 for_: self.for_.clean(cx),
                 items,
                 polarity: Some(self.polarity.clean(cx)),
+                synthetic: false,
+                blanket_impl: None,
             }),
         });
         ret
     }
 }
 
+/// The impls of auto traits and the blanket impls which apply to the struct,
+/// enum or union `did`, which aren't written for it in the source.
+pub fn get_synthetic_impls(cx: &DocContext, did: DefId) -> Vec<Item> {
+    let mut impls = auto_trait::get_auto_trait_impls(cx, did);
+    impls.extend(blanket_impl::get_blanket_impls(cx, did));
+    impls
+}
+
 fn build_deref_target_impls(cx: &DocContext,
                             items: &[Item],
                             ret: &mut Vec<Item>) {
@@ -2980,6 +3041,11 @@ impl Clean<Vec<Item>> for doctree::Import {
         });
         let path = self.path.clean(cx);
         let inner = if self.glob {
+            if !denied {
+                if let Some(items) = inline::try_inline_glob(cx, path.def) {
+                    return items;
+                }
+            }
             Import::Glob(resolve_use_source(cx, path))
         } else {
             let name = self.name;
@@ -11,6 +11,7 @@
 use rustc_lint;
 use rustc_driver::{driver, target_features, abort_on_err};
 use rustc::session::{self, config};
+use rustc::session::config::ErrorOutputType;
 use rustc::hir::def_id::DefId;
 use rustc::hir::def::Def;
 use rustc::middle::privacy::AccessLevels;
@@ -21,12 +22,15 @@ use rustc::util::nodemap::FxHashMap;
 use rustc_trans;
 use rustc_trans::back::link;
 use rustc_resolve as resolve;
+use rustc_metadata::creader::CrateLoader;
 use rustc_metadata::cstore::CStore;
 
 use syntax::codemap;
 use syntax::feature_gate::UnstableFeatures;
+use syntax::json::JsonEmitter;
 use errors;
-use errors::emitter::ColorConfig;
+use errors::CodeMapper;
+use errors::emitter::{Emitter, EmitterWriter};
 
 use std::cell::{RefCell, Cell};
 use std::mem;
@@ -37,6 +41,7 @@ use visit_ast::RustdocVisitor;
 use clean;
 use clean::Clean;
 use html::render::RenderInfo;
+use scrape_examples;
 use arena::DroplessArena;
 
 pub use rustc::session::config::Input;
@@ -47,6 +52,9 @@ pub type ExternalPaths = FxHashMap<DefId, (Vec<String>, clean::TypeKind)>;
 pub struct DocContext<'a, 'tcx: 'a> {
     pub tcx: TyCtxt<'a, 'tcx, 'tcx>,
     pub populated_all_crate_impls: Cell<bool>,
+    /// The trait impls of all the crates for a generic type, found when first
+    /// needed by `clean::blanket_impl`
+    pub blanket_impls: RefCell<Option<Vec<DefId>>>,
     // Note that external items for which `doc(hidden)` applies to are shown as
     // non-reachable while local items aren't. This is because we're reusing
     // the access levels from crateanalysis.
@@ -64,6 +72,8 @@ pub struct DocContext<'a, 'tcx: 'a> {
     pub ty_substs: RefCell<FxHashMap<Def, clean::Type>>,
     /// Table node id of lifetime parameter definition -> substituted lifetime
     pub lt_substs: RefCell<FxHashMap<DefId, clean::Lifetime>>,
+    /// The intra-doc links of the crate, resolved before analysis
+    pub doc_links: clean::links::DocLinks,
 }
 
 impl<'a, 'tcx> DocContext<'a, 'tcx> {
@@ -99,6 +109,27 @@ impl DocAccessLevels for AccessLevels<DefId> {
 }
 
 
+/// Creates a new diagnostic `Handler` emitting diagnostics in the given
+/// format, using `codemap` to display spans if there is one.
+pub fn new_handler(error_format: ErrorOutputType, codemap: Option<Rc<codemap::CodeMap>>)
+                   -> errors::Handler {
+    let emitter: Box<Emitter> = match error_format {
+        ErrorOutputType::HumanReadable(color_config) => {
+            box EmitterWriter::stderr(color_config, codemap.map(|cm| cm as Rc<CodeMapper>), false)
+        }
+        ErrorOutputType::Json(pretty) => {
+            let codemap = codemap.unwrap_or_else(|| {
+                Rc::new(codemap::CodeMap::new(codemap::FilePathMapping::empty()))
+            });
+            box JsonEmitter::stderr(None, codemap, pretty)
+        }
+        ErrorOutputType::Short(color_config) => {
+            box EmitterWriter::stderr(color_config, codemap.map(|cm| cm as Rc<CodeMapper>), true)
+        }
+    };
+    errors::Handler::with_emitter(true, false, emitter)
+}
+
 pub fn run_core(search_paths: SearchPaths,
                 cfgs: Vec<String>,
                 externs: config::Externs,
@@ -106,7 +137,9 @@ pub fn run_core(search_paths: SearchPaths,
                 triple: Option<String>,
                 maybe_sysroot: Option<PathBuf>,
                 allow_warnings: bool,
-                force_unstable_if_unmarked: bool) -> (clean::Crate, RenderInfo)
+                error_format: ErrorOutputType,
+                debugging_options: config::DebuggingOptions,
+                scrape_examples: scrape_examples::Options) -> (clean::Crate, RenderInfo)
 {
     // Parse, resolve, and typecheck the given crate.
 
@@ -128,18 +161,13 @@ pub fn run_core(search_paths: SearchPaths,
         // Ensure that rustdoc works even if rustc is feature-staged
         unstable_features: UnstableFeatures::Allow,
         actually_rustdoc: true,
-        debugging_opts: config::DebuggingOptions {
-            force_unstable_if_unmarked,
-            ..config::basic_debugging_options()
-        },
+        error_format,
+        debugging_opts: debugging_options,
         ..config::basic_options().clone()
     };
 
     let codemap = Rc::new(codemap::CodeMap::new(sessopts.file_path_mapping()));
-    let diagnostic_handler = errors::Handler::with_tty_emitter(ColorConfig::Auto,
-                                                               true,
-                                                               false,
-                                                               Some(codemap.clone()));
+    let diagnostic_handler = new_handler(error_format, Some(codemap.clone()));
 
     let cstore = Rc::new(CStore::new(box rustc_trans::LlvmMetadataLoader));
     let mut sess = session::build_session_(
@@ -158,18 +186,30 @@ pub fn run_core(search_paths: SearchPaths,
 
     let name = link::find_crate_name(Some(&sess), &krate.attrs, &input);
 
-    let driver::ExpansionResult { defs, analysis, resolutions, mut hir_forest, .. } = {
-        let result = driver::phase_2_configure_and_expand(&sess,
-                                                          &cstore,
-                                                          krate,
-                                                          None,
-                                                          &name,
-                                                          None,
-                                                          resolve::MakeGlobMap::No,
-                                                          |_| Ok(()));
+    let mut crate_loader = CrateLoader::new(&sess, &cstore, &name);
+    let resolver_arenas = resolve::Resolver::arenas();
+    let mut expansion = {
+        let result = driver::phase_2_configure_and_expand_inner(&sess,
+                                                                &cstore,
+                                                                krate,
+                                                                None,
+                                                                &name,
+                                                                None,
+                                                                resolve::MakeGlobMap::No,
+                                                                &resolver_arenas,
+                                                                &mut crate_loader,
+                                                                |_| Ok(()));
         abort_on_err(result, &sess)
     };
 
+    // Intra-doc links need the resolver, which is gone by the time the crate
+    // gets cleaned, so resolve them all up front.
+    let doc_links = clean::links::resolve_doc_links(&sess,
+                                                    &expansion.expanded_crate,
+                                                    &mut expansion.resolver);
+    let driver::ExpansionResult { defs, analysis, resolutions, mut hir_forest, .. } =
+        expansion.into_expansion_result(&name);
+
     let arena = DroplessArena::new();
     let arenas = GlobalArenas::new();
     let hir_map = hir_map::map_crate(&sess, &*cstore, &mut hir_forest, &defs);
@@ -207,20 +247,37 @@ pub fn run_core(search_paths: SearchPaths,
         let ctxt = DocContext {
             tcx,
             populated_all_crate_impls: Cell::new(false),
+            blanket_impls: RefCell::new(None),
             access_levels: RefCell::new(access_levels),
             external_traits: Default::default(),
             renderinfo: Default::default(),
             ty_substs: Default::default(),
             lt_substs: Default::default(),
+            doc_links,
         };
         debug!("crate: {:?}", tcx.hir.krate());
 
+        if let Some(ref output_path) = scrape_examples.output_path {
+            if let Err(e) = scrape_examples::scrape(tcx, &scrape_examples, output_path) {
+                sess.fatal(&format!("failed to write scraped examples to {}: {}",
+                                    output_path.display(), e));
+            }
+        }
+
         let krate = {
             let mut v = RustdocVisitor::new(&*cstore, &ctxt);
             v.visit(tcx.hir.krate());
             v.clean(&ctxt)
         };
 
-        (krate, ctxt.renderinfo.into_inner())
+        let mut renderinfo = ctxt.renderinfo.into_inner();
+        if !scrape_examples.with_examples.is_empty() {
+            match scrape_examples::load(tcx, &scrape_examples, &krate) {
+                Ok(examples) => renderinfo.scraped_examples = examples,
+                Err(e) => sess.fatal(&e),
+            }
+        }
+
+        (krate, renderinfo)
     }), &sess)
 }
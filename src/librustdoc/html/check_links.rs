@@ -0,0 +1,156 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checking the links of the documentation of a crate once it is rendered,
+//! with `--check-links`.
+//!
+//! The pages are checked with the `linkchecker` tool's library. A broken link
+//! is reported as an error where it is written in the docs of the crate when it
+//! can be found there, and otherwise where it is in the generated page.
+
+use std::path::{Path, PathBuf};
+
+use linkchecker::{Checker, LinkError, LinkErrorKind};
+use rustc::util::nodemap::FxHashMap;
+
+use clean;
+use html::markdown::markdown_links;
+
+/// Where the links written in the docs of a crate are.
+pub struct DocLinkSources {
+    /// The directory of the pages the docs are shown on, relative to the
+    /// output directory, and the span of the line of the docs the link is
+    /// on, by link target
+    links: FxHashMap<String, Vec<(PathBuf, clean::Span)>>,
+}
+
+impl DocLinkSources {
+    pub fn collect(krate: &clean::Crate) -> DocLinkSources {
+        let mut sources = DocLinkSources { links: FxHashMap() };
+        if let Some(ref module) = krate.module {
+            sources.collect_item(module, &PathBuf::from(&krate.name));
+        }
+        sources
+    }
+
+    fn collect_item(&mut self, item: &clean::Item, dir: &Path) {
+        let dir = match item.inner {
+            clean::StrippedItem(..) => return,
+            // A module's docs are on its index page, in its own directory
+            clean::ModuleItem(..) => match item.name {
+                Some(ref name) if !name.is_empty() => dir.join(name),
+                _ => dir.to_path_buf(),
+            },
+            _ => dir.to_path_buf(),
+        };
+
+        let span = item.attrs.doc_span.as_ref();
+        if let (Some(docs), Some(span)) = (item.collapsed_doc_value(), span) {
+            for link in markdown_links(&docs) {
+                if !link.shortcut {
+                    // Each line of the docs comes from a line of their source
+                    let mut span = span.clone();
+                    span.loline += link.line(&docs);
+                    span.hiline = span.loline;
+                    self.links.entry(link.target.to_string())
+                              .or_insert_with(Vec::new)
+                              .push((dir.clone(), span));
+                }
+            }
+        }
+
+        let children = match item.inner {
+            clean::ModuleItem(ref m) => &m.items,
+            clean::StructItem(ref s) => &s.fields,
+            clean::UnionItem(ref u) => &u.fields,
+            clean::EnumItem(ref e) => &e.variants,
+            clean::TraitItem(ref t) => &t.items,
+            clean::ImplItem(ref i) => &i.items,
+            _ => return,
+        };
+        for child in children {
+            self.collect_item(child, &dir);
+        }
+    }
+
+    /// The spans of the docs `url` is written in, as it appears on a page in
+    /// `dir`. The docs of the items shown on pages in `dir` are looked at
+    /// first, as relative links mean something else elsewhere.
+    fn find(&self, url: &str, dir: &Path) -> Vec<&clean::Span> {
+        let sources = match self.links.get(url) {
+            Some(sources) => sources,
+            None => return vec![],
+        };
+        let in_dir = sources.iter().filter(|&&(ref d, _)| d == dir)
+                                   .map(|&(_, ref span)| span)
+                                   .collect::<Vec<_>>();
+        if !in_dir.is_empty() {
+            return in_dir;
+        }
+        sources.iter().map(|&(_, ref span)| span).collect()
+    }
+}
+
+/// Checks the links on the pages of `crate_name` in `dst`, reporting the ones
+/// which point to pages or anchors of the crate which don't exist. Returns the
+/// number of broken links.
+pub fn check(dst: &Path, crate_name: &str, sources: &DocLinkSources) -> usize {
+    let crate_dir = dst.join(crate_name);
+    if !crate_dir.is_dir() {
+        return 0;
+    }
+    let mut errors = Vec::new();
+    Checker::new(dst).walk(&crate_dir, &mut |_| false, &mut errors);
+
+    let src_dir = Path::new("src").join(crate_name);
+    let mut broken = 0;
+    for error in &errors {
+        // Links to other crates' docs or to the static files are left to
+        // whoever puts those in place
+        if let Some(target) = error.target() {
+            if !target.starts_with(crate_name) && !target.starts_with(&src_dir) {
+                continue;
+            }
+        }
+        report(error, sources);
+        broken += 1;
+    }
+    broken
+}
+
+fn report(error: &LinkError, sources: &DocLinkSources) {
+    let url = match error.url() {
+        Some(url) => url.replace("&amp;", "&"),
+        None => return eprintln!("error: {}", error),
+    };
+    let dir = error.file.parent().unwrap_or(Path::new(""));
+    let spans = sources.find(&url, dir);
+    if spans.is_empty() {
+        return eprintln!("error: {}", error);
+    }
+
+    let problem = match error.kind {
+        LinkErrorKind::DuplicateId(..) => unreachable!(),
+        LinkErrorKind::DirectoryLink { .. } => "it points to a directory".to_string(),
+        LinkErrorKind::BrokenRedirect { ref target, .. } => {
+            format!("it is redirected to `{}`, which doesn't exist", target.display())
+        }
+        LinkErrorKind::BrokenFragment { ref fragment, ref target, .. } => {
+            format!("`{}` has no `#{}` anchor", target.display(), fragment)
+        }
+        LinkErrorKind::BrokenLink { ref target, .. } => {
+            format!("`{}` doesn't exist", target.display())
+        }
+    };
+    for span in spans {
+        eprintln!("error: broken link `{}` in the documentation: {}", url, problem);
+        eprintln!("  --> {}:{}:{}", span.filename, span.loline, span.locol + 1);
+    }
+}
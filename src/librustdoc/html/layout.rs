@@ -10,6 +10,7 @@
 
 use std::fmt;
 use std::io;
+use std::path::PathBuf;
 
 use externalfiles::ExternalHtml;
 
@@ -31,9 +32,14 @@ pub struct Page<'a> {
 
 pub fn render<T: fmt::Display, S: fmt::Display>(
     dst: &mut io::Write, layout: &Layout, page: &Page, sidebar: &S, t: &T,
-    css_file_extension: bool)
+    css_file_extension: bool, themes: &[PathBuf])
     -> io::Result<()>
 {
+    // The built-in theme is `main.css`, the others are named after their files
+    let mut theme_names = vec!["main".to_owned()];
+    theme_names.extend(themes.iter().filter_map(|theme| theme.file_stem())
+                                    .map(|stem| stem.to_string_lossy().into_owned()));
+
     write!(dst,
 r##"<!DOCTYPE html>
 <html lang="en">
@@ -47,9 +53,11 @@ r##"<!DOCTYPE html>
     <title>{title}</title>
 
     <link rel="stylesheet" type="text/css" href="{root_path}normalize.css">
-    <link rel="stylesheet" type="text/css" href="{root_path}rustdoc.css">
-    <link rel="stylesheet" type="text/css" href="{root_path}main.css">
+    <link rel="stylesheet" type="text/css" href="{root_path}rustdoc.css" id="mainThemeStyle">
+    <link rel="stylesheet" type="text/css" href="{root_path}main.css" id="themeStyle"
+          data-themes="{theme_names}">
     {css_extension}
+    <script src="{root_path}storage.js"></script>
 
     {favicon}
     {in_header}
@@ -71,6 +79,7 @@ r##"<!DOCTYPE html>
     </nav>
 
     <nav class="sub">
+        {theme_picker}
         <form class="search-form js-only">
             <div class="search-container">
                 <input class="search-input" name="search"
@@ -123,12 +132,15 @@ r##"<!DOCTYPE html>
                     Accepted types are: <code>fn</code>, <code>mod</code>,
                     <code>struct</code>, <code>enum</code>,
                     <code>trait</code>, <code>type</code>, <code>macro</code>,
-                    and <code>const</code>.
+                    <code>const</code>, and any other kind of item, such as
+                    <code>method</code> or <code>static</code>.
                 </p>
 
                 <p>
                     Search functions by type signature (e.g.
-                    <code>vec -> usize</code> or <code>* -> vec</code>)
+                    <code>Vec&lt;T&gt; -> usize</code>, <code>&amp;str -> Option&lt;_&gt;</code>
+                    or <code>* -> vec</code>), where <code>_</code> and type
+                    parameters like <code>T</code> match any type.
                 </p>
             </div>
         </div>
@@ -150,6 +162,18 @@ r##"<!DOCTYPE html>
     } else {
         "".to_owned()
     },
+    theme_names = theme_names.join(","),
+    theme_picker = if themes.is_empty() {
+        "".to_owned()
+    } else {
+        format!("<div class=\"theme-picker js-only\">\
+                     <button id=\"theme-picker\" aria-label=\"Pick another theme\">Theme</button>\
+                     <div id=\"theme-choices\" class=\"hidden\">{}</div>\
+                 </div>",
+                theme_names.iter()
+                           .map(|name| format!("<button data-theme=\"{0}\">{0}</button>", name))
+                           .collect::<String>())
+    },
     content   = *t,
     root_path = page.root_path,
     css_class = page.css_class,
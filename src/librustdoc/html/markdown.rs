@@ -21,7 +21,7 @@
 //! use rustdoc::html::markdown::{RenderType, Markdown};
 //!
 //! let s = "My *markdown* _text_";
-//! let html = format!("{}", Markdown(s, RenderType::Pulldown));
+//! let html = format!("{}", Markdown(s, &[], RenderType::Pulldown));
 //! // ... something using html
 //! ```
 
@@ -30,8 +30,9 @@
 use libc;
 use std::slice;
 
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::fmt::{self, Write};
 use std::str;
@@ -56,15 +57,17 @@ pub enum RenderType {
 /// A unit struct which has the `fmt::Display` trait implemented. When
 /// formatted, this struct will emit the HTML corresponding to the rendered
 /// version of the contained markdown string.
-// The second parameter is whether we need a shorter version or not.
-pub struct Markdown<'a>(pub &'a str, pub RenderType);
+///
+/// The second parameter is a list of intra-doc links, as pairs of the link
+/// target in the markdown and the URL it resolved to.
+pub struct Markdown<'a>(pub &'a str, pub &'a [(String, String)], pub RenderType);
 /// A unit struct like `Markdown`, that renders the markdown with a
 /// table of contents.
 pub struct MarkdownWithToc<'a>(pub &'a str, pub RenderType);
 /// A unit struct like `Markdown`, that renders the markdown escaping HTML tags.
 pub struct MarkdownHtml<'a>(pub &'a str, pub RenderType);
 /// A unit struct like `Markdown`, that renders only the first paragraph.
-pub struct MarkdownSummaryLine<'a>(pub &'a str);
+pub struct MarkdownSummaryLine<'a>(pub &'a str, pub &'a [(String, String)]);
 
 /// Controls whether a line will be hidden or shown in HTML output.
 ///
@@ -196,8 +199,8 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlocks<'a, I> {
                     .map(|l| map_line(l).for_code())
                     .collect::<Vec<&str>>().join("\n");
                 let krate = krate.as_ref().map(|s| &**s);
-                let test = test::make_test(&test, krate, false,
-                                           &Default::default());
+                let (test, _) = test::make_test(&test, krate, false,
+                                                &Default::default());
                 let channel = if test.contains("#![feature(") {
                     "&amp;version=nightly"
                 } else {
@@ -607,8 +610,8 @@ pub fn render(w: &mut fmt::Formatter,
                         .map(|l| map_line(l).for_code())
                         .collect::<Vec<&str>>().join("\n");
                     let krate = krate.as_ref().map(|s| &**s);
-                    let test = test::make_test(&test, krate, false,
-                                               &Default::default());
+                    let (test, _) = test::make_test(&test, krate, false,
+                                                    &Default::default());
                     let channel = if test.contains("#![feature(") {
                         "&amp;version=nightly"
                     } else {
@@ -993,10 +996,12 @@ impl LangString {
 
 impl<'a> fmt::Display for Markdown<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let Markdown(md, render_type) = *self;
+        let Markdown(md, links, render_type) = *self;
 
         // This is actually common enough to special-case
         if md.is_empty() { return Ok(()) }
+        let md = rewrite_links(md, links);
+        let md = &*md;
         if render_type == RenderType::Hoedown {
             render(fmt, md, false, 0)
         } else {
@@ -1076,9 +1081,11 @@ impl<'a> fmt::Display for MarkdownHtml<'a> {
 
 impl<'a> fmt::Display for MarkdownSummaryLine<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let MarkdownSummaryLine(md) = *self;
+        let MarkdownSummaryLine(md, links) = *self;
         // This is actually common enough to special-case
         if md.is_empty() { return Ok(()) }
+        let md = rewrite_links(md, links);
+        let md = &*md;
 
         let p = Parser::new(md);
 
@@ -1140,9 +1147,243 @@ pub fn plain_summary_line(md: &str) -> String {
     s
 }
 
+/// A link in markdown which might point to a Rust item, as found by
+/// `markdown_links`.
+pub struct MarkdownLink<'a> {
+    /// The target of the link, or the label of a shortcut reference link.
+    pub target: &'a str,
+    /// Whether this is a shortcut reference link like `[Foo]` or `[Foo][]`,
+    /// whose label has no link definition.
+    pub shortcut: bool,
+    /// The byte offset of `target` in the markdown.
+    offset: usize,
+}
+
+impl<'a> MarkdownLink<'a> {
+    /// The line of `md` this link was found on, counting from 0.
+    pub fn line(&self, md: &str) -> usize {
+        md[..self.offset].matches('\n').count()
+    }
+}
+
+/// Finds the targets of the inline links and link definitions in `md`, as
+/// well as the labels of the shortcut reference links which have no
+/// definition, all of which intra-doc links may use as paths to items.
+/// Links in code blocks and code spans are skipped.
+pub fn markdown_links(md: &str) -> Vec<MarkdownLink> {
+    let mut links = vec![];
+    let mut shortcuts = vec![];
+    let mut definitions = HashSet::new();
+
+    let mut fence = None;
+    let mut in_indented_code = false;
+    let mut after_blank = true;
+    let mut line_offset = 0;
+    for line in md.split('\n') {
+        let offset = line_offset;
+        line_offset += line.len() + 1;
+
+        let trimmed = line.trim_left();
+        let indent = line.len() - trimmed.len();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+        if trimmed.is_empty() {
+            after_blank = true;
+            continue;
+        }
+        in_indented_code = indent >= 4 && (after_blank || in_indented_code);
+        after_blank = false;
+        if in_indented_code {
+            continue;
+        }
+
+        // A link definition, `[label]: target`
+        if indent < 4 && trimmed.starts_with('[') {
+            if let Some(end) = trimmed.find("]:") {
+                definitions.insert(trimmed[1..end].to_lowercase());
+                let rest = &trimmed[end + 2..];
+                let target = rest.trim_left();
+                let target = &target[..target.find(char::is_whitespace).unwrap_or(target.len())];
+                if !target.is_empty() {
+                    links.push(MarkdownLink {
+                        target,
+                        shortcut: false,
+                        offset: offset + indent + end + 2 + (rest.len() - rest.trim_left().len()),
+                    });
+                }
+                continue;
+            }
+        }
+
+        let bytes = line.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'`' => i = skip_code_span(line, i),
+                b'[' if i == 0 || bytes[i - 1] != b'!' => {
+                    let end = match find_label_end(line, i + 1) {
+                        Some(end) => end,
+                        None => {
+                            i += 1;
+                            continue;
+                        }
+                    };
+                    let rest = &line[end + 1..];
+                    if rest.starts_with('(') {
+                        // An inline link, `[text](target)`
+                        let target = &rest[1..];
+                        let len = target.find(|c: char| c == ')' || c.is_whitespace())
+                                        .unwrap_or(target.len());
+                        if len > 0 {
+                            links.push(MarkdownLink {
+                                target: &target[..len],
+                                shortcut: false,
+                                offset: offset + end + 2,
+                            });
+                        }
+                        i = end + 2 + len;
+                    } else if rest.starts_with('[') && !rest.starts_with("[]") {
+                        // A full reference link, `[text][label]`
+                        match rest[1..].find(']') {
+                            Some(len) => {
+                                shortcuts.push(MarkdownLink {
+                                    target: &rest[1..len + 1],
+                                    shortcut: true,
+                                    offset: offset + end + 2,
+                                });
+                                i = end + len + 3;
+                            }
+                            None => i = end + 1,
+                        }
+                    } else {
+                        // A shortcut reference link, `[label]` or `[label][]`
+                        shortcuts.push(MarkdownLink {
+                            target: &line[i + 1..end],
+                            shortcut: true,
+                            offset: offset + i + 1,
+                        });
+                        i = end + 1;
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+    }
+
+    links.extend(shortcuts.into_iter().filter(|link| {
+        !link.target.is_empty() && !definitions.contains(&link.target.to_lowercase())
+    }));
+    links
+}
+
+/// Whether `md` contains a block of Rust code, which would be run as a
+/// doctest.
+pub fn has_rust_code_block(md: &str) -> bool {
+    let mut fence = None;
+    let mut after_blank = true;
+    for line in md.lines() {
+        let trimmed = line.trim_left();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if LangString::parse(&trimmed[3..]).rust {
+                return true;
+            }
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+        // Indented code blocks are always Rust
+        if after_blank && line.len() - trimmed.len() >= 4 && !trimmed.is_empty() {
+            return true;
+        }
+        after_blank = trimmed.is_empty();
+    }
+    false
+}
+
+/// Returns the index after the code span starting at `start` in `line`, or
+/// after its opening backticks if it isn't closed.
+fn skip_code_span(line: &str, start: usize) -> usize {
+    let ticks = line[start..].find(|c| c != '`').unwrap_or(line.len() - start);
+    let mut i = start + ticks;
+    while let Some(found) = line[i..].find('`') {
+        let found = i + found;
+        let len = line[found..].find(|c| c != '`').unwrap_or(line.len() - found);
+        if len == ticks {
+            return found + len;
+        }
+        i = found + len;
+    }
+    start + ticks
+}
+
+/// Finds the `]` closing a link label which starts at `start` in `line`.
+fn find_label_end(line: &str, start: usize) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => i = skip_code_span(line, i),
+            b'[' => return None,
+            b']' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Points the links in `md` to the URLs of the intra-doc links they use,
+/// given as pairs of the link target and its URL. Shortcut reference links
+/// get a link definition appended.
+fn rewrite_links<'a>(md: &'a str, links: &[(String, String)]) -> Cow<'a, str> {
+    if links.is_empty() {
+        return Cow::Borrowed(md);
+    }
+
+    let mut out = String::with_capacity(md.len());
+    let mut definitions = String::new();
+    let mut defined = HashSet::new();
+    let mut pos = 0;
+    for link in markdown_links(md) {
+        let url = match links.iter().find(|&&(ref target, _)| target == link.target) {
+            Some(&(_, ref url)) => url,
+            None => continue,
+        };
+        if link.shortcut {
+            if defined.insert(link.target) {
+                write!(definitions, "\n[{}]: {}", link.target, url).unwrap();
+            }
+        } else {
+            out.push_str(&md[pos..link.offset]);
+            out.push_str(url);
+            pos = link.offset + link.target.len();
+        }
+    }
+    out.push_str(&md[pos..]);
+    if !definitions.is_empty() {
+        out.push('\n');
+        out.push_str(&definitions);
+    }
+    Cow::Owned(out)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{LangString, Markdown, MarkdownHtml};
+    use super::{LangString, Markdown, MarkdownHtml, markdown_links, rewrite_links};
     use super::plain_summary_line;
     use super::RenderType;
     use html::render::reset_ids;
@@ -1191,14 +1432,14 @@ mod tests {
     #[test]
     fn issue_17736() {
         let markdown = "# title";
-        format!("{}", Markdown(markdown, RenderType::Pulldown));
+        format!("{}", Markdown(markdown, &[], RenderType::Pulldown));
         reset_ids(true);
     }
 
     #[test]
     fn test_header() {
         fn t(input: &str, expect: &str) {
-            let output = format!("{}", Markdown(input, RenderType::Pulldown));
+            let output = format!("{}", Markdown(input, &[], RenderType::Pulldown));
             assert_eq!(output, expect, "original: {}", input);
             reset_ids(true);
         }
@@ -1220,7 +1461,7 @@ mod tests {
     #[test]
     fn test_header_ids_multiple_blocks() {
         fn t(input: &str, expect: &str) {
-            let output = format!("{}", Markdown(input, RenderType::Pulldown));
+            let output = format!("{}", Markdown(input, &[], RenderType::Pulldown));
             assert_eq!(output, expect, "original: {}", input);
         }
 
@@ -1269,4 +1510,32 @@ mod tests {
         t("Struct<'a, T>", "<p>Struct&lt;'a, T&gt;</p>\n");
         t("Struct<br>", "<p>Struct&lt;br&gt;</p>\n");
     }
+
+    #[test]
+    fn test_markdown_links() {
+        fn t(input: &str, expect: &[(&str, bool)]) {
+            let links: Vec<_> = markdown_links(input).into_iter()
+                                                     .map(|link| (link.target, link.shortcut))
+                                                     .collect();
+            assert_eq!(links, expect, "original: {}", input);
+        }
+
+        t("[Foo] and [`Bar::baz`][]", &[("Foo", true), ("`Bar::baz`", true)]);
+        t("[text](Foo) and [text][Bar]", &[("Foo", false), ("Bar", true)]);
+        t("[Foo]\n\n[foo]: Bar", &[("Bar", false)]);
+        t("`[Foo]` and ![image](Foo) and \\[Foo]", &[]);
+        t("```\n[Foo]\n```\n\n    [Bar]\n\n[Baz]", &[("Baz", true)]);
+    }
+
+    #[test]
+    fn test_rewrite_links() {
+        let links = vec![("Foo".to_string(), "struct.Foo.html".to_string())];
+        let t = |input: &str, expect: &str| {
+            assert_eq!(rewrite_links(input, &links), expect, "original: {}", input);
+        };
+
+        t("[text](Foo)", "[text](struct.Foo.html)");
+        t("[Foo] and [Foo]", "[Foo] and [Foo]\n\n[Foo]: struct.Foo.html");
+        t("[Bar](Bar)", "[Bar](Bar)");
+    }
 }
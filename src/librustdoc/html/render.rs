@@ -75,6 +75,7 @@ use html::markdown::{self, Markdown, MarkdownHtml, MarkdownSummaryLine, RenderTy
 use html::{highlight, layout};
 
 use html_diff;
+use scrape_examples::ScrapedExample;
 
 /// A pair of name and its optional document.
 pub type NameDoc = (String, Option<String>);
@@ -123,6 +124,9 @@ pub struct SharedContext {
     /// The given user css file which allow to customize the generated
     /// documentation theme.
     pub css_file_extension: Option<PathBuf>,
+    /// The theme files given with `--theme`, which can be picked instead of
+    /// the built-in one.
+    pub themes: Vec<PathBuf>,
     /// Warnings for the user if rendering would differ using different markdown
     /// parsers.
     pub markdown_warnings: RefCell<Vec<(Span, String, Vec<html_diff::Difference>)>>,
@@ -174,7 +178,38 @@ pub enum ExternalLocation {
 pub struct Implementor {
     pub def_id: DefId,
     pub stability: Option<clean::Stability>,
-    pub impl_: clean::Impl,
+    pub impl_item: clean::Item,
+}
+
+impl Implementor {
+    fn inner_impl(&self) -> &clean::Impl {
+        match self.impl_item.inner {
+            clean::ImplItem(ref impl_) => impl_,
+            _ => panic!("non-impl item found in implementors")
+        }
+    }
+
+    /// Whether this implements the trait for a type which doesn't have a page
+    /// in these docs, such as a primitive or a type of another crate, rather
+    /// than for a documented type or a generic type.
+    fn is_on_foreign_type(&self, cache: &Cache) -> bool {
+        let for_ = &self.inner_impl().for_;
+        match *for_ {
+            clean::Generic(..) |
+            clean::ResolvedPath { is_generic: true, .. } |
+            clean::BorrowedRef { type_: box clean::Generic(..), .. } |
+            clean::BorrowedRef {
+                type_: box clean::ResolvedPath { is_generic: true, .. }, ..
+            } => return false,
+            _ => {}
+        }
+        match for_.def_id() {
+            Some(did) => !cache.paths.contains_key(&did),
+            None => for_.primitive_type()
+                        .and_then(|prim| cache.primitive_locations.get(&prim))
+                        .map_or(true, |did| !did.is_local()),
+        }
+    }
 }
 
 /// Metadata about implementations for a type.
@@ -292,6 +327,10 @@ pub struct Cache {
     /// The version of the crate being documented, if given fron the `--crate-version` flag.
     pub crate_version: Option<String>,
 
+    /// Calls to the functions and methods of this crate found in example
+    /// crates, given with `--with-examples`.
+    pub scraped_examples: FxHashMap<DefId, Vec<ScrapedExample>>,
+
     // Private fields only used when initially crawling a crate to build a cache
 
     stack: Vec<String>,
@@ -322,6 +361,7 @@ pub struct RenderInfo {
     pub deref_trait_did: Option<DefId>,
     pub deref_mut_trait_did: Option<DefId>,
     pub owned_box_did: Option<DefId>,
+    pub scraped_examples: FxHashMap<DefId, Vec<ScrapedExample>>,
 }
 
 /// Helper struct to render all source code to HTML pages
@@ -357,6 +397,7 @@ struct IndexItem {
     parent: Option<DefId>,
     parent_idx: Option<usize>,
     search_type: Option<IndexItemFunctionType>,
+    aliases: Vec<String>,
 }
 
 impl ToJson for IndexItem {
@@ -490,6 +531,7 @@ pub fn run(mut krate: clean::Crate,
            dst: PathBuf,
            passes: FxHashSet<String>,
            css_file_extension: Option<PathBuf>,
+           themes: Vec<PathBuf>,
            renderinfo: RenderInfo,
            render_type: RenderType) -> Result<(), Error> {
     let src_root = match krate.src {
@@ -512,6 +554,7 @@ pub fn run(mut krate: clean::Crate,
             krate: krate.name.clone(),
         },
         css_file_extension: css_file_extension.clone(),
+        themes,
         markdown_warnings: RefCell::new(vec![]),
         created_dirs: RefCell::new(FxHashSet()),
     };
@@ -569,6 +612,7 @@ pub fn run(mut krate: clean::Crate,
         deref_trait_did,
         deref_mut_trait_did,
         owned_box_did,
+        scraped_examples,
     } = renderinfo;
 
     let external_paths = external_paths.into_iter()
@@ -589,6 +633,7 @@ pub fn run(mut krate: clean::Crate,
         stripped_mod: false,
         access_levels: krate.access_levels.clone(),
         crate_version: krate.version.take(),
+        scraped_examples,
         orphan_impl_items: Vec::new(),
         traits: mem::replace(&mut krate.external_traits, FxHashMap()),
         deref_trait_did,
@@ -794,7 +839,8 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
                 desc: plain_summary_line(item.doc_value()),
                 parent: Some(did),
                 parent_idx: None,
-                search_type: get_index_search_type(&item),
+                search_type: get_index_search_type(&item, fqp.last().map(|s| &s[..])),
+                aliases: get_index_aliases(&item),
             });
         }
     }
@@ -803,8 +849,13 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
     // and prune the paths that do not appear in the index.
     let mut lastpath = String::new();
     let mut lastpathid = 0usize;
+    let mut crate_aliases = BTreeMap::new();
 
     for item in search_index {
+        for alias in item.aliases.drain(..) {
+            crate_aliases.entry(alias).or_insert_with(Vec::new).push(crate_items.len());
+        }
+
         item.parent_idx = item.parent.map(|nodeid| {
             if nodeid_to_pathid.contains_key(&nodeid) {
                 *nodeid_to_pathid.get(&nodeid).unwrap()
@@ -836,6 +887,7 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
     crate_data.insert("doc".to_owned(), Json::String(crate_doc));
     crate_data.insert("items".to_owned(), Json::Array(crate_items));
     crate_data.insert("paths".to_owned(), Json::Array(crate_paths));
+    crate_data.insert("aliases".to_owned(), crate_aliases.to_json());
 
     // Collect the index into a string
     format!("searchIndex[{}] = {};",
@@ -861,6 +913,15 @@ fn write_shared(cx: &Context,
           include_bytes!("static/rustdoc.css"))?;
     write(cx.dst.join("main.css"),
           include_bytes!("static/styles/main.css"))?;
+    write(cx.dst.join("storage.js"),
+          include_bytes!("static/storage.js"))?;
+    for theme in &cx.shared.themes {
+        let mut content = Vec::new();
+        let mut f = try_err!(File::open(theme), theme);
+        try_err!(f.read_to_end(&mut content), theme);
+        // `lib.rs` has checked that the theme files have a name
+        write(cx.dst.join(theme.file_name().unwrap()), &content)?;
+    }
     if let Some(ref css) = cx.shared.css_file_extension {
         let mut content = String::new();
         let css = css.as_path();
@@ -965,7 +1026,7 @@ fn write_shared(cx: &Context,
             // should add it.
             if !imp.def_id.is_local() { continue }
             have_impls = true;
-            write!(implementors, "{},", as_json(&imp.impl_.to_string())).unwrap();
+            write!(implementors, "{},", as_json(&imp.inner_impl().to_string())).unwrap();
         }
         implementors.push_str("];");
 
@@ -1162,7 +1223,7 @@ impl<'a> SourceCollector<'a> {
         };
         layout::render(&mut w, &self.scx.layout,
                        &page, &(""), &Source(contents),
-                       self.scx.css_file_extension.is_some())?;
+                       self.scx.css_file_extension.is_some(), &self.scx.themes)?;
         w.flush()?;
         self.scx.local_sources.insert(p.clone(), href);
         Ok(())
@@ -1194,13 +1255,16 @@ impl DocFolder for Cache {
 
         // Collect all the implementors of traits.
         if let clean::ImplItem(ref i) = item.inner {
-            if !self.masked_crates.contains(&item.def_id.krate) {
+            // Synthetic and blanket impls shown on a type's page don't make
+            // it an implementor; a blanket impl is listed as written instead.
+            let on_type_page = i.synthetic || i.blanket_impl.is_some();
+            if !self.masked_crates.contains(&item.def_id.krate) && !on_type_page {
                 if let Some(did) = i.trait_.def_id() {
                     if i.for_.def_id().map_or(true, |d| !self.masked_crates.contains(&d.krate)) {
                         self.implementors.entry(did).or_insert(vec![]).push(Implementor {
                             def_id: item.def_id,
                             stability: item.stability.clone(),
-                            impl_: i.clone(),
+                            impl_item: item.clone(),
                         });
                     }
                 }
@@ -1257,6 +1321,8 @@ impl DocFolder for Cache {
                     // which should not be indexed. The crate-item itself is
                     // inserted later on when serializing the search-index.
                     if item.def_id.index != CRATE_DEF_INDEX {
+                        let parent_name = parent.and_then(|did| self.paths.get(&did))
+                                                .and_then(|&(ref fqp, _)| fqp.last().cloned());
                         self.search_index.push(IndexItem {
                             ty: item.type_(),
                             name: s.to_string(),
@@ -1264,7 +1330,10 @@ impl DocFolder for Cache {
                             desc: plain_summary_line(item.doc_value()),
                             parent,
                             parent_idx: None,
-                            search_type: get_index_search_type(&item),
+                            search_type: get_index_search_type(&item,
+                                                               parent_name.as_ref()
+                                                                          .map(|s| &s[..])),
+                            aliases: get_index_aliases(&item),
                         });
                     }
                 }
@@ -1526,7 +1595,8 @@ impl Context {
             layout::render(writer, &self.shared.layout, &page,
                            &Sidebar{ cx: self, item: it },
                            &Item{ cx: self, item: it },
-                           self.shared.css_file_extension.is_some())?;
+                           self.shared.css_file_extension.is_some(),
+                           &self.shared.themes)?;
         } else {
             let mut url = self.root_path();
             if let Some(&(ref names, ty)) = cache().paths.get(&it.def_id) {
@@ -1858,19 +1928,46 @@ fn document(w: &mut fmt::Formatter, cx: &Context, item: &clean::Item) -> fmt::Re
     document_stability(w, cx, item)?;
     let prefix = render_assoc_const_value(item);
     document_full(w, item, cx, &prefix)?;
+    render_scraped_examples(w, item)?;
     Ok(())
 }
 
+/// Renders the calls to `item` found in the crates given with
+/// `--with-examples`, with the code of each calling item highlighted.
+fn render_scraped_examples(w: &mut fmt::Formatter, item: &clean::Item) -> fmt::Result {
+    let cache = cache();
+    let examples = match cache.scraped_examples.get(&item.def_id) {
+        Some(examples) if !examples.is_empty() => examples,
+        _ => return Ok(()),
+    };
+    write!(w, "<details class='scraped-examples'>\
+               <summary>Examples found in repository</summary>")?;
+    for example in examples {
+        let lines = example.call_lines.iter().map(|&(lo, hi)| {
+            if lo == hi { lo.to_string() } else { format!("{}-{}", lo, hi) }
+        }).collect::<Vec<_>>();
+        write!(w, "<div class='scraped-example'><div class='scraped-example-title'>\
+                   {} (line {})</div>{}</div>",
+               Escape(&example.file), lines.join(", "),
+               highlight::render_with_highlighting(&example.code,
+                                                   Some("rust-example-rendered"),
+                                                   None, None, None))?;
+    }
+    write!(w, "</details>")
+}
+
 /// Render md_text as markdown. Warns the user if there are difference in
 /// rendering between Pulldown and Hoedown.
 fn render_markdown(w: &mut fmt::Formatter,
                    md_text: &str,
+                   links: &[(String, String)],
                    span: Span,
                    render_type: RenderType,
                    prefix: &str,
                    scx: &SharedContext)
                    -> fmt::Result {
-    let (hoedown_output, pulldown_output) = render_text(|ty| format!("{}", Markdown(md_text, ty)));
+    let (hoedown_output, pulldown_output) =
+        render_text(|ty| format!("{}", Markdown(md_text, links, ty)));
     let mut differences = html_diff::get_differences(&pulldown_output, &hoedown_output);
     differences.retain(|s| {
         match *s {
@@ -1902,7 +1999,8 @@ fn document_short(w: &mut fmt::Formatter, item: &clean::Item, link: AssocItemLin
         } else {
             format!("{}", &plain_summary_line(Some(s)))
         };
-        render_markdown(w, &markdown, item.source.clone(), cx.render_type, prefix, &cx.shared)?;
+        render_markdown(w, &markdown, &item.links(), item.source.clone(), cx.render_type,
+                        prefix, &cx.shared)?;
     } else if !prefix.is_empty() {
         write!(w, "<div class='docblock'>{}</div>", prefix)?;
     }
@@ -1928,7 +2026,8 @@ fn document_full(w: &mut fmt::Formatter, item: &clean::Item,
                  cx: &Context, prefix: &str) -> fmt::Result {
     if let Some(s) = cx.shared.maybe_collapsed_doc_value(item) {
         debug!("Doc block: =====\n{}\n=====", s);
-        render_markdown(w, &*s, item.source.clone(), cx.render_type, prefix, &cx.shared)?;
+        render_markdown(w, &*s, &item.links(), item.source.clone(), cx.render_type,
+                        prefix, &cx.shared)?;
     } else if !prefix.is_empty() {
         write!(w, "<div class='docblock'>{}</div>", prefix)?;
     }
@@ -2152,10 +2251,10 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
                        stab_docs = stab_docs,
                        docs = if cx.render_type == RenderType::Hoedown {
                            format!("{}",
-                                   shorter(Some(&Markdown(doc_value,
+                                   shorter(Some(&Markdown(doc_value, &myitem.links(),
                                                           RenderType::Hoedown).to_string())))
                        } else {
-                           format!("{}", MarkdownSummaryLine(doc_value))
+                           format!("{}", MarkdownSummaryLine(doc_value, &myitem.links()))
                        },
                        class = myitem.type_(),
                        stab = myitem.stability_class().unwrap_or("".to_string()),
@@ -2321,18 +2420,6 @@ fn item_function(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
     document(w, cx, it)
 }
 
-fn implementor2item<'a>(cache: &'a Cache, imp : &Implementor) -> Option<&'a clean::Item> {
-    if let Some(t_did) = imp.impl_.for_.def_id() {
-        if let Some(impl_item) = cache.impls.get(&t_did).and_then(|i| i.iter()
-            .find(|i| i.impl_item.def_id == imp.def_id))
-        {
-            let i = &impl_item.impl_item;
-            return Some(i);
-        }
-    }
-    None
-}
-
 fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
               t: &clean::Trait) -> fmt::Result {
     let mut bounds = String::new();
@@ -2516,7 +2603,7 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
         // if any Types with the same name but different DefId have been found.
         let mut implementor_dups: FxHashMap<&str, (DefId, bool)> = FxHashMap();
         for implementor in implementors {
-            match implementor.impl_.for_ {
+            match implementor.inner_impl().for_ {
                 clean::ResolvedPath { ref path, did, is_generic: false, .. } |
                 clean::BorrowedRef {
                     type_: box clean::ResolvedPath { ref path, did, is_generic: false, .. },
@@ -2532,9 +2619,8 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
             }
         }
 
-        let (local, foreign) = implementors.iter()
-            .partition::<Vec<_>, _>(|i| i.impl_.for_.def_id()
-                                         .map_or(true, |d| cache.paths.contains_key(&d)));
+        let (foreign, local) = implementors.iter()
+            .partition::<Vec<_>, _>(|i| i.is_on_foreign_type(&cache));
 
         if !foreign.is_empty() {
             write!(w, "
@@ -2544,14 +2630,13 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
             ")?;
 
             for implementor in foreign {
-                if let Some(i) = implementor2item(&cache, implementor) {
-                    let impl_ = Impl { impl_item: i.clone() };
-                    let assoc_link = AssocItemLink::GotoSource(
-                        i.def_id, &implementor.impl_.provided_trait_methods
-                    );
-                    render_impl(w, cx, &impl_, assoc_link,
-                                RenderMode::Normal, i.stable_since(), false)?;
-                }
+                let i = &implementor.impl_item;
+                let impl_ = Impl { impl_item: i.clone() };
+                let assoc_link = AssocItemLink::GotoSource(
+                    i.def_id, &implementor.inner_impl().provided_trait_methods
+                );
+                render_impl(w, cx, &impl_, assoc_link,
+                            RenderMode::Normal, i.stable_since(), false)?;
             }
         }
 
@@ -2559,18 +2644,16 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
 
         for implementor in local {
             write!(w, "<li>")?;
-            if let Some(item) = implementor2item(&cache, implementor) {
-                if let Some(l) = (Item { cx, item }).src_href() {
-                    write!(w, "<div class='out-of-band'>")?;
-                    write!(w, "<a class='srclink' href='{}' title='{}'>[src]</a>",
-                                l, "goto source code")?;
-                    write!(w, "</div>")?;
-                }
+            if let Some(l) = (Item { cx, item: &implementor.impl_item }).src_href() {
+                write!(w, "<div class='out-of-band'>")?;
+                write!(w, "<a class='srclink' href='{}' title='{}'>[src]</a>",
+                            l, "goto source code")?;
+                write!(w, "</div>")?;
             }
             write!(w, "<code>")?;
             // If there's already another implementor that has the same abbridged name, use the
             // full path, for example in `std::iter::ExactSizeIterator`
-            let use_absolute = match implementor.impl_.for_ {
+            let use_absolute = match implementor.inner_impl().for_ {
                 clean::ResolvedPath { ref path, is_generic: false, .. } |
                 clean::BorrowedRef {
                     type_: box clean::ResolvedPath { ref path, is_generic: false, .. },
@@ -2578,8 +2661,8 @@ fn item_trait(w: &mut fmt::Formatter, cx: &Context, it: &clean::Item,
                 } => implementor_dups[path.last_name()].1,
                 _ => false,
             };
-            fmt_impl_for_trait_page(&implementor.impl_, w, use_absolute)?;
-            for it in &implementor.impl_.items {
+            fmt_impl_for_trait_page(implementor.inner_impl(), w, use_absolute)?;
+            for it in &implementor.inner_impl().items {
                 if let clean::TypedefItem(ref tydef, _) = it.inner {
                     write!(w, "<span class=\"where fmt-newline\">  ")?;
                     assoc_type(w, it, &vec![], Some(&tydef.type_), AssocItemLink::Anchor(None))?;
@@ -3211,21 +3294,53 @@ fn render_assoc_items(w: &mut fmt::Formatter,
             }).is_some();
             render_deref_methods(w, cx, impl_, containing_item, has_deref_mut)?;
         }
-        write!(w, "
-            <h2 id='implementations' class='small-section-header'>
-              Trait Implementations<a href='#implementations' class='anchor'></a>
-            </h2>
-        ")?;
-        for i in &traits {
-            let did = i.trait_did().unwrap();
-            let assoc_link = AssocItemLink::GotoSource(did, &i.inner_impl().provided_trait_methods);
-            render_impl(w, cx, i, assoc_link,
-                        RenderMode::Normal, containing_item.stable_since(), true)?;
+
+        let (synthetic, concrete): (Vec<&&Impl>, Vec<&&Impl>) = traits.iter()
+            .partition(|t| t.inner_impl().synthetic);
+        let (blanket, concrete): (Vec<&&Impl>, _) = concrete.into_iter()
+            .partition(|t| t.inner_impl().blanket_impl.is_some());
+
+        if !concrete.is_empty() {
+            write!(w, "
+                <h2 id='implementations' class='small-section-header'>
+                  Trait Implementations<a href='#implementations' class='anchor'></a>
+                </h2>
+            ")?;
+            render_impls(w, cx, &concrete, containing_item)?;
+        }
+        if !synthetic.is_empty() {
+            write!(w, "
+                <h2 id='synthetic-implementations' class='small-section-header'>
+                  Auto Trait Implementations\
+                  <a href='#synthetic-implementations' class='anchor'></a>
+                </h2>
+            ")?;
+            render_impls(w, cx, &synthetic, containing_item)?;
+        }
+        if !blanket.is_empty() {
+            write!(w, "
+                <h2 id='blanket-implementations' class='small-section-header'>
+                  Blanket Implementations\
+                  <a href='#blanket-implementations' class='anchor'></a>
+                </h2>
+            ")?;
+            render_impls(w, cx, &blanket, containing_item)?;
         }
     }
     Ok(())
 }
 
+fn render_impls(w: &mut fmt::Formatter, cx: &Context, traits: &[&&Impl],
+                containing_item: &clean::Item) -> fmt::Result {
+    for i in traits {
+        let did = i.trait_did().unwrap();
+        let assoc_link = AssocItemLink::GotoSource(did, &i.inner_impl().provided_trait_methods);
+        render_impl(w, cx, i, assoc_link,
+                    RenderMode::Normal, containing_item.stable_since(), true)?;
+    }
+    Ok(())
+}
+
 fn render_deref_methods(w: &mut fmt::Formatter, cx: &Context, impl_: &Impl,
                         container_item: &clean::Item, deref_mut: bool) -> fmt::Result {
     let deref_type = impl_.inner_impl().trait_.as_ref().unwrap();
@@ -3351,7 +3466,13 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
         write!(w, "<a href='#{}' class='anchor'></a>", id)?;
         write!(w, "</span><span class='out-of-band'>")?;
         let since = i.impl_item.stability.as_ref().map(|s| &s.since[..]);
-        if let Some(l) = (Item { item: &i.impl_item, cx: cx }).src_href() {
+        // Impls of auto traits the compiler provides have no source to link to
+        let src_href = if i.inner_impl().synthetic {
+            None
+        } else {
+            (Item { item: &i.impl_item, cx: cx }).src_href()
+        };
+        if let Some(l) = src_href {
             write!(w, "<div class='ghost'></div>")?;
             render_stability_since_raw(w, since, outer_version)?;
             write!(w, "<a class='srclink' href='{}' title='{}'>[src]</a>",
@@ -3362,7 +3483,8 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
         write!(w, "</span>")?;
         write!(w, "</h3>\n")?;
         if let Some(ref dox) = cx.shared.maybe_collapsed_doc_value(&i.impl_item) {
-            write!(w, "<div class='docblock'>{}</div>", Markdown(&*dox, cx.render_type))?;
+            write!(w, "<div class='docblock'>{}</div>",
+                   Markdown(&*dox, &i.impl_item.links(), cx.render_type))?;
         }
     }
 
@@ -3454,6 +3576,7 @@ fn render_impl(w: &mut fmt::Formatter, cx: &Context, i: &Impl, link: AssocItemLi
                     document_stability(w, cx, item)?;
                     if show_def_docs {
                         document_full(w, item, cx, &prefix)?;
+                        render_scraped_examples(w, item)?;
                     }
                 }
             } else {
@@ -3721,32 +3844,48 @@ fn sidebar_assoc_items(it: &clean::Item) -> String {
                     }
                 }
             }
-            let mut links = HashSet::new();
-            let ret = v.iter()
-                       .filter_map(|i| {
-                           let is_negative_impl = is_negative_impl(i.inner_impl());
-                           if let Some(ref i) = i.inner_impl().trait_ {
-                               let i_display = format!("{:#}", i);
-                               let out = Escape(&i_display);
-                               let encoded = small_url_encode(&format!("{:#}", i));
-                               let generated = format!("<a href=\"#impl-{}\">{}{}</a>",
-                                                       encoded,
-                                                       if is_negative_impl { "!" } else { "" },
-                                                       out);
-                               if !links.contains(&generated) && links.insert(generated.clone()) {
-                                   Some(generated)
-                               } else {
-                                   None
-                               }
-                           } else {
-                               None
-                           }
-                       })
-                       .collect::<String>();
-            if !ret.is_empty() {
-                out.push_str("<a class=\"sidebar-title\" href=\"#implementations\">\
-                              Trait Implementations</a>");
-                out.push_str(&format!("<div class=\"sidebar-links\">{}</div>", ret));
+            let format_impls = |impls: Vec<&Impl>| {
+                let mut links = HashSet::new();
+                impls.iter()
+                     .filter_map(|i| {
+                         let is_negative_impl = is_negative_impl(i.inner_impl());
+                         if let Some(ref i) = i.inner_impl().trait_ {
+                             let i_display = format!("{:#}", i);
+                             let out = Escape(&i_display);
+                             let encoded = small_url_encode(&format!("{:#}", i));
+                             let generated = format!("<a href=\"#impl-{}\">{}{}</a>",
+                                                     encoded,
+                                                     if is_negative_impl { "!" } else { "" },
+                                                     out);
+                             if !links.contains(&generated) && links.insert(generated.clone()) {
+                                 Some(generated)
+                             } else {
+                                 None
+                             }
+                         } else {
+                             None
+                         }
+                     })
+                     .collect::<String>()
+            };
+
+            let (synthetic, concrete): (Vec<&Impl>, Vec<&Impl>) = v.iter()
+                .partition(|i| i.inner_impl().synthetic);
+            let (blanket, concrete): (Vec<&Impl>, Vec<&Impl>) = concrete.into_iter()
+                .partition(|i| i.inner_impl().blanket_impl.is_some());
+
+            let sections = [
+                ("implementations", "Trait Implementations", format_impls(concrete)),
+                ("synthetic-implementations", "Auto Trait Implementations",
+                 format_impls(synthetic)),
+                ("blanket-implementations", "Blanket Implementations", format_impls(blanket)),
+            ];
+            for &(id, title, ref ret) in sections.iter() {
+                if !ret.is_empty() {
+                    out.push_str(&format!("<a class=\"sidebar-title\" href=\"#{}\">{}</a>",
+                                          id, title));
+                    out.push_str(&format!("<div class=\"sidebar-links\">{}</div>", ret));
+                }
             }
         }
     }
@@ -3868,20 +4007,15 @@ fn sidebar_trait(fmt: &mut fmt::Formatter, it: &clean::Item,
 
     if let Some(implementors) = c.implementors.get(&it.def_id) {
         let res = implementors.iter()
-                              .filter(|i| i.impl_.for_.def_id()
-                                                      .map_or(false, |d| !c.paths.contains_key(&d)))
+                              .filter(|i| i.is_on_foreign_type(&c))
                               .filter_map(|i| {
-                                  if let Some(item) = implementor2item(&c, i) {
-                                      match extract_for_impl_name(&item) {
-                                          Some((ref name, ref url)) => {
-                                              Some(format!("<a href=\"#impl-{}\">{}</a>",
-                                                           small_url_encode(url),
-                                                           Escape(name)))
-                                          }
-                                          _ => None,
+                                  match extract_for_impl_name(&i.impl_item) {
+                                      Some((ref name, ref url)) => {
+                                          Some(format!("<a href=\"#impl-{}\">{}</a>",
+                                                       small_url_encode(url),
+                                                       Escape(name)))
                                       }
-                                  } else {
-                                      None
+                                      _ => None,
                                   }
                               })
                               .collect::<String>();
@@ -4089,58 +4223,125 @@ fn make_item_keywords(it: &clean::Item) -> String {
     format!("{}, {}", BASIC_KEYWORDS, it.name.as_ref().unwrap())
 }
 
-fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
-    let decl = match item.inner {
-        clean::FunctionItem(ref f) => &f.decl,
-        clean::MethodItem(ref m) => &m.decl,
-        clean::TyMethodItem(ref m) => &m.decl,
+/// Gets the type of a function or method to index, with `Self` standing for
+/// `parent`, the type or trait the method is on.
+fn get_index_search_type(item: &clean::Item,
+                         parent: Option<&str>) -> Option<IndexItemFunctionType> {
+    let (generics, decl) = match item.inner {
+        clean::FunctionItem(ref f) => (&f.generics, &f.decl),
+        clean::MethodItem(ref m) => (&m.generics, &m.decl),
+        clean::TyMethodItem(ref m) => (&m.generics, &m.decl),
         _ => return None
     };
 
-    let inputs = decl.inputs.values.iter().map(|arg| get_index_type(&arg.type_)).collect();
+    let inputs = decl.inputs.values.iter()
+                                   .map(|arg| get_index_type(&arg.type_, generics, parent))
+                                   .collect();
     let output = match decl.output {
-        clean::FunctionRetTy::Return(ref return_type) => Some(get_index_type(return_type)),
+        clean::FunctionRetTy::Return(ref return_type) => {
+            Some(get_index_type(return_type, generics, parent))
+        }
         _ => None
     };
 
     Some(IndexItemFunctionType { inputs: inputs, output: output })
 }
 
-fn get_index_type(clean_type: &clean::Type) -> Type {
+fn get_index_type(clean_type: &clean::Type, generics: &clean::Generics,
+                  parent: Option<&str>) -> Type {
+    let clean_type = get_index_resolved_type(clean_type, generics);
     let t = Type {
-        name: get_index_type_name(clean_type, true).map(|s| s.to_ascii_lowercase()),
-        generics: get_generics(clean_type),
+        name: get_index_type_name(clean_type, parent).map(|s| s.to_ascii_lowercase()),
+        generics: get_generics(clean_type, generics, parent),
     };
     t
 }
 
-fn get_index_type_name(clean_type: &clean::Type, accept_generic: bool) -> Option<String> {
+/// Looks through references and pointers, and replaces a type parameter with
+/// its first trait bound, so that `P: AsRef<Path>` is found by searching for
+/// `Path`.
+fn get_index_resolved_type<'a>(clean_type: &'a clean::Type,
+                               generics: &'a clean::Generics) -> &'a clean::Type {
+    match *clean_type {
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) => get_index_resolved_type(type_, generics),
+        clean::Generic(ref name) if name != "Self" => {
+            let param_bounds = generics.type_params.iter()
+                                                   .filter(|param| param.name == *name)
+                                                   .flat_map(|param| param.bounds.iter());
+            let where_bounds = generics.where_predicates.iter().filter_map(|pred| match *pred {
+                clean::WherePredicate::BoundPredicate {
+                    ty: clean::Generic(ref ty), ref bounds
+                } if ty == name => Some(bounds),
+                _ => None,
+            }).flat_map(|bounds| bounds.iter());
+            param_bounds.chain(where_bounds).filter_map(|bound| match *bound {
+                clean::TraitBound(ref poly, hir::TraitBoundModifier::None) => Some(&poly.trait_),
+                _ => None,
+            }).next().unwrap_or(clean_type)
+        }
+        _ => clean_type,
+    }
+}
+
+/// Gets the name of a type to index. Type parameters which couldn't be
+/// replaced with a bound are indexed as `_`, which any type matches.
+fn get_index_type_name(clean_type: &clean::Type, parent: Option<&str>) -> Option<String> {
     match *clean_type {
         clean::ResolvedPath { ref path, .. } => {
             let segments = &path.segments;
             Some(segments[segments.len() - 1].name.clone())
         }
-        clean::Generic(ref s) if accept_generic => Some(s.clone()),
+        clean::Generic(ref s) if s == "Self" && parent.is_some() => parent.map(str::to_owned),
+        clean::Generic(_) => Some("_".to_owned()),
         clean::Primitive(ref p) => Some(format!("{:?}", p)),
-        clean::BorrowedRef { ref type_, .. } => get_index_type_name(type_, accept_generic),
+        clean::BorrowedRef { ref type_, .. } |
+        clean::RawPointer(_, ref type_) => get_index_type_name(type_, parent),
+        clean::Slice(_) => Some("slice".to_owned()),
+        clean::Array(..) => Some("array".to_owned()),
+        clean::Tuple(ref types) if types.is_empty() => Some("()".to_owned()),
+        clean::Tuple(_) => Some("tuple".to_owned()),
+        clean::ImplTrait(ref bounds) => bounds.iter().filter_map(|bound| match *bound {
+            clean::TraitBound(ref poly, _) => get_index_type_name(&poly.trait_, parent),
+            _ => None,
+        }).next(),
         // FIXME: add all from clean::Type.
         _ => None
     }
 }
 
-fn get_generics(clean_type: &clean::Type) -> Option<Vec<String>> {
-    clean_type.generics()
-              .and_then(|types| {
-                  let r = types.iter()
-                               .filter_map(|t| get_index_type_name(t, false))
-                               .map(|s| s.to_ascii_lowercase())
-                               .collect::<Vec<_>>();
-                  if r.is_empty() {
-                      None
-                  } else {
-                      Some(r)
-                  }
-              })
+fn get_generics(clean_type: &clean::Type, generics: &clean::Generics,
+                parent: Option<&str>) -> Option<Vec<String>> {
+    let types = match *clean_type {
+        clean::ImplTrait(ref bounds) => bounds.iter().filter_map(|bound| match *bound {
+            clean::TraitBound(ref poly, _) => poly.trait_.generics(),
+            _ => None,
+        }).next(),
+        _ => clean_type.generics(),
+    };
+    types.and_then(|types| {
+        let r = types.iter()
+                     .filter_map(|t| {
+                         get_index_type_name(get_index_resolved_type(t, generics), parent)
+                     })
+                     .map(|s| s.to_ascii_lowercase())
+                     .collect::<Vec<_>>();
+        if r.is_empty() {
+            None
+        } else {
+            Some(r)
+        }
+    })
+}
+
+/// Gets the other names an item can be searched by, given with
+/// `#[doc(alias = "...")]`.
+fn get_index_aliases(item: &clean::Item) -> Vec<String> {
+    item.attrs.lists("doc")
+              .filter(|attr| attr.check_name("alias"))
+              .filter_map(|attr| attr.value_str())
+              .map(|alias| alias.to_string().to_ascii_lowercase())
+              .collect()
 }
 
 pub fn cache() -> Arc<Cache> {
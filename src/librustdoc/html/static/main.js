@@ -375,7 +375,7 @@
     })();
 
     function initSearch(rawSearchIndex) {
-        var currentResults, index, searchIndex;
+        var currentResults, index, searchIndex, aliases;
         var MAX_LEV_DISTANCE = 3;
         var MAX_RESULTS = 200;
         var params = getQueryStringParams();
@@ -715,6 +715,124 @@
                 return ret_lev;
             }
 
+            // Splits a list of types on the commas which aren't in angle brackets.
+            function splitTypes(s) {
+                var types = [];
+                var depth = 0;
+                var start = 0;
+                for (var i = 0; i < s.length; ++i) {
+                    var c = s.charAt(i);
+                    if (c === "<") {
+                        depth += 1;
+                    } else if (c === ">") {
+                        depth -= 1;
+                    } else if (c === "," && depth === 0) {
+                        types.push(s.substring(start, i));
+                        start = i + 1;
+                    }
+                }
+                types.push(s.substring(start));
+                return types.map(function(t) { return t.trim(); })
+                            .filter(function(t) { return t !== ""; });
+            }
+
+            // Parses a type of a signature query, like `&mut Vec<T>`. As in the
+            // index, references and pointers are left out and only the last
+            // segment of a path is kept. `_` and single capital letters, like
+            // `T`, stand for any type.
+            function parseType(s) {
+                s = s.trim().replace(/^(&\s*('\w+\s+)?(mut\s+)?|\*\s*(const|mut)\s+)+/, "");
+                var name = s;
+                var generics = [];
+                var open = s.indexOf("<");
+                if (open !== -1) {
+                    name = s.substring(0, open);
+                    generics = splitTypes(s.substring(open + 1, s.lastIndexOf(">")))
+                        .map(parseType);
+                } else if (s.charAt(0) === "[") {
+                    name = s.indexOf(";") !== -1 ? "array" : "slice";
+                }
+                name = name.split("::").pop().trim();
+                if (name === "" || name === "_" || /^[A-Z]$/.test(name)) {
+                    name = "_";
+                }
+                return {
+                    name: name.toLowerCase(),
+                    generics: generics,
+                };
+            }
+
+            // Returns how far the indexed type `obj` is from the query type
+            // `val`: 0 for an exact match, more for each part only matched
+            // through a type parameter or a generic, or `null` if they don't
+            // match.
+            function checkSignatureType(obj, val) {
+                if (!obj) {
+                    return null;
+                }
+                if (val.name === "_" || obj.name === "_") {
+                    return 1;
+                }
+                if (obj.name !== val.name) {
+                    // `Path` is still found in `AsRef<Path>`, but less exactly
+                    if (val.generics.length === 0 && obj.generics &&
+                        obj.generics.indexOf(val.name) !== -1) {
+                        return 1;
+                    }
+                    return null;
+                }
+                var distance = 0;
+                var elems = (obj.generics || []).slice(0);
+                for (var i = 0; i < val.generics.length; ++i) {
+                    var wanted = val.generics[i].name;
+                    if (wanted === "_") {
+                        continue;
+                    }
+                    var pos = elems.indexOf(wanted);
+                    if (pos === -1) {
+                        pos = elems.indexOf("_");
+                        distance += 1;
+                    }
+                    if (pos === -1) {
+                        return null;
+                    }
+                    elems.splice(pos, 1);
+                }
+                return distance;
+            }
+
+            // Checks that each of the query inputs matches its own argument of
+            // the function type, in any order. Arguments which weren't asked
+            // for make the match less exact.
+            function checkInputs(type, inputs) {
+                var remaining = type.inputs.slice(0);
+                var total = 0;
+                for (var i = 0; i < inputs.length; ++i) {
+                    var best = null;
+                    var pos = -1;
+                    for (var x = 0; x < remaining.length; ++x) {
+                        var distance = checkSignatureType(remaining[x], inputs[i]);
+                        if (distance !== null && (best === null || distance < best)) {
+                            best = distance;
+                            pos = x;
+                        }
+                    }
+                    if (pos === -1) {
+                        return null;
+                    }
+                    remaining.splice(pos, 1);
+                    total += best;
+                }
+                return total + remaining.length;
+            }
+
+            function checkOutput(type, output) {
+                if (output.name === "()") {
+                    return !type.output || type.output.name === "()" ? 0 : null;
+                }
+                return checkSignatureType(type.output, output);
+            }
+
             function typePassesFilter(filter, type) {
                 // No filter
                 if (filter < 0) return true;
@@ -794,67 +912,51 @@
                 query.search = val;
             // searching by type
             } else if (val.search("->") > -1) {
-                var trimmer = function (s) { return s.trim(); };
-                var parts = val.split("->").map(trimmer);
-                var input = parts[0];
-                // sort inputs so that order does not matter
-                var inputs = input.split(",").map(trimmer).sort();
-                for (var i = 0; i < inputs.length; ++i) {
-                    inputs[i] = extractGenerics(inputs[i]);
-                }
-                var output = extractGenerics(parts[1]);
+                var parts = query.query.split("->");
+                var input = parts[0].trim();
+                var inputs = input === "*" ? null : splitTypes(input).map(parseType);
+                var outputPart = parts.slice(1).join("->").trim();
+                var output = outputPart === "*" ? null : parseType(outputPart);
 
                 for (var i = 0; i < nSearchWords; ++i) {
-                    var type = searchIndex[i].type;
                     var ty = searchIndex[i];
-                    if (!type) {
+                    var type = ty.type;
+                    if (!type || typePassesFilter(typeFilter, ty.ty) === false) {
                         continue;
                     }
                     var fullId = generateId(ty);
-
-                    // allow searching for void (no output) functions as well
-                    var typeOutput = type.output ? type.output.name : "";
-                    var returned = checkReturned(ty, output, true);
-                    if (output.name === "*" || returned === true) {
-                        var in_args = false;
-                        var module = false;
-
-                        if (input === "*") {
-                            module = true;
-                        } else {
-                            var allFound = true;
-                            for (var it = 0; allFound === true && it < inputs.length; it++) {
-                                allFound = checkType(type, inputs[it], true);
-                            }
-                            in_args = allFound;
-                        }
-                        if (in_args === true) {
-                            results_in_args[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (returned === true) {
-                            results_returned[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
-                        if (module === true) {
-                            results[fullId] = {
-                                id: i,
-                                index: -1,
-                                dontValidate: true,
-                            };
-                        }
+                    var in_args = inputs === null ? 0 : checkInputs(type, inputs);
+                    var returned = output === null ? 0 : checkOutput(type, output);
+
+                    if (inputs !== null && in_args !== null) {
+                        results_in_args[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: in_args,
+                            dontValidate: true,
+                        };
+                    }
+                    if (output !== null && returned !== null) {
+                        results_returned[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: returned,
+                            dontValidate: true,
+                        };
+                    }
+                    if (in_args !== null && returned !== null) {
+                        results[fullId] = {
+                            id: i,
+                            index: -1,
+                            lev: in_args + returned,
+                            dontValidate: true,
+                        };
                     }
                 }
-                query.inputs = inputs.map(function(input) {
+                query.inputs = inputs === null ? ["*"] : inputs.map(function(input) {
                     return input.name;
                 });
-                query.output = output.name;
+                query.output = output === null ? "*" : output.name;
             } else {
                 query.inputs = [val];
                 query.output = val;
@@ -969,6 +1071,22 @@
                         results[fullId].lev = Math.min(results[fullId].lev, lev);
                     }
                 }
+
+                // Items with a `#[doc(alias = "...")]` of the searched name
+                if (aliases.hasOwnProperty(val)) {
+                    for (j = 0; j < aliases[val].length; ++j) {
+                        var aliased = searchIndex[aliases[val][j]];
+                        if (typePassesFilter(typeFilter, aliased.ty) === false) {
+                            continue;
+                        }
+                        results[generateId(aliased)] = {
+                            id: aliases[val][j],
+                            index: -1,
+                            lev: 0,
+                            dontValidate: true,
+                        };
+                    }
+                }
             }
 
             return {
@@ -1017,10 +1135,14 @@
                 document.getElementsByClassName('search-input')[0].value;
             query = raw;
 
-            matches = query.match(/^(fn|mod|struct|enum|trait|type|const|macro)\s*:\s*/i);
+            // Any item type can be used as a filter, like `fn:` or `method:`
+            matches = query.match(/^(\w+)\s*:(?!:)\s*/);
             if (matches) {
-                type = matches[1].replace(/^const$/, 'constant');
-                query = query.substring(matches[0].length);
+                var typeName = matches[1].toLowerCase().replace(/^const$/, 'constant');
+                if (itemTypeFromName(typeName) !== -1) {
+                    type = typeName;
+                    query = query.substring(matches[0].length);
+                }
             }
 
             return {
@@ -1295,6 +1417,7 @@
 
         function buildIndex(rawSearchIndex) {
             searchIndex = [];
+            aliases = {};
             var searchWords = [];
             for (var crate in rawSearchIndex) {
                 if (!rawSearchIndex.hasOwnProperty(crate)) { continue; }
@@ -1349,6 +1472,19 @@
                     }
                     lastPath = row.path;
                 }
+
+                // map of the `#[doc(alias)]`es of the crate to the items they are on
+                var crateAliases = rawSearchIndex[crate].aliases || {};
+                var offset = searchIndex.length - len;
+                for (var alias in crateAliases) {
+                    if (!crateAliases.hasOwnProperty(alias)) { continue; }
+                    if (!aliases.hasOwnProperty(alias)) {
+                        aliases[alias] = [];
+                    }
+                    for (var j = 0; j < crateAliases[alias].length; ++j) {
+                        aliases[alias].push(crateAliases[alias][j] + offset);
+                    }
+                }
             }
             return searchWords;
         }
@@ -1855,6 +1991,25 @@
         search.innerHTML = '<h3 style="text-align: center;">Loading search results...</h3>';
     }
 
+    var themePicker = document.getElementById("theme-picker");
+    var themeChoices = document.getElementById("theme-choices");
+    if (themePicker && themeChoices) {
+        themePicker.onclick = function() {
+            if (hasClass(themeChoices, "hidden")) {
+                removeClass(themeChoices, "hidden");
+            } else {
+                addClass(themeChoices, "hidden");
+            }
+        };
+        onEach(themeChoices.getElementsByTagName("button"), function(button) {
+            button.onclick = function() {
+                // `switchTheme` and the theme elements come from storage.js
+                switchTheme(currentTheme, mainTheme, button.getAttribute("data-theme"));
+                addClass(themeChoices, "hidden");
+            };
+        });
+    }
+
     var sidebar_menu = document.getElementsByClassName("sidebar-menu")[0];
     if (sidebar_menu) {
         sidebar_menu.onclick = function() {
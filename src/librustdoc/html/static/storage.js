@@ -0,0 +1,57 @@
+/*!
+ * Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+ * file at the top-level directory of this distribution and at
+ * http://rust-lang.org/COPYRIGHT.
+ *
+ * Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+ * http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+ * <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+ * option. This file may not be copied, modified, or distributed
+ * except according to those terms.
+ */
+
+// This is loaded in the `<head>` of every page, so that the theme picked on
+// another page is applied before the page is first drawn.
+
+var currentTheme = document.getElementById("themeStyle");
+var mainTheme = document.getElementById("mainThemeStyle");
+
+function getCurrentValue(name) {
+    try {
+        if (window.localStorage) {
+            return window.localStorage.getItem(name);
+        }
+    } catch (e) {
+        // Storage is disabled, for instance for `file://` pages in some browsers
+    }
+    return null;
+}
+
+function updateLocalStorage(name, value) {
+    try {
+        if (window.localStorage) {
+            window.localStorage.setItem(name, value);
+        }
+    } catch (e) {
+        // Same as above
+    }
+}
+
+function switchTheme(styleElem, mainStyleElem, newTheme) {
+    // The themes are next to `rustdoc.css`, whatever the depth of the page
+    var newHref = mainStyleElem.href.replace(/rustdoc\.css$/, newTheme + ".css");
+    if (styleElem.href !== newHref) {
+        styleElem.href = newHref;
+    }
+    updateLocalStorage("rustdoc-theme", newTheme);
+}
+
+// Only switch to one of the themes these docs were generated with, which are
+// listed on the theme's `<link>` as it comes before this script.
+(function() {
+    var savedTheme = getCurrentValue("rustdoc-theme");
+    var themes = (currentTheme.getAttribute("data-themes") || "").split(",");
+    if (savedTheme && themes.indexOf(savedTheme) !== -1) {
+        switchTheme(currentTheme, mainTheme, savedTheme);
+    }
+}());
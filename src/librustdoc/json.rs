@@ -0,0 +1,589 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rustdoc's JSON backend, selected with `--output-format json`.
+//!
+//! This writes the cleaned crate to `<crate name>.json` in the output
+//! directory, as a single object:
+//!
+//! ```text
+//! {
+//!   "format_version": 1,
+//!   "crate_name": "foo",
+//!   "crate_version": "1.0.0" or null,
+//!   "root": "0:0",
+//!   "index": { "<id>": item, ... },
+//!   "paths": { "<id>": { "crate_id": 0, "path": ["foo", "Bar"], "kind": "struct" }, ... },
+//!   "external_crates": { "<crate id>": { "name": "std" }, ... }
+//! }
+//! ```
+//!
+//! Items refer to each other by id, which is `"<crate id>:<index>"` of their
+//! `DefId`. The index is flat: modules, traits, impls, structs, enums and
+//! variants list the ids of their children. Each item has the fields `id`,
+//! `name`, `source`, `visibility`, `docs`, `links`, `attrs`, `stability`,
+//! `deprecation`, `kind` (as used in the HTML file names, like `struct` or
+//! `fn`) and `inner`, whose fields depend on the kind.
+//!
+//! `FORMAT_VERSION` is bumped whenever the format changes in a way that
+//! existing consumers could trip over.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use rustc::hir;
+use rustc::hir::def_id::DefId;
+use rustc::middle::stability::StabilityLevel;
+use rustc::util::nodemap::FxHashMap;
+use serialize::json::{Json, ToJson};
+use syntax::abi::Abi;
+use syntax::print::pprust;
+
+use clean::{self, GetDefId};
+use doctree;
+use html::item_type::ItemType;
+use html::render::RenderInfo;
+
+/// The version of the JSON format written by this backend.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Writes the documentation of `krate` as JSON to `dst/<crate name>.json`.
+pub fn run(krate: clean::Crate, renderinfo: RenderInfo, dst: PathBuf) -> io::Result<()> {
+    let mut collector = Collector {
+        index: FxHashMap(),
+        paths: BTreeMap::new(),
+        impls: FxHashMap(),
+        implementors: FxHashMap(),
+        path: vec![],
+    };
+    let root = match krate.module {
+        Some(ref module) => {
+            collector.path.push(krate.name.clone());
+            collector.add_item(module)
+        }
+        None => None,
+    };
+    collector.link_impls();
+
+    for (&did, &(ref fqp, kind)) in &renderinfo.external_paths {
+        collector.paths.insert(id_str(did), path_json(did, fqp, ItemType::from(kind)));
+    }
+
+    let mut json = BTreeMap::new();
+    json.insert("format_version".to_string(), FORMAT_VERSION.to_json());
+    json.insert("crate_name".to_string(), krate.name.to_json());
+    json.insert("crate_version".to_string(), krate.version.to_json());
+    json.insert("root".to_string(), root.map_or(Json::Null, id));
+    json.insert("index".to_string(), Json::Object(collector.index.into_iter().map(|(did, item)| {
+        (id_str(did), Json::Object(item))
+    }).collect()));
+    json.insert("paths".to_string(), Json::Object(collector.paths));
+    json.insert("external_crates".to_string(), Json::Object(krate.externs.iter().map(|e| {
+        let mut data = BTreeMap::new();
+        data.insert("name".to_string(), e.1.name.to_json());
+        (e.0.as_u32().to_string(), Json::Object(data))
+    }).collect()));
+
+    fs::create_dir_all(&dst)?;
+    let mut file = BufWriter::new(File::create(dst.join(format!("{}.json", krate.name)))?);
+    write!(file, "{}", Json::Object(json))?;
+    file.flush()
+}
+
+struct Collector {
+    /// The JSON of every item, which `link_impls` later adds impls to.
+    index: FxHashMap<DefId, BTreeMap<String, Json>>,
+    paths: BTreeMap<String, Json>,
+    /// Impls by the type they are for
+    impls: FxHashMap<DefId, Vec<DefId>>,
+    /// Impls by the trait they implement
+    implementors: FxHashMap<DefId, Vec<DefId>>,
+    /// The path of the module being visited
+    path: Vec<String>,
+}
+
+impl Collector {
+    /// Adds `item` and everything in it to the index, returning its id
+    /// unless it was stripped.
+    fn add_item(&mut self, item: &clean::Item) -> Option<DefId> {
+        let inner = match item.inner {
+            // Stripped modules can still contain impls which aren't stripped
+            clean::StrippedItem(box clean::ModuleItem(ref m)) => {
+                self.path.push(item.name.clone().unwrap_or_default());
+                self.add_items(&m.items);
+                self.path.pop();
+                return None;
+            }
+            clean::StrippedItem(..) => return None,
+            // The impls rustdoc works out for a type's page have no id of
+            // their own; blanket impls are output where they are written
+            clean::ImplItem(ref i) if i.synthetic || i.blanket_impl.is_some() => return None,
+            clean::ModuleItem(ref m) => {
+                let is_root = item.name.as_ref().map_or(true, |name| name.is_empty());
+                if !is_root {
+                    self.path.push(item.name.clone().unwrap());
+                }
+                let items = self.add_items(&m.items);
+                self.add_path(item);
+                if !is_root {
+                    self.path.pop();
+                }
+                object(vec![
+                    ("is_crate", m.is_crate.to_json()),
+                    ("items", items),
+                ])
+            }
+            _ => {
+                self.add_path(item);
+                self.inner_json(item)
+            }
+        };
+
+        let mut data = BTreeMap::new();
+        data.insert("id".to_string(), id(item.def_id));
+        data.insert("name".to_string(), item.name.to_json());
+        data.insert("source".to_string(), item.source.to_json());
+        data.insert("visibility".to_string(), match item.visibility {
+            Some(clean::Public) => "public",
+            Some(clean::Inherited) | None => "default",
+        }.to_json());
+        data.insert("docs".to_string(), item.collapsed_doc_value().to_json());
+        data.insert("links".to_string(), Json::Object(item.attrs.links.iter().map(|link| {
+            (link.0.clone(), id(link.1))
+        }).collect()));
+        data.insert("attrs".to_string(), item.attrs.other_attrs.iter().filter(|attr| {
+            !attr.check_name("doc")
+        }).map(|attr| pprust::attribute_to_string(attr)).collect::<Vec<_>>().to_json());
+        data.insert("stability".to_string(), item.stability.to_json());
+        data.insert("deprecation".to_string(), item.deprecation.to_json());
+        data.insert("kind".to_string(), ItemType::from(item).css_class().to_json());
+        data.insert("inner".to_string(), inner);
+        self.index.insert(item.def_id, data);
+        Some(item.def_id)
+    }
+
+    fn add_items(&mut self, items: &[clean::Item]) -> Json {
+        items.iter().filter_map(|item| self.add_item(item).map(id)).collect::<Vec<_>>().to_json()
+    }
+
+    /// Records the path of an item which has its own page.
+    fn add_path(&mut self, item: &clean::Item) {
+        let fqp = match item.inner {
+            clean::ModuleItem(..) => self.path.clone(),
+            // These don't get a page of their own
+            clean::ImplItem(..) | clean::ImportItem(..) | clean::ExternCrateItem(..) |
+            clean::AutoImplItem(..) | clean::StructFieldItem(..) | clean::VariantItem(..) |
+            clean::MethodItem(..) | clean::TyMethodItem(..) | clean::AssociatedConstItem(..) |
+            clean::AssociatedTypeItem(..) | clean::TypedefItem(_, true) => return,
+            _ => match item.name {
+                Some(ref name) if self.path.len() > 0 => {
+                    let mut fqp = self.path.clone();
+                    fqp.push(name.clone());
+                    fqp
+                }
+                _ => return,
+            },
+        };
+        self.paths.insert(id_str(item.def_id), path_json(item.def_id, &fqp, ItemType::from(item)));
+    }
+
+    fn inner_json(&mut self, item: &clean::Item) -> Json {
+        match item.inner {
+            clean::ExternCrateItem(ref name, ref original) => object(vec![
+                ("name", name.to_json()),
+                ("original", original.to_json()),
+            ]),
+            clean::ImportItem(ref import) => {
+                let (name, source, glob) = match *import {
+                    clean::Import::Simple(ref name, ref source) => (Some(name), source, false),
+                    clean::Import::Glob(ref source) => (None, source, true),
+                };
+                object(vec![
+                    ("name", name.to_json()),
+                    ("source", path_str(&source.path).to_json()),
+                    ("id", source.did.map_or(Json::Null, id)),
+                    ("glob", glob.to_json()),
+                ])
+            }
+            clean::StructItem(clean::Struct {
+                struct_type, ref generics, ref fields, fields_stripped
+            }) |
+            clean::UnionItem(clean::Union {
+                struct_type, ref generics, ref fields, fields_stripped
+            }) => object(vec![
+                ("struct_type", struct_type_json(struct_type)),
+                ("generics", generics.to_json()),
+                ("fields", self.add_items(fields)),
+                ("fields_stripped", fields_stripped.to_json()),
+                ("impls", Json::Array(vec![])),
+            ]),
+            clean::EnumItem(ref e) => object(vec![
+                ("generics", e.generics.to_json()),
+                ("variants", self.add_items(&e.variants)),
+                ("variants_stripped", e.variants_stripped.to_json()),
+                ("impls", Json::Array(vec![])),
+            ]),
+            clean::VariantItem(ref v) => match v.kind {
+                clean::VariantKind::CLike => object(vec![("kind", "plain".to_json())]),
+                clean::VariantKind::Tuple(ref types) => object(vec![
+                    ("kind", "tuple".to_json()),
+                    ("types", types.to_json()),
+                ]),
+                clean::VariantKind::Struct(ref s) => object(vec![
+                    ("kind", "struct".to_json()),
+                    ("fields", self.add_items(&s.fields)),
+                    ("fields_stripped", s.fields_stripped.to_json()),
+                ]),
+            },
+            clean::StructFieldItem(ref ty) => object(vec![("type", ty.to_json())]),
+            clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => object(vec![
+                ("decl", f.decl.to_json()),
+                ("generics", f.generics.to_json()),
+                ("header", header_json(f.unsafety, f.constness, f.abi)),
+            ]),
+            clean::TyMethodItem(ref m) => object(vec![
+                ("decl", m.decl.to_json()),
+                ("generics", m.generics.to_json()),
+                ("header", header_json(m.unsafety, hir::Constness::NotConst, m.abi)),
+                ("has_body", false.to_json()),
+            ]),
+            clean::MethodItem(ref m) => object(vec![
+                ("decl", m.decl.to_json()),
+                ("generics", m.generics.to_json()),
+                ("header", header_json(m.unsafety, m.constness, m.abi)),
+                ("has_body", true.to_json()),
+            ]),
+            clean::TypedefItem(ref t, _) => object(vec![
+                ("type", t.type_.to_json()),
+                ("generics", t.generics.to_json()),
+            ]),
+            clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => object(vec![
+                ("type", s.type_.to_json()),
+                ("mutable", (s.mutability == clean::Mutable).to_json()),
+                ("expr", s.expr.to_json()),
+            ]),
+            clean::ConstantItem(ref c) => object(vec![
+                ("type", c.type_.to_json()),
+                ("expr", c.expr.to_json()),
+            ]),
+            clean::TraitItem(ref t) => object(vec![
+                ("unsafe", (t.unsafety == hir::Unsafety::Unsafe).to_json()),
+                ("items", self.add_items(&t.items)),
+                ("generics", t.generics.to_json()),
+                ("bounds", t.bounds.to_json()),
+                ("implementors", Json::Array(vec![])),
+            ]),
+            clean::ImplItem(ref i) => {
+                if let Some(did) = i.for_.def_id() {
+                    self.impls.entry(did).or_insert_with(Vec::new).push(item.def_id);
+                }
+                if let Some(did) = i.trait_.def_id() {
+                    self.implementors.entry(did).or_insert_with(Vec::new).push(item.def_id);
+                }
+                let mut provided = i.provided_trait_methods.iter().cloned().collect::<Vec<_>>();
+                provided.sort();
+                object(vec![
+                    ("unsafe", (i.unsafety == hir::Unsafety::Unsafe).to_json()),
+                    ("generics", i.generics.to_json()),
+                    ("provided_trait_methods", provided.to_json()),
+                    ("trait", i.trait_.to_json()),
+                    ("for", i.for_.to_json()),
+                    ("items", self.add_items(&i.items)),
+                    ("negative", (i.polarity == Some(clean::ImplPolarity::Negative)).to_json()),
+                ])
+            }
+            clean::AutoImplItem(ref i) => object(vec![
+                ("unsafe", (i.unsafety == hir::Unsafety::Unsafe).to_json()),
+                ("trait", i.trait_.to_json()),
+            ]),
+            clean::AssociatedConstItem(ref ty, ref default) => object(vec![
+                ("type", ty.to_json()),
+                ("default", default.to_json()),
+            ]),
+            clean::AssociatedTypeItem(ref bounds, ref default) => object(vec![
+                ("bounds", bounds.to_json()),
+                ("default", default.to_json()),
+            ]),
+            clean::MacroItem(ref m) => object(vec![("source", m.source.to_json())]),
+            clean::PrimitiveItem(p) => object(vec![("name", p.as_str().to_json())]),
+            clean::ForeignTypeItem => object(vec![]),
+            clean::ModuleItem(..) | clean::StrippedItem(..) => unreachable!(),
+        }
+    }
+
+    /// Adds the impls of local types and traits to their JSON, once all the
+    /// impls have been seen.
+    fn link_impls(&mut self) {
+        for (field, impls) in vec![("impls", &self.impls), ("implementors", &self.implementors)] {
+            for (did, impl_dids) in impls {
+                let inner = self.index.get_mut(did).and_then(|item| item.get_mut("inner"));
+                if let Some(&mut Json::Object(ref mut inner)) = inner {
+                    if let Some(list) = inner.get_mut(field) {
+                        *list = impl_dids.iter().cloned().map(id).collect::<Vec<_>>().to_json();
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+}
+
+fn id_str(did: DefId) -> String {
+    format!("{}:{}", did.krate.as_u32(), did.index.as_u32())
+}
+
+fn id(did: DefId) -> Json {
+    Json::String(id_str(did))
+}
+
+fn path_json(did: DefId, fqp: &[String], kind: ItemType) -> Json {
+    object(vec![
+        ("crate_id", did.krate.as_u32().to_json()),
+        ("path", fqp.to_json()),
+        ("kind", kind.css_class().to_json()),
+    ])
+}
+
+fn path_str(path: &clean::Path) -> String {
+    let segments = path.segments.iter().map(|s| &s.name[..]).collect::<Vec<_>>().join("::");
+    if path.global { format!("::{}", segments) } else { segments }
+}
+
+fn struct_type_json(struct_type: doctree::StructType) -> Json {
+    match struct_type {
+        doctree::Plain => "plain",
+        doctree::Tuple => "tuple",
+        doctree::Unit => "unit",
+    }.to_json()
+}
+
+fn header_json(unsafety: hir::Unsafety, constness: hir::Constness, abi: Abi) -> Json {
+    object(vec![
+        ("unsafe", (unsafety == hir::Unsafety::Unsafe).to_json()),
+        ("const", (constness == hir::Constness::Const).to_json()),
+        ("abi", abi.name().to_json()),
+    ])
+}
+
+/// Maps empty strings, which the cleaned crate uses for missing values, to
+/// `null`.
+fn non_empty(s: &str) -> Json {
+    if s.is_empty() { Json::Null } else { s.to_json() }
+}
+
+impl ToJson for clean::Span {
+    fn to_json(&self) -> Json {
+        if self.loline == 0 && self.hiline == 0 {
+            return Json::Null;
+        }
+        object(vec![
+            ("filename", self.filename.to_string().to_json()),
+            ("begin", vec![self.loline, self.locol].to_json()),
+            ("end", vec![self.hiline, self.hicol].to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::Stability {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("level", match self.level {
+                StabilityLevel::Stable => "stable",
+                StabilityLevel::Unstable => "unstable",
+            }.to_json()),
+            ("feature", non_empty(&self.feature)),
+            ("since", non_empty(&self.since)),
+            ("deprecated_since", non_empty(&self.deprecated_since)),
+            ("deprecated_reason", non_empty(&self.deprecated_reason)),
+            ("unstable_reason", non_empty(&self.unstable_reason)),
+            ("issue", self.issue.to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::Deprecation {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("since", non_empty(&self.since)),
+            ("note", non_empty(&self.note)),
+        ])
+    }
+}
+
+impl ToJson for clean::Lifetime {
+    fn to_json(&self) -> Json {
+        self.get_ref().to_json()
+    }
+}
+
+impl ToJson for clean::Generics {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("lifetimes", self.lifetimes.to_json()),
+            ("type_params", self.type_params.iter().map(|param| object(vec![
+                ("name", param.name.to_json()),
+                ("bounds", param.bounds.to_json()),
+                ("default", param.default.to_json()),
+            ])).collect::<Vec<_>>().to_json()),
+            ("where_predicates", self.where_predicates.to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::WherePredicate {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::WherePredicate::BoundPredicate { ref ty, ref bounds } => object(vec![
+                ("kind", "bound_predicate".to_json()),
+                ("type", ty.to_json()),
+                ("bounds", bounds.to_json()),
+            ]),
+            clean::WherePredicate::RegionPredicate { ref lifetime, ref bounds } => object(vec![
+                ("kind", "region_predicate".to_json()),
+                ("lifetime", lifetime.to_json()),
+                ("bounds", bounds.to_json()),
+            ]),
+            clean::WherePredicate::EqPredicate { ref lhs, ref rhs } => object(vec![
+                ("kind", "eq_predicate".to_json()),
+                ("lhs", lhs.to_json()),
+                ("rhs", rhs.to_json()),
+            ]),
+        }
+    }
+}
+
+impl ToJson for clean::TyParamBound {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::RegionBound(ref lifetime) => object(vec![
+                ("kind", "outlives".to_json()),
+                ("lifetime", lifetime.to_json()),
+            ]),
+            clean::TraitBound(ref poly_trait, modifier) => object(vec![
+                ("kind", "trait_bound".to_json()),
+                ("trait", poly_trait.trait_.to_json()),
+                ("lifetimes", poly_trait.lifetimes.to_json()),
+                ("maybe", (modifier == hir::TraitBoundModifier::Maybe).to_json()),
+            ]),
+        }
+    }
+}
+
+impl ToJson for clean::FnDecl {
+    fn to_json(&self) -> Json {
+        object(vec![
+            ("inputs", self.inputs.values.iter().map(|arg| object(vec![
+                ("name", arg.name.to_json()),
+                ("type", arg.type_.to_json()),
+            ])).collect::<Vec<_>>().to_json()),
+            ("output", match self.output {
+                clean::Return(ref ty) => ty.to_json(),
+                clean::DefaultReturn => Json::Null,
+            }),
+            ("variadic", self.variadic.to_json()),
+        ])
+    }
+}
+
+impl ToJson for clean::PathParameters {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::PathParameters::AngleBracketed { ref lifetimes, ref types, ref bindings } => {
+                object(vec![
+                    ("kind", "angle_bracketed".to_json()),
+                    ("lifetimes", lifetimes.to_json()),
+                    ("types", types.to_json()),
+                    ("bindings", bindings.iter().map(|binding| object(vec![
+                        ("name", binding.name.to_json()),
+                        ("type", binding.ty.to_json()),
+                    ])).collect::<Vec<_>>().to_json()),
+                ])
+            }
+            clean::PathParameters::Parenthesized { ref inputs, ref output } => object(vec![
+                ("kind", "parenthesized".to_json()),
+                ("inputs", inputs.to_json()),
+                ("output", output.to_json()),
+            ]),
+        }
+    }
+}
+
+impl ToJson for clean::Type {
+    fn to_json(&self) -> Json {
+        match *self {
+            clean::ResolvedPath { ref path, ref typarams, did, .. } => object(vec![
+                ("kind", "resolved_path".to_json()),
+                ("name", path_str(path).to_json()),
+                ("id", id(did)),
+                ("args", path.segments.last().map_or(Json::Null, |s| s.params.to_json())),
+                ("bounds", typarams.to_json()),
+            ]),
+            clean::Generic(ref name) => object(vec![
+                ("kind", "generic".to_json()),
+                ("name", name.to_json()),
+            ]),
+            clean::Primitive(p) => object(vec![
+                ("kind", "primitive".to_json()),
+                ("name", p.as_str().to_json()),
+            ]),
+            clean::BareFunction(ref f) => object(vec![
+                ("kind", "function_pointer".to_json()),
+                ("decl", f.decl.to_json()),
+                ("lifetimes", f.generics.lifetimes.to_json()),
+                ("header", header_json(f.unsafety, hir::Constness::NotConst, f.abi)),
+            ]),
+            clean::Tuple(ref types) => object(vec![
+                ("kind", "tuple".to_json()),
+                ("types", types.to_json()),
+            ]),
+            clean::Slice(ref ty) => object(vec![
+                ("kind", "slice".to_json()),
+                ("type", ty.to_json()),
+            ]),
+            clean::Array(ref ty, ref len) => object(vec![
+                ("kind", "array".to_json()),
+                ("type", ty.to_json()),
+                ("len", len.to_json()),
+            ]),
+            clean::Never => object(vec![("kind", "never".to_json())]),
+            clean::Unique(ref ty) => object(vec![
+                ("kind", "unique".to_json()),
+                ("type", ty.to_json()),
+            ]),
+            clean::RawPointer(mutability, ref ty) => object(vec![
+                ("kind", "raw_pointer".to_json()),
+                ("mutable", (mutability == clean::Mutable).to_json()),
+                ("type", ty.to_json()),
+            ]),
+            clean::BorrowedRef { ref lifetime, mutability, ref type_ } => object(vec![
+                ("kind", "borrowed_ref".to_json()),
+                ("lifetime", lifetime.to_json()),
+                ("mutable", (mutability == clean::Mutable).to_json()),
+                ("type", type_.to_json()),
+            ]),
+            clean::QPath { ref name, ref self_type, ref trait_ } => object(vec![
+                ("kind", "qualified_path".to_json()),
+                ("name", name.to_json()),
+                ("self_type", self_type.to_json()),
+                ("trait", trait_.to_json()),
+            ]),
+            clean::Infer => object(vec![("kind", "infer".to_json())]),
+            clean::ImplTrait(ref bounds) => object(vec![
+                ("kind", "impl_trait".to_json()),
+                ("bounds", bounds.to_json()),
+            ]),
+        }
+    }
+}
@@ -29,6 +29,7 @@ extern crate arena;
 extern crate getopts;
 extern crate env_logger;
 extern crate html_diff;
+extern crate linkchecker;
 extern crate libc;
 extern crate rustc;
 extern crate rustc_data_structures;
@@ -63,8 +64,10 @@ use std::process;
 use std::sync::mpsc::channel;
 
 use externalfiles::ExternalHtml;
+use serialize::json::ToJson;
+use errors::emitter::ColorConfig;
 use rustc::session::search_paths::SearchPaths;
-use rustc::session::config::{ErrorOutputType, RustcOptGroup, nightly_options,
+use rustc::session::config::{self, ErrorOutputType, RustcOptGroup, nightly_options,
                              Externs};
 
 #[macro_use]
@@ -75,6 +78,7 @@ pub mod core;
 pub mod doctree;
 pub mod fold;
 pub mod html {
+    pub mod check_links;
     pub mod highlight;
     pub mod escape;
     pub mod item_type;
@@ -84,9 +88,12 @@ pub mod html {
     pub mod render;
     pub mod toc;
 }
+pub mod json;
 pub mod markdown;
 pub mod passes;
 pub mod plugins;
+pub mod scrape_examples;
+pub mod theme;
 pub mod visit_ast;
 pub mod visit_lib;
 pub mod test;
@@ -140,7 +147,7 @@ pub fn opts() -> Vec<RustcOptGroup> {
                      "[rust]")
         }),
         stable("w", |o| {
-            o.optopt("w", "output-format", "the output type to write", "[html]")
+            o.optopt("w", "output-format", "the output type to write", "[html|json]")
         }),
         stable("o", |o| o.optopt("o", "output", "where to place the output", "PATH")),
         stable("crate-name", |o| {
@@ -253,6 +260,64 @@ pub fn opts() -> Vec<RustcOptGroup> {
         unstable("linker", |o| {
             o.optopt("", "linker", "linker used for building executable test code", "PATH")
         }),
+        unstable("error-format", |o| {
+            o.optopt("",
+                     "error-format",
+                     "how errors and other messages are produced",
+                     "human|json|short")
+        }),
+        unstable("color", |o| {
+            o.optopt("",
+                     "color",
+                     "configure coloring of output:
+                                auto   = colorize, if output goes to a tty (default);
+                                always = always colorize output;
+                                never  = never colorize output",
+                     "auto|always|never")
+        }),
+        unstable("show-coverage", |o| {
+            o.optflag("",
+                      "show-coverage",
+                      "print how many items are documented and have examples instead of \
+                       generating documentation, as a table or as json with `--output-format`")
+        }),
+        unstable("scrape-examples-output-path", |o| {
+            o.optopt("",
+                     "scrape-examples-output-path",
+                     "find the calls this crate makes to the crates given with \
+                      `--scrape-examples-target-crate` and write them to PATH, instead of \
+                      generating documentation",
+                     "PATH")
+        }),
+        unstable("scrape-examples-target-crate", |o| {
+            o.optmulti("",
+                       "scrape-examples-target-crate",
+                       "crate to look for calls to when scraping examples",
+                       "NAME")
+        }),
+        unstable("with-examples", |o| {
+            o.optmulti("",
+                       "with-examples",
+                       "file of calls written by `--scrape-examples-output-path`, to show on \
+                        the pages of the called functions",
+                       "PATH")
+        }),
+        unstable("check-links", |o| {
+            o.optflag("", "check-links", "fail on broken links in the generated documentation")
+        }),
+        unstable("theme", |o| {
+            o.optmulti("",
+                       "theme",
+                       "additional theme for the generated documentation, which readers can \
+                        pick instead of the built-in one",
+                       "PATH")
+        }),
+        unstable("check-theme", |o| {
+            o.optmulti("",
+                       "check-theme",
+                       "check that the theme styles everything the built-in theme does, and exit",
+                       "PATH")
+        }),
     ]
 }
 
@@ -302,6 +367,29 @@ pub fn main_args(args: &[String]) -> isize {
         return 0;
     }
 
+    let to_check = matches.opt_strs("check-theme");
+    if !to_check.is_empty() {
+        let mut failed = false;
+        for theme_file in &to_check {
+            match theme::check_theme_file(Path::new(theme_file)) {
+                Ok(ref missing) if missing.is_empty() => println!("{}: ok", theme_file),
+                Ok(missing) => {
+                    failed = true;
+                    println!("{}: missing rules for the following selectors of the built-in \
+                              theme:", theme_file);
+                    for selector in missing {
+                        println!("    {}", selector);
+                    }
+                }
+                Err(err) => {
+                    print_error(format!("failed to read theme `{}`: {}", theme_file, err));
+                    return 1;
+                }
+            }
+        }
+        return if failed { 1 } else { 0 };
+    }
+
     if matches.free.is_empty() {
         print_error("missing file operand");
         return 1;
@@ -312,9 +400,32 @@ pub fn main_args(args: &[String]) -> isize {
     }
     let input = &matches.free[0];
 
+    let color = match matches.opt_str("color").as_ref().map(|s| &s[..]) {
+        Some("auto") | None => ColorConfig::Auto,
+        Some("always") => ColorConfig::Always,
+        Some("never") => ColorConfig::Never,
+        Some(arg) => {
+            print_error(format!("argument for --color must be `auto`, `always` or `never` \
+                                 (instead was `{}`)", arg));
+            return 1;
+        }
+    };
+    let error_format = match matches.opt_str("error-format").as_ref().map(|s| &s[..]) {
+        Some("human") | None => ErrorOutputType::HumanReadable(color),
+        Some("json") => ErrorOutputType::Json(false),
+        Some("pretty-json") => ErrorOutputType::Json(true),
+        Some("short") => ErrorOutputType::Short(color),
+        Some(arg) => {
+            print_error(format!("argument for --error-format must be `human`, `json` or \
+                                 `short` (instead was `{}`)", arg));
+            return 1;
+        }
+    };
+    let debugging_options = config::build_debugging_options(&matches, error_format);
+
     let mut libs = SearchPaths::new();
     for s in &matches.opt_strs("L") {
-        libs.add_path(s, ErrorOutputType::default());
+        libs.add_path(s, error_format);
     }
     let externs = match parse_externs(&matches) {
         Ok(ex) => ex,
@@ -354,6 +465,37 @@ pub fn main_args(args: &[String]) -> isize {
         }
     }
 
+    let mut themes = Vec::new();
+    for theme in matches.opt_strs("theme") {
+        let path = PathBuf::from(&theme);
+        if !path.is_file() || path.extension().map_or(true, |ext| ext != "css") {
+            print_error(format!("option --theme argument must be a CSS file (`{}` isn't)", theme));
+            return 1;
+        }
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        // Keep the name usable as is in the HTML and in the theme picker, and
+        // away from the names of the other style sheets
+        if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') ||
+           ["main", "normalize", "rustdoc", "theme"].contains(&&*name) ||
+           themes.iter().any(|t: &PathBuf| t.file_stem() == path.file_stem()) {
+            print_error(format!("the theme `{}` can't be named `{}`", theme, name));
+            return 1;
+        }
+        match theme::check_theme_file(&path) {
+            Ok(ref missing) if missing.is_empty() => {}
+            Ok(_) => {
+                eprintln!("warning: the theme `{}` doesn't style everything the built-in one \
+                           does; run rustdoc with `--check-theme {}` for the details",
+                          theme, theme);
+            }
+            Err(err) => {
+                print_error(format!("failed to read theme `{}`: {}", theme, err));
+                return 1;
+            }
+        }
+        themes.push(path);
+    }
+
     let external_html = match ExternalHtml::load(
             &matches.opt_strs("html-in-header"),
             &matches.opt_strs("html-before-content"),
@@ -373,11 +515,12 @@ pub fn main_args(args: &[String]) -> isize {
     match (should_test, markdown_input) {
         (true, true) => {
             return markdown::test(input, cfgs, libs, externs, test_args, maybe_sysroot,
-                                  render_type, display_warnings, linker)
+                                  render_type, display_warnings, linker, error_format)
         }
         (true, false) => {
             return test::run(Path::new(input), cfgs, libs, externs, test_args, crate_name,
-                             maybe_sysroot, render_type, display_warnings, linker)
+                             maybe_sysroot, render_type, display_warnings, linker,
+                             error_format, debugging_options)
         }
         (false, true) => return markdown::render(Path::new(input),
                                                  output.unwrap_or(PathBuf::from("doc")),
@@ -388,18 +531,60 @@ pub fn main_args(args: &[String]) -> isize {
     }
 
     let output_format = matches.opt_str("w");
-    let res = acquire_input(PathBuf::from(input), externs, &matches, move |out| {
+    let show_coverage = matches.opt_present("show-coverage");
+    let scraping_examples = matches.opt_present("scrape-examples-output-path");
+    let check_links = matches.opt_present("check-links");
+    if output_format.as_ref().map(|s| &**s) == Some("json") &&
+       !nightly_options::is_unstable_enabled(&matches) {
+        print_error("the json output format is unstable and requires `-Z unstable-options`");
+        return 1;
+    }
+    let res = acquire_input(PathBuf::from(input), externs, error_format, debugging_options,
+                            &matches, move |out| {
         let Output { krate, passes, renderinfo } = out;
+        if scraping_examples {
+            // The calls have already been written out while the crate was analyzed
+            return 0;
+        }
+        if show_coverage {
+            let (_, report) = passes::CoverageReport::calculate(krate);
+            match output_format.as_ref().map(|s| &**s) {
+                Some("json") => println!("{}", report.to_json()),
+                _ => report.print_table(),
+            }
+            return 0;
+        }
         info!("going to format");
         match output_format.as_ref().map(|s| &**s) {
             Some("html") | None => {
+                let dst = output.unwrap_or(PathBuf::from("doc"));
+                let crate_name = krate.name.clone();
+                let link_sources = if check_links {
+                    Some(html::check_links::DocLinkSources::collect(&krate))
+                } else {
+                    None
+                };
                 html::render::run(krate, &external_html, playground_url,
-                                  output.unwrap_or(PathBuf::from("doc")),
+                                  dst.clone(),
                                   passes.into_iter().collect(),
                                   css_file_extension,
+                                  themes,
                                   renderinfo,
                                   render_type)
                     .expect("failed to generate documentation");
+                if let Some(ref sources) = link_sources {
+                    let broken = html::check_links::check(&dst, &crate_name, sources);
+                    if broken > 0 {
+                        eprintln!("error: aborting due to {} broken link{}",
+                                  broken, if broken == 1 { "" } else { "s" });
+                        return 1;
+                    }
+                }
+                0
+            }
+            Some("json") => {
+                json::run(krate, renderinfo, output.unwrap_or(PathBuf::from("doc")))
+                    .expect("failed to generate documentation");
                 0
             }
             Some(s) => {
@@ -427,14 +612,18 @@ fn print_error<T>(error_message: T) where T: Display {
 /// and files and then generates the necessary rustdoc output for formatting.
 fn acquire_input<R, F>(input: PathBuf,
                        externs: Externs,
+                       error_format: ErrorOutputType,
+                       debugging_options: config::DebuggingOptions,
                        matches: &getopts::Matches,
                        f: F)
                        -> Result<R, String>
 where R: 'static + Send, F: 'static + Send + FnOnce(Output) -> R {
     match matches.opt_str("r").as_ref().map(|s| &**s) {
-        Some("rust") => Ok(rust_input(input, externs, matches, f)),
+        Some("rust") => {
+            Ok(rust_input(input, externs, error_format, debugging_options, matches, f))
+        }
         Some(s) => Err(format!("unknown input format: {}", s)),
-        None => Ok(rust_input(input, externs, matches, f))
+        None => Ok(rust_input(input, externs, error_format, debugging_options, matches, f))
     }
 }
 
@@ -460,7 +649,12 @@ fn parse_externs(matches: &getopts::Matches) -> Result<Externs, String> {
 /// generated from the cleaned AST of the crate.
 ///
 /// This form of input will run all of the plug/cleaning passes
-fn rust_input<R, F>(cratefile: PathBuf, externs: Externs, matches: &getopts::Matches, f: F) -> R
+fn rust_input<R, F>(cratefile: PathBuf,
+                    externs: Externs,
+                    error_format: ErrorOutputType,
+                    debugging_options: config::DebuggingOptions,
+                    matches: &getopts::Matches,
+                    f: F) -> R
 where R: 'static + Send, F: 'static + Send + FnOnce(Output) -> R {
     let mut default_passes = !matches.opt_present("no-defaults");
     let mut passes = matches.opt_strs("passes");
@@ -480,7 +674,7 @@ where R: 'static + Send, F: 'static + Send + FnOnce(Output) -> R {
     // First, parse the crate and extract all relevant information.
     let mut paths = SearchPaths::new();
     for s in &matches.opt_strs("L") {
-        paths.add_path(s, ErrorOutputType::default());
+        paths.add_path(s, error_format);
     }
     let cfgs = matches.opt_strs("cfg");
     let triple = matches.opt_str("target");
@@ -488,21 +682,22 @@ where R: 'static + Send, F: 'static + Send + FnOnce(Output) -> R {
     let crate_name = matches.opt_str("crate-name");
     let crate_version = matches.opt_str("crate-version");
     let plugin_path = matches.opt_str("plugin-path");
+    let scrape_examples = scrape_examples::Options {
+        output_path: matches.opt_str("scrape-examples-output-path").map(PathBuf::from),
+        target_crates: matches.opt_strs("scrape-examples-target-crate"),
+        with_examples: matches.opt_strs("with-examples").into_iter().map(PathBuf::from).collect(),
+    };
 
     info!("starting to run rustc");
     let display_warnings = matches.opt_present("display-warnings");
 
-    let force_unstable_if_unmarked = matches.opt_strs("Z").iter().any(|x| {
-        *x == "force-unstable-if-unmarked"
-    });
-
     let (tx, rx) = channel();
     rustc_driver::monitor(move || {
         use rustc::session::config::Input;
 
         let (mut krate, renderinfo) =
             core::run_core(paths, cfgs, externs, Input::File(cratefile), triple, maybe_sysroot,
-                           display_warnings, force_unstable_if_unmarked);
+                           display_warnings, error_format, debugging_options, scrape_examples);
 
         info!("finished with rustc");
 
@@ -573,7 +768,6 @@ where R: 'static + Send, F: 'static + Send + FnOnce(Output) -> R {
 fn check_deprecated_options(matches: &getopts::Matches) {
     let deprecated_flags = [
        "input-format",
-       "output-format",
        "plugin-path",
        "plugins",
        "no-defaults",
@@ -0,0 +1,193 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::BTreeMap;
+use std::ops;
+
+use serialize::json::{Json, ToJson};
+
+use clean::{self, AttributesExt, NestedAttributesExt, Item};
+use fold::DocFolder;
+use html::markdown::has_rust_code_block;
+use plugins;
+
+/// Prints how many of the items left in the crate are documented and have
+/// examples, per module.
+pub fn calculate_doc_coverage(krate: clean::Crate) -> plugins::PluginResult {
+    let (krate, report) = CoverageReport::calculate(krate);
+    report.print_table();
+    krate
+}
+
+/// The number of items in a module, and how many of them are documented and
+/// have examples.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ItemCount {
+    pub total: u64,
+    pub with_docs: u64,
+    pub with_examples: u64,
+}
+
+impl ItemCount {
+    fn count_item(&mut self, item: &Item) {
+        self.total += 1;
+        if let Some(docs) = item.collapsed_doc_value() {
+            if !docs.trim().is_empty() {
+                self.with_docs += 1;
+            }
+            if has_rust_code_block(&docs) {
+                self.with_examples += 1;
+            }
+        }
+    }
+
+    pub fn docs_percentage(&self) -> f64 {
+        percentage(self.with_docs, self.total)
+    }
+
+    pub fn examples_percentage(&self) -> f64 {
+        percentage(self.with_examples, self.total)
+    }
+}
+
+fn percentage(count: u64, total: u64) -> f64 {
+    if total == 0 { 100.0 } else { count as f64 * 100.0 / total as f64 }
+}
+
+impl ops::AddAssign for ItemCount {
+    fn add_assign(&mut self, rhs: ItemCount) {
+        self.total += rhs.total;
+        self.with_docs += rhs.with_docs;
+        self.with_examples += rhs.with_examples;
+    }
+}
+
+impl ToJson for ItemCount {
+    fn to_json(&self) -> Json {
+        let mut data = BTreeMap::new();
+        data.insert("total".to_owned(), self.total.to_json());
+        data.insert("with_docs".to_owned(), self.with_docs.to_json());
+        data.insert("with_examples".to_owned(), self.with_examples.to_json());
+        data.insert("docs_percentage".to_owned(), self.docs_percentage().to_json());
+        data.insert("examples_percentage".to_owned(), self.examples_percentage().to_json());
+        Json::Object(data)
+    }
+}
+
+/// The documentation coverage of a crate, by module path.
+///
+/// Only the items left after the other passes are counted, so private items
+/// only count with `--document-private-items`. Items marked `#[doc(hidden)]`
+/// never count, and neither do the items of trait impls, which get their
+/// docs from the trait.
+pub struct CoverageReport {
+    pub modules: BTreeMap<String, ItemCount>,
+}
+
+impl CoverageReport {
+    pub fn calculate(krate: clean::Crate) -> (clean::Crate, CoverageReport) {
+        let mut calculator = CoverageCalculator {
+            modules: BTreeMap::new(),
+            path: vec![krate.name.clone()],
+        };
+        let krate = calculator.fold_crate(krate);
+        (krate, CoverageReport { modules: calculator.modules })
+    }
+
+    pub fn total(&self) -> ItemCount {
+        let mut total = ItemCount::default();
+        for count in self.modules.values() {
+            total += *count;
+        }
+        total
+    }
+
+    pub fn print_table(&self) {
+        let width = self.modules.keys().map(|name| name.len()).max().unwrap_or(0).max(6);
+        let separator = format!("+-{}-+------------+------------+------------+------------+",
+                                "-".repeat(width));
+        let print_row = |name: &str, count: &ItemCount| {
+            println!("| {:<width$} | {:>10} | {:>9.1}% | {:>10} | {:>9.1}% |",
+                     name, format!("{}/{}", count.with_docs, count.total),
+                     count.docs_percentage(), count.with_examples, count.examples_percentage(),
+                     width = width);
+        };
+
+        println!("{}", separator);
+        println!("| {:<width$} | {:>10} | {:>10} | {:>10} | {:>10} |",
+                 "Module", "Documented", "Percentage", "Examples", "Percentage",
+                 width = width);
+        println!("{}", separator);
+        for (name, count) in &self.modules {
+            print_row(name, count);
+        }
+        println!("{}", separator);
+        print_row("Total", &self.total());
+        println!("{}", separator);
+    }
+}
+
+impl ToJson for CoverageReport {
+    fn to_json(&self) -> Json {
+        let mut data = BTreeMap::new();
+        data.insert("modules".to_owned(), self.modules.to_json());
+        data.insert("total".to_owned(), self.total().to_json());
+        Json::Object(data)
+    }
+}
+
+struct CoverageCalculator {
+    modules: BTreeMap<String, ItemCount>,
+    /// The path of the module being visited
+    path: Vec<String>,
+}
+
+impl DocFolder for CoverageCalculator {
+    fn fold_item(&mut self, i: Item) -> Option<Item> {
+        if i.attrs.lists("doc").has_word("hidden") {
+            return Some(i);
+        }
+
+        match i.inner {
+            // Items of trait impls are documented on the trait
+            clean::ImplItem(clean::Impl { trait_: Some(..), .. }) => return Some(i),
+            // Items which just hold other items don't need docs of their own
+            clean::StrippedItem(..) | clean::ImplItem(..) | clean::ImportItem(..) |
+            clean::ExternCrateItem(..) | clean::PrimitiveItem(..) |
+            clean::AutoImplItem(..) => {}
+            _ => {
+                let module = self.path.join("::");
+                self.modules.entry(module).or_insert_with(ItemCount::default).count_item(&i);
+            }
+        }
+
+        match i.inner {
+            clean::ModuleItem(..) | clean::StrippedItem(box clean::ModuleItem(..)) => {
+                let name = i.name.clone().unwrap_or_default();
+                let is_root = name.is_empty();
+                if !is_root {
+                    self.path.push(name);
+                }
+                let ret = self.fold_item_recur(i);
+                if !is_root {
+                    self.path.pop();
+                }
+                ret
+            }
+            _ => self.fold_item_recur(i),
+        }
+    }
+
+    fn fold_crate(&mut self, mut c: clean::Crate) -> clean::Crate {
+        // Unlike the default, leave the traits of other crates alone
+        c.module = c.module.and_then(|module| self.fold_item(module));
+        c
+    }
+}
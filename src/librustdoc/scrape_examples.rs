@@ -0,0 +1,232 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Finding calls to the documented functions in other crates, such as the
+//! examples of a package, so they can be shown on the item pages.
+//!
+//! This happens in two steps. Each example crate is first passed to rustdoc
+//! with `--scrape-examples-output-path`, which type-checks it and writes the
+//! calls it makes into the target crates to a json file. Those files are then
+//! given to the run documenting the target crate with `--with-examples`.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use rustc::hir::{self, intravisit};
+use rustc::hir::def::Def;
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::hir::intravisit::{Visitor, NestedVisitorMap};
+use rustc::ty::{self, TyCtxt};
+use rustc::util::nodemap::FxHashMap;
+use serialize::json;
+use syntax_pos::{Span, DUMMY_SP, SyntaxContext};
+
+use clean;
+
+/// The options controlling the scraping and loading of examples.
+#[derive(Clone, Default, Debug)]
+pub struct Options {
+    /// Where to write the calls found in the crate, if it is an example to
+    /// scrape rather than a crate to document
+    pub output_path: Option<PathBuf>,
+    /// The crates whose functions we look for calls to
+    pub target_crates: Vec<String>,
+    /// The files written by scraping example crates earlier
+    pub with_examples: Vec<PathBuf>,
+}
+
+/// An item of an example crate calling a documented function.
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+pub struct ScrapedExample {
+    /// The file the example is in, as given to the compiler
+    pub file: String,
+    /// The line the example starts on
+    pub line: usize,
+    /// The source code of the whole calling item
+    pub code: String,
+    /// The first and last lines of each call in the example
+    pub call_lines: Vec<(usize, usize)>,
+}
+
+/// The examples found for each function, keyed by the crate name and the
+/// def-path of the function.
+pub type AllCalls = BTreeMap<String, Vec<ScrapedExample>>;
+
+fn fn_key(tcx: TyCtxt, did: DefId) -> String {
+    format!("{}{}", tcx.crate_name(did.krate), tcx.def_path(did).to_string_no_crate())
+}
+
+/// Finds the calls the local crate makes to functions and methods of the
+/// target crates and writes them to `output_path`.
+pub fn scrape<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, options: &Options, output_path: &Path)
+                        -> io::Result<()> {
+    let empty_tables = ty::TypeckTables::empty(None);
+    let mut finder = CallFinder {
+        tcx,
+        target_crates: &options.target_crates,
+        tables: &empty_tables,
+        item_span: DUMMY_SP,
+        calls: AllCalls::new(),
+    };
+    intravisit::walk_crate(&mut finder, tcx.hir.krate());
+
+    let mut file = File::create(output_path)?;
+    write!(file, "{}", json::as_json(&finder.calls))
+}
+
+/// Reads the scraped examples in `options.with_examples` and picks out the
+/// ones calling the functions and methods of `krate`.
+pub fn load<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, options: &Options, krate: &clean::Crate)
+                      -> Result<FxHashMap<DefId, Vec<ScrapedExample>>, String> {
+    let mut calls = AllCalls::new();
+    for path in &options.with_examples {
+        let mut contents = String::new();
+        File::open(path).and_then(|mut f| f.read_to_string(&mut contents))
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let file_calls: AllCalls = json::decode(&contents)
+            .map_err(|e| format!("failed to load examples from {}: {}", path.display(), e))?;
+        for (key, examples) in file_calls {
+            calls.entry(key).or_insert_with(Vec::new).extend(examples);
+        }
+    }
+
+    let mut examples = FxHashMap();
+    if let Some(ref module) = krate.module {
+        collect_examples(tcx, module, &mut calls, &mut examples);
+    }
+    Ok(examples)
+}
+
+fn collect_examples(tcx: TyCtxt, item: &clean::Item, calls: &mut AllCalls,
+                    examples: &mut FxHashMap<DefId, Vec<ScrapedExample>>) {
+    match item.inner {
+        clean::ModuleItem(ref m) => {
+            for item in &m.items {
+                collect_examples(tcx, item, calls, examples);
+            }
+        }
+        clean::ImplItem(ref i) => {
+            for item in &i.items {
+                collect_examples(tcx, item, calls, examples);
+            }
+        }
+        clean::TraitItem(ref t) => {
+            for item in &t.items {
+                collect_examples(tcx, item, calls, examples);
+            }
+        }
+        clean::FunctionItem(..) | clean::MethodItem(..) | clean::TyMethodItem(..)
+            if item.def_id.krate == LOCAL_CRATE => {
+            if let Some(found) = calls.remove(&fn_key(tcx, item.def_id)) {
+                examples.insert(item.def_id, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+struct CallFinder<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    target_crates: &'a [String],
+    tables: &'a ty::TypeckTables<'tcx>,
+    /// The span of the item whose body is being visited
+    item_span: Span,
+    calls: AllCalls,
+}
+
+impl<'a, 'tcx> CallFinder<'a, 'tcx> {
+    fn record_call(&mut self, did: DefId, call_span: Span) {
+        if did.is_local() || call_span.ctxt() != SyntaxContext::empty() {
+            return;
+        }
+        let crate_name = self.tcx.crate_name(did.krate);
+        if !self.target_crates.iter().any(|name| *name == *crate_name.as_str()) {
+            return;
+        }
+
+        let codemap = self.tcx.sess.codemap();
+        let code = match codemap.span_to_snippet(self.item_span) {
+            Ok(code) => code,
+            Err(_) => return,
+        };
+        let item_lo = codemap.lookup_char_pos(self.item_span.lo());
+        let call_lines = (codemap.lookup_char_pos(call_span.lo()).line,
+                          codemap.lookup_char_pos(call_span.hi()).line);
+        let file = item_lo.file.name.to_string();
+
+        let examples = self.calls.entry(fn_key(self.tcx, did)).or_insert_with(Vec::new);
+        // Several calls from the same item make up a single example
+        if let Some(example) = examples.iter_mut()
+                                       .find(|e| e.file == file && e.line == item_lo.line) {
+            example.call_lines.push(call_lines);
+            return;
+        }
+        examples.push(ScrapedExample {
+            file,
+            line: item_lo.line,
+            code,
+            call_lines: vec![call_lines],
+        });
+    }
+
+    fn with_item_span<F: FnOnce(&mut Self)>(&mut self, span: Span, f: F) {
+        let orig_span = mem::replace(&mut self.item_span, span);
+        f(self);
+        self.item_span = orig_span;
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for CallFinder<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::All(&self.tcx.hir)
+    }
+
+    fn visit_nested_body(&mut self, body: hir::BodyId) {
+        let orig_tables = mem::replace(&mut self.tables, self.tcx.body_tables(body));
+        let body = self.tcx.hir.body(body);
+        self.visit_body(body);
+        self.tables = orig_tables;
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item) {
+        self.with_item_span(item.span, |this| intravisit::walk_item(this, item));
+    }
+
+    fn visit_trait_item(&mut self, item: &'tcx hir::TraitItem) {
+        self.with_item_span(item.span, |this| intravisit::walk_trait_item(this, item));
+    }
+
+    fn visit_impl_item(&mut self, item: &'tcx hir::ImplItem) {
+        self.with_item_span(item.span, |this| intravisit::walk_impl_item(this, item));
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        let callee = match expr.node {
+            hir::ExprCall(ref callee, _) => match callee.node {
+                hir::ExprPath(ref qpath) => match self.tables.qpath_def(qpath, callee.hir_id) {
+                    Def::Fn(did) | Def::Method(did) => Some(did),
+                    _ => None,
+                },
+                _ => None,
+            },
+            hir::ExprMethodCall(..) => {
+                self.tables.type_dependent_defs().get(expr.hir_id).map(|def| def.def_id())
+            }
+            _ => None,
+        };
+        if let Some(did) = callee {
+            self.record_call(did, expr.span);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
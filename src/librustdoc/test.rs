@@ -11,8 +11,10 @@
 use std::collections::HashMap;
 use std::env;
 use std::ffi::OsString;
+use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io;
+use std::mem;
 use std::path::{Path, PathBuf};
 use std::panic::{self, AssertUnwindSafe};
 use std::process::Command;
@@ -25,7 +27,7 @@ use rustc_lint;
 use rustc::hir;
 use rustc::hir::intravisit;
 use rustc::session::{self, CompileIncomplete, config};
-use rustc::session::config::{OutputType, OutputTypes, Externs};
+use rustc::session::config::{OutputType, OutputTypes, Externs, ErrorOutputType};
 use rustc::session::search_paths::{SearchPaths, PathKind};
 use rustc_metadata::dynamic_lib::DynamicLibrary;
 use tempdir::TempDir;
@@ -36,13 +38,17 @@ use rustc_resolve::MakeGlobMap;
 use rustc_trans;
 use rustc_trans::back::link;
 use syntax::ast;
-use syntax::codemap::CodeMap;
+use syntax::codemap::{CodeMap, FilePathMapping};
 use syntax::feature_gate::UnstableFeatures;
+use syntax::json::JsonEmitter;
+use syntax::parse::{self, ParseSess};
+use syntax::visit::{self, Visitor};
 use syntax_pos::{BytePos, DUMMY_SP, Pos, Span, FileName};
 use errors;
-use errors::emitter::ColorConfig;
+use errors::emitter::{Emitter, EmitterWriter};
 
 use clean::Attributes;
+use core;
 use html::markdown::{self, RenderType};
 
 #[derive(Clone, Default)]
@@ -60,7 +66,9 @@ pub fn run(input_path: &Path,
            maybe_sysroot: Option<PathBuf>,
            render_type: RenderType,
            display_warnings: bool,
-           linker: Option<PathBuf>)
+           linker: Option<PathBuf>,
+           error_format: ErrorOutputType,
+           debugging_options: config::DebuggingOptions)
            -> isize {
     let input = config::Input::File(input_path.to_owned());
 
@@ -73,14 +81,13 @@ pub fn run(input_path: &Path,
         unstable_features: UnstableFeatures::from_environment(),
         lint_cap: Some(::rustc::lint::Level::Allow),
         actually_rustdoc: true,
+        error_format,
+        debugging_opts: debugging_options,
         ..config::basic_options().clone()
     };
 
     let codemap = Rc::new(CodeMap::new(sessopts.file_path_mapping()));
-    let handler =
-        errors::Handler::with_tty_emitter(ColorConfig::Auto,
-                                          true, false,
-                                          Some(codemap.clone()));
+    let handler = core::new_handler(error_format, Some(codemap.clone()));
 
     let cstore = Rc::new(CStore::new(box rustc_trans::LlvmMetadataLoader));
     let mut sess = session::build_session_(
@@ -121,7 +128,8 @@ pub fn run(input_path: &Path,
                                        Some(codemap),
                                        None,
                                        render_type,
-                                       linker);
+                                       linker,
+                                       error_format);
 
     {
         let map = hir::map::map_crate(&sess, &*cstore, &mut hir_forest, &defs);
@@ -176,39 +184,24 @@ fn scrape_test_config(krate: &::rustc::hir::Crate) -> TestOptions {
     opts
 }
 
-fn run_test(test: &str, cratename: &str, filename: &FileName, cfgs: Vec<String>, libs: SearchPaths,
+fn run_test(test: &str, cratename: &str, filename: &FileName, line: usize,
+            cfgs: Vec<String>, libs: SearchPaths,
             externs: Externs,
             should_panic: bool, no_run: bool, as_test_harness: bool,
             compile_fail: bool, mut error_codes: Vec<String>, opts: &TestOptions,
             maybe_sysroot: Option<PathBuf>,
-            linker: Option<PathBuf>) {
+            linker: Option<PathBuf>,
+            error_format: ErrorOutputType) {
     // the test harness wants its own `main` & top level functions, so
     // never wrap the test in `fn main() { ... }`
-    let test = make_test(test, Some(cratename), as_test_harness, opts);
+    let (test, line_offset) = make_test(test, Some(cratename), as_test_harness, opts);
     // FIXME(#44940): if doctests ever support path remapping, then this filename
     // needs to be the result of CodeMap::span_to_unmapped_path
     let input = config::Input::Str {
         name: filename.to_owned(),
         input: test.to_owned(),
     };
-    let outputs = OutputTypes::new(&[(OutputType::Exe, None)]);
-
-    let sessopts = config::Options {
-        maybe_sysroot: maybe_sysroot.or_else(
-            || Some(env::current_exe().unwrap().parent().unwrap().parent().unwrap().to_path_buf())),
-        search_paths: libs,
-        crate_types: vec![config::CrateTypeExecutable],
-        output_types: outputs,
-        externs,
-        cg: config::CodegenOptions {
-            prefer_dynamic: true,
-            linker,
-            .. config::basic_codegen_options()
-        },
-        test: as_test_harness,
-        unstable_features: UnstableFeatures::from_environment(),
-        ..config::basic_options().clone()
-    };
+    let sessopts = test_session_options(libs, externs, maybe_sysroot, linker, as_test_harness);
 
     // Shuffle around a few input and output handles here. We're going to pass
     // an explicit handle into rustc to collect output messages, but we also
@@ -234,15 +227,26 @@ fn run_test(test: &str, cratename: &str, filename: &FileName, cfgs: Vec<String>,
         }
     }
     let data = Arc::new(Mutex::new(Vec::new()));
-    let codemap = Rc::new(CodeMap::new(sessopts.file_path_mapping()));
-    let emitter = errors::emitter::EmitterWriter::new(box Sink(data.clone()),
-                                                      Some(codemap.clone()),
-                                                      false);
+    // Report the spans of the test as the lines of the doc comment it is in
+    let codemap = Rc::new(CodeMap::new_doctest(
+        sessopts.file_path_mapping(), filename.clone(), line as isize - line_offset as isize
+    ));
+    let emitter: Box<Emitter> = match error_format {
+        ErrorOutputType::HumanReadable(_) => {
+            box EmitterWriter::new(box Sink(data.clone()), Some(codemap.clone()), false)
+        }
+        ErrorOutputType::Json(pretty) => {
+            box JsonEmitter::new(box Sink(data.clone()), None, codemap.clone(), pretty)
+        }
+        ErrorOutputType::Short(_) => {
+            box EmitterWriter::new(box Sink(data.clone()), Some(codemap.clone()), true)
+        }
+    };
     let old = io::set_panic(Some(box Sink(data.clone())));
     let _bomb = Bomb(data.clone(), old.unwrap_or(box io::stdout()));
 
     // Compile the code
-    let diagnostic_handler = errors::Handler::with_emitter(true, false, box emitter);
+    let diagnostic_handler = errors::Handler::with_emitter(true, false, emitter);
 
     let cstore = Rc::new(CStore::new(box rustc_trans::LlvmMetadataLoader));
     let mut sess = session::build_session_(
@@ -293,18 +297,42 @@ fn run_test(test: &str, cratename: &str, filename: &FileName, cfgs: Vec<String>,
 
     if no_run { return }
 
-    // Run the code!
-    //
+    let cmd = Command::new(&outdir.lock().unwrap().path().join("rust_out"));
+    run_test_binary(cmd, libdir, should_panic);
+}
+
+/// The options of the session compiling a test into an executable.
+fn test_session_options(libs: SearchPaths, externs: Externs, maybe_sysroot: Option<PathBuf>,
+                        linker: Option<PathBuf>, as_test_harness: bool) -> config::Options {
+    config::Options {
+        maybe_sysroot: maybe_sysroot.or_else(
+            || Some(env::current_exe().unwrap().parent().unwrap().parent().unwrap().to_path_buf())),
+        search_paths: libs,
+        crate_types: vec![config::CrateTypeExecutable],
+        output_types: OutputTypes::new(&[(OutputType::Exe, None)]),
+        externs,
+        cg: config::CodegenOptions {
+            prefer_dynamic: true,
+            linker,
+            .. config::basic_codegen_options()
+        },
+        test: as_test_harness,
+        unstable_features: UnstableFeatures::from_environment(),
+        ..config::basic_options().clone()
+    }
+}
+
+/// Runs a compiled test, panicking if its outcome isn't the expected one.
+fn run_test_binary(mut cmd: Command, libdir: PathBuf, should_panic: bool) {
     // We're careful to prepend the *target* dylib search path to the child's
     // environment to ensure that the target loads the right libraries at
     // runtime. It would be a sad day if the *host* libraries were loaded as a
     // mistake.
-    let mut cmd = Command::new(&outdir.lock().unwrap().path().join("rust_out"));
     let var = DynamicLibrary::envvar();
     let newpath = {
         let path = env::var_os(var).unwrap_or(OsString::new());
         let mut path = env::split_paths(&path).collect::<Vec<_>>();
-        path.insert(0, libdir.clone());
+        path.insert(0, libdir);
         env::join_paths(path).unwrap()
     };
     cmd.env(var, &newpath);
@@ -326,14 +354,17 @@ fn run_test(test: &str, cratename: &str, filename: &FileName, cfgs: Vec<String>,
     }
 }
 
+/// Transforms a test into code that can be compiled into a Rust binary, and
+/// returns the number of lines before the test's own code starts.
 pub fn make_test(s: &str,
                  cratename: Option<&str>,
                  dont_insert_main: bool,
                  opts: &TestOptions)
-                 -> String {
+                 -> (String, usize) {
     let (crate_attrs, everything_else) = partition_source(s);
 
     let mut prog = String::new();
+    let mut line_offset = 0;
 
     if opts.attrs.is_empty() {
         // If there aren't any attributes supplied by #![doc(test(attr(...)))], then allow some
@@ -341,11 +372,13 @@ pub fn make_test(s: &str,
         // commonly used to make tests fail in case they trigger warnings, so having this there in
         // that case may cause some tests to pass when they shouldn't have.
         prog.push_str("#![allow(unused)]\n");
+        line_offset += 1;
     }
 
     // Next, any attributes that came from the crate root via #![doc(test(attr(...)))].
     for attr in &opts.attrs {
         prog.push_str(&format!("#![{}]\n", attr));
+        line_offset += 1;
     }
 
     // Now push any outer attributes from the example, assuming they
@@ -358,27 +391,16 @@ pub fn make_test(s: &str,
         if let Some(cratename) = cratename {
             if s.contains(cratename) {
                 prog.push_str(&format!("extern crate {};\n", cratename));
+                line_offset += 1;
             }
         }
     }
 
-    // FIXME (#21299): prefer libsyntax or some other actual parser over this
-    // best-effort ad hoc approach
-    let already_has_main = s.lines()
-        .map(|line| {
-            let comment = line.find("//");
-            if let Some(comment_begins) = comment {
-                &line[0..comment_begins]
-            } else {
-                line
-            }
-        })
-        .any(|code| code.contains("fn main"));
-
-    if dont_insert_main || already_has_main {
+    if dont_insert_main || has_main_fn(s) {
         prog.push_str(&everything_else);
     } else {
         prog.push_str("fn main() {\n");
+        line_offset += 1;
         prog.push_str(&everything_else);
         prog = prog.trim().into();
         prog.push_str("\n}");
@@ -386,7 +408,22 @@ pub fn make_test(s: &str,
 
     info!("final test program: {}", prog);
 
-    prog
+    (prog, line_offset)
+}
+
+// FIXME (#21299): prefer libsyntax or some other actual parser over this
+// best-effort ad hoc approach
+fn has_main_fn(s: &str) -> bool {
+    s.lines()
+        .map(|line| {
+            let comment = line.find("//");
+            if let Some(comment_begins) = comment {
+                &line[0..comment_begins]
+            } else {
+                line
+            }
+        })
+        .any(|code| code.contains("fn main"))
 }
 
 // FIXME(aburka): use a real parser to deal with multiline attributes
@@ -414,6 +451,216 @@ fn partition_source(s: &str) -> (String, String) {
     (before, after)
 }
 
+/// The doctests which can be compiled together, as the modules of a single
+/// test harness, instead of paying for a compile and link on each of them.
+///
+/// The harness is built by the first of the tests to run. If that fails, for
+/// instance because one of the tests doesn't compile, each test falls back to
+/// being compiled on its own, so its errors are reported as usual.
+struct DoctestBatch {
+    cratename: String,
+    cfgs: Vec<String>,
+    libs: SearchPaths,
+    externs: Externs,
+    maybe_sysroot: Option<PathBuf>,
+    linker: Option<PathBuf>,
+    state: Mutex<BatchState>,
+}
+
+enum BatchState {
+    /// The programs of the tests added so far, and the files they come from
+    Collecting(Vec<(String, PathBuf)>),
+    /// The directory the harness was built in and the target library path,
+    /// if it could be built
+    Built(Option<(TempDir, PathBuf)>),
+}
+
+impl DoctestBatch {
+    /// Adds the program of a test, returning its index in the batch.
+    fn add(&self, program: String, filename: PathBuf) -> usize {
+        match *self.state.lock().unwrap() {
+            BatchState::Collecting(ref mut programs) => {
+                programs.push((program, filename));
+                programs.len() - 1
+            }
+            BatchState::Built(..) => panic!("doctest added after the batch was built"),
+        }
+    }
+
+    /// Runs the test at `index` from the harness, building it first if need
+    /// be. Returns `false` if the harness couldn't be built.
+    fn run_test(&self, index: usize, should_panic: bool) -> bool {
+        let (binary, libdir) = {
+            let mut state = self.state.lock().unwrap();
+            if let BatchState::Collecting(..) = *state {
+                let programs = match mem::replace(&mut *state, BatchState::Built(None)) {
+                    BatchState::Collecting(programs) => programs,
+                    BatchState::Built(..) => unreachable!(),
+                };
+                *state = BatchState::Built(self.build(programs));
+            }
+            match *state {
+                BatchState::Built(Some((ref dir, ref libdir))) => {
+                    (dir.path().join("rust_out"), libdir.clone())
+                }
+                _ => return false,
+            }
+        };
+
+        let mut cmd = Command::new(&binary);
+        cmd.args(&["--exact", "--nocapture", "--test-threads", "1", "--quiet"])
+           .arg(format!("doctest_{}::run", index));
+        run_test_binary(cmd, libdir, should_panic);
+        true
+    }
+
+    fn build(&self, programs: Vec<(String, PathBuf)>) -> Option<(TempDir, PathBuf)> {
+        // A lone test gains nothing from the harness
+        if programs.len() < 2 {
+            return None;
+        }
+
+        let dir = TempDir::new("rustdoctest").ok()?;
+        let mut sessopts = test_session_options(self.libs.clone(), self.externs.clone(),
+                                                self.maybe_sysroot.clone(), self.linker.clone(),
+                                                true);
+
+        let mut root = String::from("#![allow(unused)]\n");
+        let extern_crate = format!("extern crate {};", self.cratename);
+        if programs.iter().any(|&(ref program, _)| program.lines().any(|l| l == extern_crate)) {
+            root.push_str(&extern_crate);
+            root.push('\n');
+        }
+        for (i, (program, filename)) in programs.into_iter().enumerate() {
+            // Each test is in a file named like the one it comes from, and that
+            // path is remapped to the original, so `file!()` and `line!()` give
+            // the same results as when the test is compiled on its own.
+            let module_dir = dir.path().join(format!("doctest_{}", i));
+            let module_path = module_dir.join(filename.file_name()?);
+            fs::create_dir(&module_dir).ok()?;
+            File::create(&module_path).and_then(|mut f| {
+                write!(f, "{}\n\n{}", program, BATCH_TEST_FN)
+            }).ok()?;
+            root.push_str(&format!("#[path = {:?}]\nmod doctest_{};\n",
+                                   module_path.display().to_string(), i));
+
+            let original_dir = filename.parent().map(Path::to_path_buf).unwrap_or_default();
+            sessopts.debugging_opts.remap_path_prefix_from.push(module_dir);
+            sessopts.debugging_opts.remap_path_prefix_to.push(original_dir);
+        }
+        let root_path = dir.path().join("rust_out.rs");
+        File::create(&root_path).and_then(|mut f| f.write_all(root.as_bytes())).ok()?;
+
+        // The errors of the tests are reported when they're compiled on their own
+        let codemap = Rc::new(CodeMap::new(sessopts.file_path_mapping()));
+        let emitter = box EmitterWriter::new(box io::sink(), Some(codemap.clone()), false);
+        let diagnostic_handler = errors::Handler::with_emitter(true, false, emitter);
+
+        let cstore = Rc::new(CStore::new(box rustc_trans::LlvmMetadataLoader));
+        let mut sess = session::build_session_(
+            sessopts, Some(root_path.clone()), diagnostic_handler, codemap,
+        );
+        rustc_trans::init(&sess);
+        rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+        sess.parse_sess.config =
+            config::build_configuration(&sess, config::parse_cfgspecs(self.cfgs.clone()));
+        let libdir = sess.target_filesearch(PathKind::All).get_lib_path();
+
+        let input = config::Input::File(root_path);
+        let out = Some(dir.path().to_path_buf());
+        let control = driver::CompileController::basic();
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            driver::compile_input(&sess, &cstore, &input, &out, &None, None, &control)
+        }));
+        match res {
+            Ok(Ok(())) => Some((dir, libdir)),
+            _ => None,
+        }
+    }
+}
+
+/// The test added after the program of each test in the batch harness. It runs
+/// `main` on a thread named like the main thread of a test compiled alone, so
+/// that the messages of its panics are the same.
+const BATCH_TEST_FN: &'static str = "\
+#[test]
+fn run() {
+    let main = ::std::thread::Builder::new().name(\"main\".to_string()).spawn(main).unwrap();
+    if let Err(payload) = main.join() {
+        ::std::panic::resume_unwind(payload);
+    }
+}
+";
+
+/// Whether the program of a test defines nothing but its `main`, so that it
+/// can go in the batch harness. Items of one test would be visible to the
+/// others there even from their own module, as is the case of trait impls, so
+/// a test could pass or fail differently than when it's compiled alone. The
+/// only macros allowed are the ones from std which can't expand to items.
+///
+/// The harness is also compiled with `--test`, so `cfg(test)` holds in it but
+/// not in a test compiled alone, and no `cfg` attribute is allowed either.
+fn defines_no_items(program: &str) -> bool {
+    const EXPR_MACROS: &'static [&'static str] = &[
+        "assert", "assert_eq", "assert_ne", "debug_assert", "debug_assert_eq",
+        "debug_assert_ne", "eprint", "eprintln", "format", "panic", "print", "println",
+        "try", "unimplemented", "unreachable", "vec", "write", "writeln",
+    ];
+
+    struct ItemFinder {
+        found: bool,
+    }
+
+    impl<'a> Visitor<'a> for ItemFinder {
+        fn visit_item(&mut self, item: &'a ast::Item) {
+            match item.node {
+                ast::ItemKind::Use(..) => {}
+                _ => self.found = true,
+            }
+        }
+
+        fn visit_mac(&mut self, mac: &'a ast::Mac) {
+            let segments = &mac.node.path.segments;
+            if segments.len() != 1 ||
+               !EXPR_MACROS.contains(&&*segments[0].identifier.name.as_str()) {
+                self.found = true;
+            }
+        }
+
+        fn visit_attribute(&mut self, attr: &'a ast::Attribute) {
+            if attr.check_name("cfg") || attr.check_name("cfg_attr") {
+                self.found = true;
+            }
+        }
+    }
+
+    // The test compiled alone reports any syntax error
+    let codemap = Rc::new(CodeMap::new(FilePathMapping::empty()));
+    let emitter = box EmitterWriter::new(box io::sink(), Some(codemap.clone()), false);
+    let handler = errors::Handler::with_emitter(false, false, emitter);
+    let sess = ParseSess::with_span_handler(handler, codemap);
+    let krate = match parse::parse_crate_from_source_str(FileName::Anon, program.to_owned(),
+                                                         &sess) {
+        Ok(krate) => krate,
+        Err(mut err) => {
+            err.cancel();
+            return false;
+        }
+    };
+
+    let mut finder = ItemFinder { found: false };
+    for item in &krate.module.items {
+        match item.node {
+            ast::ItemKind::ExternCrate(..) | ast::ItemKind::Use(..) => {}
+            ast::ItemKind::Fn(.., ref body) if item.ident.name == "main" => {
+                visit::walk_block(&mut finder, body);
+            }
+            _ => return false,
+        }
+    }
+    !finder.found
+}
+
 pub struct Collector {
     pub tests: Vec<testing::TestDescAndFn>,
     // to be removed when hoedown will be definitely gone
@@ -454,13 +701,25 @@ pub struct Collector {
     // to be removed when hoedown will be removed as well
     pub render_type: RenderType,
     linker: Option<PathBuf>,
+    error_format: ErrorOutputType,
+    batch: Arc<DoctestBatch>,
 }
 
 impl Collector {
     pub fn new(cratename: String, cfgs: Vec<String>, libs: SearchPaths, externs: Externs,
                use_headers: bool, opts: TestOptions, maybe_sysroot: Option<PathBuf>,
                codemap: Option<Rc<CodeMap>>, filename: Option<PathBuf>,
-               render_type: RenderType, linker: Option<PathBuf>) -> Collector {
+               render_type: RenderType, linker: Option<PathBuf>,
+               error_format: ErrorOutputType) -> Collector {
+        let batch = Arc::new(DoctestBatch {
+            cratename: cratename.clone(),
+            cfgs: cfgs.clone(),
+            libs: libs.clone(),
+            externs: externs.clone(),
+            maybe_sysroot: maybe_sysroot.clone(),
+            linker: linker.clone(),
+            state: Mutex::new(BatchState::Collecting(Vec::new())),
+        });
         Collector {
             tests: Vec::new(),
             old_tests: HashMap::new(),
@@ -477,9 +736,29 @@ impl Collector {
             filename,
             render_type,
             linker,
+            error_format,
+            batch,
         }
     }
 
+    /// Whether a test can go in the batch harness with the others. The tests
+    /// which need a crate of their own, for their crate attributes such as
+    /// `#![feature]`, their `main` or their `extern crate`s, are compiled alone,
+    /// as are the ones which aren't run or only need to fail to compile. The
+    /// program of the test must also pass `defines_no_items`.
+    fn can_batch(&self, test: &str, filename: &FileName, no_run: bool,
+                 should_ignore: bool, as_test_harness: bool, compile_fail: bool) -> bool {
+        let real_file = match *filename {
+            FileName::Real(ref path) => path.file_name().is_some(),
+            _ => false,
+        };
+        real_file && !no_run && !should_ignore && !as_test_harness && !compile_fail &&
+            partition_source(test).0.trim().is_empty() &&
+            !test.contains("extern crate") &&
+            !has_main_fn(test) &&
+            !self.opts.attrs.iter().any(|attr| attr.starts_with("feature"))
+    }
+
     fn generate_name(&self, line: usize, filename: &FileName) -> String {
         format!("{} - {} (line {})", filename, self.names.join("::"), line)
     }
@@ -517,6 +796,19 @@ impl Collector {
                 return
             }
         }
+        let batch_index = if self.can_batch(&test, &filename, no_run, should_ignore,
+                                            as_test_harness, compile_fail) {
+            let (program, _) = make_test(&test, Some(&self.cratename), false, &self.opts);
+            match filename {
+                FileName::Real(ref path) if defines_no_items(&program) => {
+                    Some(self.batch.add(program, path.clone()))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+        let batch = self.batch.clone();
         let cfgs = self.cfgs.clone();
         let libs = self.libs.clone();
         let externs = self.externs.clone();
@@ -524,6 +816,7 @@ impl Collector {
         let opts = self.opts.clone();
         let maybe_sysroot = self.maybe_sysroot.clone();
         let linker = self.linker.clone();
+        let error_format = self.error_format;
         debug!("Creating test {}: {}", name, test);
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
@@ -532,6 +825,7 @@ impl Collector {
                 // compiler failures are test failures
                 should_panic: testing::ShouldPanic::No,
                 allow_fail,
+                timeout: None,
             },
             testfn: testing::DynTestFn(box move |()| {
                 let panic = io::set_panic(None);
@@ -540,9 +834,15 @@ impl Collector {
                     rustc_driver::in_rustc_thread(move || {
                         io::set_panic(panic);
                         io::set_print(print);
+                        if let Some(index) = batch_index {
+                            if batch.run_test(index, should_panic) {
+                                return;
+                            }
+                        }
                         run_test(&test,
                                  &cratename,
                                  &filename,
+                                 line,
                                  cfgs,
                                  libs,
                                  externs,
@@ -553,7 +853,8 @@ impl Collector {
                                  error_codes,
                                  &opts,
                                  maybe_sysroot,
-                                 linker)
+                                 linker,
+                                 error_format)
                     })
                 } {
                     Ok(()) => (),
@@ -0,0 +1,148 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checking that a theme given with `--theme` styles everything the built-in
+//! one does, so that no part of the pages is left with the colors of the
+//! built-in theme, or none at all.
+//!
+//! Only the selectors are compared: a theme is free to give them whatever
+//! properties it likes.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// The built-in theme, which the others are checked against.
+pub const DEFAULT_THEME: &'static str = include_str!("html/static/styles/main.css");
+
+/// The selectors of the rule sets of a style sheet.
+///
+/// Selectors separated by commas are split up, and the ones in blocks such as
+/// `@media` are prefixed with the block's rule, so that a theme needs to give
+/// its rule sets in the same blocks as the built-in one.
+pub fn selectors(css: &str) -> BTreeSet<String> {
+    let mut selectors = BTreeSet::new();
+    collect_selectors(&strip_comments(css), "", &mut selectors);
+    selectors
+}
+
+fn collect_selectors(css: &str, prefix: &str, selectors: &mut BTreeSet<String>) {
+    let mut rest = css;
+    while let Some(open) = rest.find('{') {
+        let close = matching_brace(rest, open);
+        // Statements such as `@import` end with a semicolon and have no block
+        let selector = rest[..open].rsplit(';').next().unwrap_or("");
+        let body = &rest[open + 1..close];
+        if selector.trim_left().starts_with('@') {
+            let selector = normalize(selector);
+            if body.contains('{') {
+                collect_selectors(body, &format!("{}{} > ", prefix, selector), selectors);
+            } else {
+                // `@font-face` and such have declarations of their own
+                selectors.insert(format!("{}{}", prefix, selector));
+            }
+        } else {
+            for selector in selector.split(',') {
+                let selector = normalize(selector);
+                if !selector.is_empty() {
+                    selectors.insert(format!("{}{}", prefix, selector));
+                }
+            }
+        }
+        rest = if close < rest.len() { &rest[close + 1..] } else { "" };
+    }
+}
+
+/// The position of the brace closing the one at `open`, or the end of `css`
+/// if it isn't closed.
+fn matching_brace(css: &str, open: usize) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in css[open..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + i;
+                }
+            }
+            _ => {}
+        }
+    }
+    css.len()
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut stripped = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn normalize(selector: &str) -> String {
+    selector.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The selectors of the built-in theme which `theme` has no rule set for.
+pub fn missing_selectors(theme: &str) -> Vec<String> {
+    let theme = selectors(theme);
+    selectors(DEFAULT_THEME).into_iter().filter(|selector| !theme.contains(selector)).collect()
+}
+
+/// Reads the theme at `path` and checks it against the built-in one.
+pub fn check_theme_file(path: &Path) -> io::Result<Vec<String>> {
+    let mut theme = String::new();
+    File::open(path)?.read_to_string(&mut theme)?;
+    Ok(missing_selectors(&theme))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selectors() {
+        let css = r#"
+            /* A comment { with braces } */
+            @import url("foo.css");
+            body { color: black; }
+            h1, h2 > a,
+            .sidebar   .location { content: "}"; }
+            @media (max-width: 700px) {
+                .sidebar { background: white; }
+            }
+            @font-face { font-family: "Fira Sans"; }
+        "#;
+        let expected = ["@font-face", "@media (max-width: 700px) > .sidebar", "body", "h1",
+                        "h2 > a", ".sidebar .location"];
+        assert_eq!(selectors(css), expected.iter().map(|s| s.to_string()).collect());
+    }
+
+    #[test]
+    fn test_missing_selectors() {
+        assert!(missing_selectors(DEFAULT_THEME).is_empty());
+        assert!(missing_selectors("").len() > 10);
+
+        let missing = missing_selectors(&DEFAULT_THEME.replace("\n.in-band {", "\n.other {"));
+        assert_eq!(missing, vec![".in-band".to_string()]);
+    }
+}
@@ -15,7 +15,7 @@ use io::prelude::*;
 use cmp;
 use error;
 use fmt;
-use io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, SeekFrom};
+use io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, IoSlice, IoSliceMut, SeekFrom};
 use memchr;
 
 /// The `BufReader` struct adds buffering to any reader.
@@ -211,6 +211,19 @@ impl<R: Read> Read for BufReader<R> {
         Ok(nread)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let total_len = bufs.iter().map(|b| b.len()).sum::<usize>();
+        if self.pos == self.cap && total_len >= self.buf.len() {
+            return self.inner.read_vectored(bufs);
+        }
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read_vectored(bufs)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+
     // we can't skip unconditionally because of the large buffer case in read.
     unsafe fn initializer(&self) -> Initializer {
         self.inner.initializer()
@@ -521,6 +534,22 @@ impl<W: Write> Write for BufWriter<W> {
             Write::write(&mut self.buf, buf)
         }
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let total_len = bufs.iter().map(|b| b.len()).sum::<usize>();
+        if self.buf.len() + total_len > self.buf.capacity() {
+            self.flush_buf()?;
+        }
+        if total_len >= self.buf.capacity() {
+            self.panicked = true;
+            let r = self.inner.as_mut().unwrap().write_vectored(bufs);
+            self.panicked = false;
+            r
+        } else {
+            Write::write_vectored(&mut self.buf, bufs)
+        }
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         self.flush_buf().and_then(|()| self.get_mut().flush())
     }
@@ -849,6 +878,19 @@ impl<W: Write> Write for LineWriter<W> {
         }
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        // Only the data up to the last newline is flushed, which is what
+        // `write` takes care of, so the buffers go through it one at a time
+        // when one of them holds a newline.
+        if bufs.iter().any(|buf| memchr::memchr(b'\n', buf).is_some()) {
+            return io::default_write_vectored(|buf| self.write(buf), bufs);
+        }
+        if self.need_flush {
+            self.flush()?;
+        }
+        self.inner.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         self.inner.flush()?;
         self.need_flush = false;
@@ -870,7 +912,7 @@ impl<W: Write> fmt::Debug for LineWriter<W> where W: fmt::Debug {
 #[cfg(test)]
 mod tests {
     use io::prelude::*;
-    use io::{self, BufReader, BufWriter, LineWriter, SeekFrom};
+    use io::{self, BufReader, BufWriter, IoSlice, IoSliceMut, LineWriter, SeekFrom};
     use sync::atomic::{AtomicUsize, Ordering};
     use thread;
     use test;
@@ -1022,6 +1064,23 @@ mod tests {
         assert_eq!(*writer.get_ref(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
     }
 
+    #[test]
+    fn test_buffered_writer_vectored() {
+        let mut writer = BufWriter::with_capacity(4, Vec::new());
+
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[0]), IoSlice::new(&[1, 2])]).unwrap(), 3);
+        assert_eq!(*writer.get_ref(), []);
+
+        // Doesn't fit next to what's buffered, but fits in the buffer
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[3, 4])]).unwrap(), 2);
+        assert_eq!(*writer.get_ref(), [0, 1, 2]);
+
+        // Doesn't fit in the buffer at all
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[5, 6]),
+                                           IoSlice::new(&[7, 8])]).unwrap(), 4);
+        assert_eq!(*writer.get_ref(), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
     #[test]
     fn test_buffered_writer_inner_flushes() {
         let mut w = BufWriter::with_capacity(3, Vec::new());
@@ -1109,6 +1168,37 @@ mod tests {
         assert_eq!(*writer.get_ref(), [0, 1, 0, b'\n', 1, b'\n', 2, 3, b'\n']);
     }
 
+    #[test]
+    fn test_buffered_reader_vectored() {
+        let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
+        let mut reader = BufReader::with_capacity(2, inner);
+
+        let mut buf1 = [0, 0];
+        let mut buf2 = [0];
+        let nread = reader.read_vectored(&mut [IoSliceMut::new(&mut buf1),
+                                               IoSliceMut::new(&mut buf2)]).unwrap();
+        assert_eq!(nread, 3);
+        assert_eq!(buf1, [5, 6]);
+        assert_eq!(buf2, [7]);
+
+        let nread = reader.read_vectored(&mut [IoSliceMut::new(&mut buf2)]).unwrap();
+        assert_eq!(nread, 1);
+        assert_eq!(buf2, [0]);
+        assert_eq!(reader.fill_buf().ok(), Some(&[1][..]));
+    }
+
+    #[test]
+    fn test_line_buffer_vectored() {
+        let mut writer = LineWriter::new(Vec::new());
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[0]), IoSlice::new(&[1])]).unwrap(), 2);
+        assert_eq!(*writer.get_ref(), []);
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[2, b'\n']),
+                                           IoSlice::new(&[3])]).unwrap(), 2);
+        assert_eq!(*writer.get_ref(), [0, 1, 2, b'\n']);
+        writer.flush().unwrap();
+        assert_eq!(*writer.get_ref(), [0, 1, 2, b'\n']);
+    }
+
     #[test]
     fn test_read_line() {
         let in_buf: &[u8] = b"a\nb\nc";
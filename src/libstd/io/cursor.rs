@@ -12,7 +12,7 @@ use io::prelude::*;
 
 use core::convert::TryInto;
 use cmp;
-use io::{self, Initializer, SeekFrom, Error, ErrorKind};
+use io::{self, Initializer, IoSlice, IoSliceMut, SeekFrom, Error, ErrorKind};
 
 /// A `Cursor` wraps another type and provides it with a
 /// [`Seek`] implementation.
@@ -230,6 +230,18 @@ impl<T> Read for Cursor<T> where T: AsRef<[u8]> {
         Ok(n)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let mut nread = 0;
+        for buf in bufs {
+            let n = self.read(buf)?;
+            nread += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(nread)
+    }
+
     fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
         let n = buf.len();
         Read::read_exact(&mut self.fill_buf()?, buf)?;
@@ -252,6 +264,19 @@ impl<T> BufRead for Cursor<T> where T: AsRef<[u8]> {
     fn consume(&mut self, amt: usize) { self.pos += amt as u64; }
 }
 
+// Writes the buffers one after the other until the slice of a cursor is full.
+fn slice_write_vectored<W: Write>(cursor: &mut W, bufs: &[IoSlice]) -> io::Result<usize> {
+    let mut nwritten = 0;
+    for buf in bufs {
+        let n = cursor.write(buf)?;
+        nwritten += n;
+        if n < buf.len() {
+            break;
+        }
+    }
+    Ok(nwritten)
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Write for Cursor<&'a mut [u8]> {
     #[inline]
@@ -261,6 +286,11 @@ impl<'a> Write for Cursor<&'a mut [u8]> {
         self.pos += amt as u64;
         Ok(amt)
     }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        slice_write_vectored(self, bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -291,6 +321,14 @@ impl Write for Cursor<Vec<u8>> {
         self.set_position((pos + buf.len()) as u64);
         Ok(buf.len())
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let mut nwritten = 0;
+        for buf in bufs {
+            nwritten += self.write(buf)?;
+        }
+        Ok(nwritten)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -303,13 +341,18 @@ impl Write for Cursor<Box<[u8]>> {
         self.pos += amt as u64;
         Ok(amt)
     }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        slice_write_vectored(self, bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
 #[cfg(test)]
 mod tests {
     use io::prelude::*;
-    use io::{Cursor, SeekFrom};
+    use io::{Cursor, IoSlice, IoSliceMut, SeekFrom};
 
     #[test]
     fn test_vec_writer() {
@@ -331,6 +374,17 @@ mod tests {
         assert_eq!(&writer.get_ref()[..], b);
     }
 
+    #[test]
+    fn test_mem_writer_vectored() {
+        let mut writer = Cursor::new(Vec::new());
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[0])]).unwrap(), 1);
+        assert_eq!(writer.write_vectored(&[IoSlice::new(&[1, 2, 3]),
+                                           IoSlice::new(&[]),
+                                           IoSlice::new(&[4, 5, 6, 7])]).unwrap(), 7);
+        let b: &[_] = &[0, 1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(&writer.get_ref()[..], b);
+    }
+
     #[test]
     fn test_box_slice_writer() {
         let mut writer = Cursor::new(vec![0u8; 9].into_boxed_slice());
@@ -370,6 +424,24 @@ mod tests {
         assert_eq!(buf, b);
     }
 
+    #[test]
+    fn test_buf_writer_vectored() {
+        let mut buf = [0 as u8; 9];
+        {
+            let mut writer = Cursor::new(&mut buf[..]);
+            assert_eq!(writer.write_vectored(&[IoSlice::new(&[0, 1, 2]),
+                                               IoSlice::new(&[3, 4])]).unwrap(), 5);
+            assert_eq!(writer.position(), 5);
+            assert_eq!(writer.write_vectored(&[IoSlice::new(&[5, 6, 7]),
+                                               IoSlice::new(&[8, 9]),
+                                               IoSlice::new(&[10])]).unwrap(), 4);
+            assert_eq!(writer.position(), 9);
+            assert_eq!(writer.write_vectored(&[IoSlice::new(&[11])]).unwrap(), 0);
+        }
+        let b: &[_] = &[0, 1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(buf, b);
+    }
+
     #[test]
     fn test_buf_writer_seek() {
         let mut buf = [0 as u8; 8];
@@ -430,6 +502,25 @@ mod tests {
         assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
 
+    #[test]
+    fn test_mem_reader_vectored() {
+        let mut reader = Cursor::new(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        let mut buf = [];
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf)]).unwrap(), 0);
+        assert_eq!(reader.position(), 0);
+        let mut buf1 = [0];
+        let mut buf2 = [0; 4];
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf1),
+                                              IoSliceMut::new(&mut buf2)]).unwrap(), 5);
+        assert_eq!(reader.position(), 5);
+        assert_eq!(buf1, [0]);
+        assert_eq!(buf2, [1, 2, 3, 4]);
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf2),
+                                              IoSliceMut::new(&mut buf1)]).unwrap(), 3);
+        assert_eq!(&buf2[..3], &[5, 6, 7]);
+        assert_eq!(reader.read_vectored(&mut [IoSliceMut::new(&mut buf1)]).unwrap(), 0);
+    }
+
     #[test]
     fn test_boxed_slice_reader() {
         let mut reader = Cursor::new(vec![0, 1, 2, 3, 4, 5, 6, 7].into_boxed_slice());
@@ -277,7 +277,9 @@ use fmt;
 use result;
 use str;
 use memchr;
+use ops::{Deref, DerefMut};
 use ptr;
+use sys;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::buffered::{BufReader, BufWriter, LineWriter};
@@ -525,6 +527,41 @@ pub trait Read {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
 
+    /// Like `read`, except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must
+    /// behave as a single call to `read` with the buffers concatenated would.
+    ///
+    /// The default implementation calls `read` with the first nonempty
+    /// buffer, or an empty one if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iovec)]
+    ///
+    /// use std::io::{self, IoSliceMut, Read};
+    ///
+    /// # fn foo() -> io::Result<()> {
+    /// let mut reader: &[u8] = b"headerbody";
+    /// let mut header = [0; 6];
+    /// let mut body = [0; 4];
+    ///
+    /// let n = reader.read_vectored(&mut [IoSliceMut::new(&mut header),
+    ///                                    IoSliceMut::new(&mut body)])?;
+    /// assert_eq!(n, 10);
+    /// assert_eq!(&header, b"header");
+    /// assert_eq!(&body, b"body");
+    /// # Ok(())
+    /// # }
+    /// # foo().unwrap();
+    /// ```
+    #[unstable(feature = "iovec", issue = "0")]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        default_read_vectored(|b| self.read(b), bufs)
+    }
+
     /// Determines if this `Read`er can work with buffers of uninitialized
     /// memory.
     ///
@@ -906,6 +943,111 @@ pub trait Read {
     }
 }
 
+pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut]) -> Result<usize>
+    where F: FnOnce(&mut [u8]) -> Result<usize>
+{
+    let buf = bufs.iter_mut().find(|b| !b.is_empty()).map_or(&mut [][..], |b| &mut **b);
+    read(buf)
+}
+
+pub(crate) fn default_write_vectored<F>(write: F, bufs: &[IoSlice]) -> Result<usize>
+    where F: FnOnce(&[u8]) -> Result<usize>
+{
+    let buf = bufs.iter().find(|b| !b.is_empty()).map_or(&[][..], |b| &**b);
+    write(buf)
+}
+
+/// A buffer type used with `Read::read_vectored`.
+///
+/// It is semantically a wrapper around a `&mut [u8]`, but is guaranteed to be
+/// ABI compatible with the `iovec` type on Unix platforms, so that a slice of
+/// them can be handed to the operating system as is.
+#[unstable(feature = "iovec", issue = "0")]
+#[repr(C)]
+pub struct IoSliceMut<'a>(sys::io_slice::IoSliceMut<'a>);
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Send for IoSliceMut<'a> {}
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Sync for IoSliceMut<'a> {}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoSliceMut<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0.as_slice(), fmt)
+    }
+}
+
+impl<'a> IoSliceMut<'a> {
+    /// Creates a new `IoSliceMut` wrapping a byte slice.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> IoSliceMut<'a> {
+        IoSliceMut(sys::io_slice::IoSliceMut::new(buf))
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoSliceMut<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> DerefMut for IoSliceMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+
+/// A buffer type used with `Write::write_vectored`.
+///
+/// It is semantically a wrapper around a `&[u8]`, but is guaranteed to be
+/// ABI compatible with the `iovec` type on Unix platforms, so that a slice of
+/// them can be handed to the operating system as is.
+#[unstable(feature = "iovec", issue = "0")]
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct IoSlice<'a>(sys::io_slice::IoSlice<'a>);
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Send for IoSlice<'a> {}
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Sync for IoSlice<'a> {}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0.as_slice(), fmt)
+    }
+}
+
+impl<'a> IoSlice<'a> {
+    /// Creates a new `IoSlice` wrapping a byte slice.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a [u8]) -> IoSlice<'a> {
+        IoSlice(sys::io_slice::IoSlice::new(buf))
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoSlice<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
 /// A type used to conditionally initialize buffers passed to `Read` methods.
 #[unstable(feature = "read_initializer", issue = "42788")]
 #[derive(Debug)]
@@ -1032,6 +1174,37 @@ pub trait Write {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
+    /// Like `write`, except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. This method must behave
+    /// as a single call to `write` with the buffers concatenated would.
+    ///
+    /// The default implementation calls `write` with the first nonempty
+    /// buffer, or an empty one if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(iovec)]
+    ///
+    /// use std::io::{self, IoSlice, Write};
+    /// use std::net::TcpStream;
+    ///
+    /// # fn foo() -> io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254")?;
+    ///
+    /// // Sends the header and the body of a frame at once, without copying
+    /// // them into one buffer first. Some prefix of them may be written.
+    /// stream.write_vectored(&[IoSlice::new(b"header"), IoSlice::new(b"body")])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "iovec", issue = "0")]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        default_write_vectored(|b| self.write(b), bufs)
+    }
+
     /// Flush this output stream, ensuring that all intermediately buffered
     /// contents reach their destination.
     ///
@@ -11,7 +11,7 @@
 #![allow(missing_copy_implementations)]
 
 use fmt;
-use io::{self, Read, Initializer, Write, ErrorKind, BufRead};
+use io::{self, Read, Initializer, IoSlice, Write, ErrorKind, BufRead};
 use mem;
 
 /// Copies the entire contents of a reader into a writer.
@@ -29,6 +29,16 @@ use mem;
 /// `write` returns an error. All instances of `ErrorKind::Interrupted` are
 /// handled by this function and the underlying operation is retried.
 ///
+/// # Platform-specific behavior
+///
+/// On Linux and Android, when both `reader` and `writer` are files, sockets,
+/// or the pipes of a child process (and not, for instance, wrapped in a
+/// `BufReader`), the data is copied by the kernel with `copy_file_range`,
+/// `sendfile` or `splice` rather than through a buffer in the process.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: index.html#platform-specific-behavior
+///
 /// # Examples
 ///
 /// ```
@@ -48,6 +58,21 @@ use mem;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
     where R: Read, W: Write
+{
+    // Let the kernel copy between file descriptors when it can
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let written = match ::sys::kernel_copy::copy_spec(reader, writer) {
+        ::sys::kernel_copy::CopyResult::Ended(result) => return result,
+        ::sys::kernel_copy::CopyResult::Fallback(written) => written,
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let written = 0;
+
+    Ok(written + generic_copy(reader, writer)?)
+}
+
+fn generic_copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+    where R: Read, W: Write
 {
     let mut buf = unsafe {
         let mut buf: [u8; super::DEFAULT_BUF_SIZE] = mem::uninitialized();
@@ -202,6 +227,10 @@ impl Write for Sink {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { Ok(buf.len()) }
     #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        Ok(bufs.iter().map(|b| b.len()).sum())
+    }
+    #[inline]
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -0,0 +1,681 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use fmt;
+use io;
+use net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use sys_common::net as net_imp;
+use sys_common::{AsInner, FromInner, IntoInner};
+use time::Duration;
+
+/// A socket which isn't connected or listening yet, to set options on before
+/// it is bound or connected.
+///
+/// [`TcpListener::bind`], [`TcpStream::connect`] and [`UdpSocket::bind`]
+/// create a socket and put it to use all at once, but some options only
+/// matter if they are set before that, like [`set_reuse_address`] or
+/// [`set_only_v6`] before binding. A `SocketBuilder` is created with a
+/// protocol and an address family, configured, optionally [bound], and then
+/// turned into a [`TcpListener`] with [`listen`], a [`TcpStream`] with
+/// [`connect`], or a [`UdpSocket`] with [`into_udp_socket`]. Those check
+/// that the socket has the matching protocol, and return an error otherwise.
+///
+/// Unlike [`TcpListener::bind`], nothing is set on the socket but what is
+/// asked for.
+///
+/// [`TcpListener::bind`]: struct.TcpListener.html#method.bind
+/// [`TcpStream::connect`]: struct.TcpStream.html#method.connect
+/// [`UdpSocket::bind`]: struct.UdpSocket.html#method.bind
+/// [`set_reuse_address`]: #method.set_reuse_address
+/// [`set_only_v6`]: #method.set_only_v6
+/// [bound]: #method.bind
+/// [`TcpListener`]: struct.TcpListener.html
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`UdpSocket`]: struct.UdpSocket.html
+/// [`listen`]: #method.listen
+/// [`connect`]: #method.connect
+/// [`into_udp_socket`]: #method.into_udp_socket
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+/// use std::net::SocketBuilder;
+///
+/// let builder = SocketBuilder::tcp_v6().unwrap();
+/// builder.set_reuse_address(true).unwrap();
+/// builder.set_only_v6(false).unwrap();
+/// builder.bind(&"[::]:8080".parse().unwrap()).unwrap();
+/// let listener = builder.listen(1024).unwrap();
+///
+/// for stream in listener.incoming() {
+///     // ...
+/// }
+/// ```
+#[unstable(feature = "socket_builder", issue = "0")]
+pub struct SocketBuilder(net_imp::SocketBuilder);
+
+impl SocketBuilder {
+    /// Creates a TCP socket for IPv4.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn tcp_v4() -> io::Result<SocketBuilder> {
+        net_imp::SocketBuilder::tcp(false).map(SocketBuilder)
+    }
+
+    /// Creates a TCP socket for IPv6.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v6().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn tcp_v6() -> io::Result<SocketBuilder> {
+        net_imp::SocketBuilder::tcp(true).map(SocketBuilder)
+    }
+
+    /// Creates a UDP socket for IPv4.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::udp_v4().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn udp_v4() -> io::Result<SocketBuilder> {
+        net_imp::SocketBuilder::udp(false).map(SocketBuilder)
+    }
+
+    /// Creates a UDP socket for IPv6.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::udp_v6().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn udp_v6() -> io::Result<SocketBuilder> {
+        net_imp::SocketBuilder::udp(true).map(SocketBuilder)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix, this lets a listening socket be bound to an address which
+    /// connections closed a moment ago still hold, without waiting for them
+    /// to time out. On Windows, it lets several sockets be bound to the same
+    /// address, even by another user.
+    ///
+    /// It has to be set before [`bind`] to make a difference.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_reuse_address(true).expect("set_reuse_address call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_address(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuse_address`].
+    ///
+    /// [`set_reuse_address`]: #method.set_reuse_address
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_reuse_address(true).expect("set_reuse_address call failed");
+    /// assert_eq!(builder.reuse_address().unwrap(), true);
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.0.reuse_address()
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. Otherwise an IPv6 socket bound to the
+    /// unspecified address can also talk to IPv4 peers, through IPv4-mapped
+    /// IPv6 addresses. The default differs between platforms.
+    ///
+    /// It has to be set before [`bind`], and only applies to IPv6 sockets.
+    ///
+    /// [`bind`]: #method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v6().unwrap();
+    /// builder.set_only_v6(false).expect("set_only_v6 call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// For more information about this option, see [`set_only_v6`].
+    ///
+    /// [`set_only_v6`]: #method.set_only_v6
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket, the size of
+    /// the buffer the kernel keeps for the data sent through it.
+    ///
+    /// The system may round the size up or down, or on Linux double it to
+    /// make room for its own bookkeeping, so [`send_buffer_size`] tells the
+    /// size it actually picked.
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_send_buffer_size(64 * 1024).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket, the size of
+    /// the buffer the kernel keeps for the data received on it.
+    ///
+    /// The system may round the size up or down, or on Linux double it to
+    /// make room for its own bookkeeping, so [`recv_buffer_size`] tells the
+    /// size it actually picked. For TCP, the size of the window advertised
+    /// to the peer follows from it, so it has to be set before [`connect`]
+    /// or [`listen`] to have its full effect.
+    ///
+    /// [`recv_buffer_size`]: #method.recv_buffer_size
+    /// [`connect`]: #method.connect
+    /// [`listen`]: #method.listen
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_recv_buffer_size(64 * 1024).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: #method.set_recv_buffer_size
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Turns TCP keepalive on or off for this socket.
+    ///
+    /// With `Some(time)`, the `SO_KEEPALIVE` option is set, and once the
+    /// connection has been idle for `time`, probes are sent to the peer
+    /// every `interval` until it answers or the connection is given up. With
+    /// `None`, no probes are sent and `interval` is ignored.
+    ///
+    /// The idle time and the interval between probes are set for this socket
+    /// on Linux, Android, macOS, iOS and Windows, and other platforms use
+    /// system-wide settings for both. The times are rounded up to whole
+    /// seconds, or milliseconds on Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_keepalive(Some(Duration::from_secs(60)), Duration::from_secs(10))
+    ///        .expect("set_keepalive call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn set_keepalive(&self, keepalive: Option<Duration>, interval: Duration)
+                         -> io::Result<()> {
+        self.0.set_keepalive(keepalive, interval)
+    }
+
+    /// Gets whether TCP keepalive is on for this socket, from the value of
+    /// the `SO_KEEPALIVE` option.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: #method.set_keepalive
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Gets how long the connection has to be idle before the first
+    /// keepalive probe is sent.
+    ///
+    /// This returns an error on the platforms where the time is a
+    /// system-wide setting, and on Windows before Windows 10 version 1709.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: #method.set_keepalive
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        self.0.keepalive_time()
+    }
+
+    /// Gets the interval between the keepalive probes after the first one.
+    ///
+    /// This returns an error on the platforms where the interval is a
+    /// system-wide setting, and on Windows before Windows 10 version 1709.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: #method.set_keepalive
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.0.keepalive_interval()
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// With `Some(time)`, dropping the connected socket blocks for up to
+    /// `time` while the data not sent yet is sent, and with
+    /// `Some(Duration::from_secs(0))` the connection is reset right away,
+    /// dropping that data. With `None`, the default, the data is sent in the
+    /// background after the socket is dropped.
+    ///
+    /// The time is rounded up to whole seconds, so that a time below a second
+    /// does not reset the connection, and Windows takes at most 65535 of
+    /// them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_linger(Some(Duration::from_secs(5))).expect("set_linger call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.0.set_linger(linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`set_linger`].
+    ///
+    /// [`set_linger`]: #method.set_linger
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.0.linger()
+    }
+
+    /// Binds this socket to the specified address.
+    ///
+    /// This is optional before [`connect`], which otherwise lets the system
+    /// pick a local address.
+    ///
+    /// [`connect`]: #method.connect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::udp_v4().unwrap();
+    /// builder.bind(&"127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        self.0.bind(addr)
+    }
+
+    /// Returns the socket address this socket is bound to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::{Ipv4Addr, SocketBuilder};
+    ///
+    /// let builder = SocketBuilder::udp_v4().unwrap();
+    /// builder.bind(&"127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// assert_eq!(builder.local_addr().unwrap().ip(), Ipv4Addr::new(127, 0, 0, 1));
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Gets the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. This can be useful for checking errors between
+    /// calls.
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+
+    /// Starts listening for connections on this TCP socket, which has to be
+    /// [bound] first, and returns it as a [`TcpListener`].
+    ///
+    /// `backlog` is how many connections the system keeps waiting to be
+    /// accepted. It may be capped, or otherwise taken as a hint.
+    ///
+    /// [bound]: #method.bind
+    /// [`TcpListener`]: struct.TcpListener.html
+    ///
+    /// # Errors
+    ///
+    /// This returns an error of the kind [`InvalidInput`] for a UDP socket.
+    ///
+    /// [`InvalidInput`]: ../io/enum.ErrorKind.html#variant.InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_reuse_address(true).unwrap();
+    /// builder.bind(&"127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// let listener = builder.listen(128).expect("couldn't listen");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn listen(self, backlog: i32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener::from_inner)
+    }
+
+    /// Connects this TCP socket to a remote host, and returns it as a
+    /// [`TcpStream`].
+    ///
+    /// [`TcpStream`]: struct.TcpStream.html
+    ///
+    /// # Errors
+    ///
+    /// This returns an error of the kind [`InvalidInput`] for a UDP socket.
+    ///
+    /// [`InvalidInput`]: ../io/enum.ErrorKind.html#variant.InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_keepalive(Some(Duration::from_secs(30)), Duration::from_secs(5)).unwrap();
+    /// let stream = builder.connect(&"127.0.0.1:8080".parse().unwrap())
+    ///                     .expect("Couldn't connect to the server...");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(addr).map(TcpStream::from_inner)
+    }
+
+    /// Returns this UDP socket as a [`UdpSocket`].
+    ///
+    /// It should usually be [bound] first, or else the system binds it to an
+    /// address of its choosing when it first sends.
+    ///
+    /// [`UdpSocket`]: struct.UdpSocket.html
+    /// [bound]: #method.bind
+    ///
+    /// # Errors
+    ///
+    /// This returns an error of the kind [`InvalidInput`] for a TCP socket.
+    ///
+    /// [`InvalidInput`]: ../io/enum.ErrorKind.html#variant.InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    ///
+    /// let builder = SocketBuilder::udp_v4().unwrap();
+    /// builder.set_recv_buffer_size(1 << 20).unwrap();
+    /// builder.bind(&"127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// let socket = builder.into_udp_socket().expect("not a UDP socket");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    pub fn into_udp_socket(self) -> io::Result<UdpSocket> {
+        self.0.into_udp_socket().map(UdpSocket::from_inner)
+    }
+}
+
+impl AsInner<net_imp::SocketBuilder> for SocketBuilder {
+    fn as_inner(&self) -> &net_imp::SocketBuilder { &self.0 }
+}
+
+impl FromInner<net_imp::SocketBuilder> for SocketBuilder {
+    fn from_inner(inner: net_imp::SocketBuilder) -> SocketBuilder { SocketBuilder(inner) }
+}
+
+impl IntoInner<net_imp::SocketBuilder> for SocketBuilder {
+    fn into_inner(self) -> net_imp::SocketBuilder { self.0 }
+}
+
+#[unstable(feature = "socket_builder", issue = "0")]
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use io::ErrorKind;
+    use io::prelude::*;
+    use net::*;
+    use net::test::{next_test_ip4, next_test_ip6};
+    use thread;
+    use time::Duration;
+
+    macro_rules! t {
+        ($e:expr) => {
+            match $e {
+                Ok(t) => t,
+                Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+            }
+        }
+    }
+
+    fn each_ip(f: &mut FnMut(SocketAddr)) {
+        f(next_test_ip4());
+        f(next_test_ip6());
+    }
+
+    fn builder(addr: &SocketAddr, udp: bool) -> SocketBuilder {
+        let builder = match (*addr, udp) {
+            (SocketAddr::V4(..), false) => SocketBuilder::tcp_v4(),
+            (SocketAddr::V6(..), false) => SocketBuilder::tcp_v6(),
+            (SocketAddr::V4(..), true) => SocketBuilder::udp_v4(),
+            (SocketAddr::V6(..), true) => SocketBuilder::udp_v6(),
+        };
+        t!(builder)
+    }
+
+    #[test]
+    fn listen_connect() {
+        each_ip(&mut |addr| {
+            let server = builder(&addr, false);
+            t!(server.set_reuse_address(true));
+            t!(server.bind(&addr));
+            assert_eq!(t!(server.local_addr()), addr);
+            let listener = t!(server.listen(16));
+
+            let t = thread::spawn(move || {
+                let client = builder(&addr, false);
+                t!(client.set_keepalive(Some(Duration::from_secs(30)), Duration::from_secs(5)));
+                let mut stream = t!(client.connect(&addr));
+                t!(stream.write_all(b"hello"));
+            });
+
+            let mut stream = t!(listener.accept()).0;
+            let mut buf = Vec::new();
+            t!(stream.read_to_end(&mut buf));
+            assert_eq!(buf, b"hello");
+            t.join().unwrap();
+        })
+    }
+
+    #[test]
+    fn udp() {
+        each_ip(&mut |addr| {
+            let builder = builder(&addr, true);
+            t!(builder.bind(&addr));
+            let socket = t!(builder.into_udp_socket());
+            t!(socket.send_to(b"hello", &addr));
+
+            let mut buf = [0; 16];
+            let (len, from) = t!(socket.recv_from(&mut buf));
+            assert_eq!(&buf[..len], b"hello");
+            assert_eq!(from, addr);
+        })
+    }
+
+    #[test]
+    fn options() {
+        let builder = builder(&next_test_ip4(), false);
+
+        t!(builder.set_reuse_address(true));
+        assert!(t!(builder.reuse_address()));
+        t!(builder.set_reuse_address(false));
+        assert!(!t!(builder.reuse_address()));
+
+        t!(builder.set_keepalive(Some(Duration::from_secs(30)), Duration::from_secs(5)));
+        assert!(t!(builder.keepalive()));
+        t!(builder.set_keepalive(None, Duration::from_secs(5)));
+        assert!(!t!(builder.keepalive()));
+
+        t!(builder.set_send_buffer_size(16 * 1024));
+        assert!(t!(builder.send_buffer_size()) >= 16 * 1024);
+        t!(builder.set_recv_buffer_size(16 * 1024));
+        assert!(t!(builder.recv_buffer_size()) >= 16 * 1024);
+    }
+
+    // The linger time is read back as it was set where it is counted in
+    // seconds, which elsewhere may take clock ticks or be capped
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android",
+              target_os = "macos", target_os = "ios", windows))]
+    fn linger() {
+        let builder = builder(&next_test_ip4(), false);
+
+        t!(builder.set_linger(Some(Duration::from_secs(5))));
+        assert_eq!(t!(builder.linger()), Some(Duration::from_secs(5)));
+        t!(builder.set_linger(Some(Duration::from_millis(500))));
+        assert_eq!(t!(builder.linger()), Some(Duration::from_secs(1)));
+        t!(builder.set_linger(None));
+        assert_eq!(t!(builder.linger()), None);
+    }
+
+    #[test]
+    fn mismatched_protocol() {
+        let addr = next_test_ip4();
+
+        let err = builder(&addr, true).listen(16).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = builder(&addr, true).connect(&addr).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = builder(&addr, false).into_udp_socket().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android",
+              target_os = "macos", target_os = "ios"))]
+    fn keepalive_times() {
+        let builder = builder(&next_test_ip4(), false);
+
+        t!(builder.set_keepalive(Some(Duration::from_secs(30)), Duration::from_secs(5)));
+        assert_eq!(t!(builder.keepalive_time()), Duration::from_secs(30));
+        assert_eq!(t!(builder.keepalive_interval()), Duration::from_secs(5));
+
+        t!(builder.set_keepalive(Some(Duration::from_millis(1500)), Duration::from_millis(1)));
+        assert_eq!(t!(builder.keepalive_time()), Duration::from_secs(2));
+        assert_eq!(t!(builder.keepalive_interval()), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn only_v6() {
+        let builder = builder(&next_test_ip6(), false);
+        t!(builder.set_only_v6(true));
+        assert!(t!(builder.only_v6()));
+        t!(builder.set_only_v6(false));
+        assert!(!t!(builder.only_v6()));
+    }
+}
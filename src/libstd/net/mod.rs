@@ -52,12 +52,15 @@ pub use self::tcp::{TcpStream, TcpListener, Incoming};
 pub use self::udp::UdpSocket;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::parser::AddrParseError;
+#[unstable(feature = "socket_builder", issue = "0")]
+pub use self::builder::SocketBuilder;
 
 mod ip;
 mod addr;
 mod tcp;
 mod udp;
 mod parser;
+mod builder;
 #[cfg(test)]
 mod test;
 
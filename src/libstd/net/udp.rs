@@ -488,6 +488,95 @@ impl UdpSocket {
         self.0.multicast_loop_v6()
     }
 
+    /// Sets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// Specifies the local interface, by its address, which outgoing multicast
+    /// packets are sent from. With [`Ipv4Addr::unspecified`], the default,
+    /// the system picks it from the routing table. This only applies to IPv4
+    /// sockets.
+    ///
+    /// [`Ipv4Addr::unspecified`]: struct.Ipv4Addr.html#method.unspecified
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_multicast_if)]
+    /// use std::net::{Ipv4Addr, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:34254").expect("couldn't bind to address");
+    /// socket.set_multicast_if_v4(&Ipv4Addr::new(192, 168, 0, 1))
+    ///       .expect("set_multicast_if_v4 call failed");
+    /// ```
+    #[unstable(feature = "udp_multicast_if", issue = "0")]
+    pub fn set_multicast_if_v4(&self, interface: &Ipv4Addr) -> io::Result<()> {
+        self.0.set_multicast_if_v4(interface)
+    }
+
+    /// Gets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_multicast_if_v4`][link].
+    ///
+    /// [link]: #method.set_multicast_if_v4
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_multicast_if)]
+    /// use std::net::{Ipv4Addr, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:34254").expect("couldn't bind to address");
+    /// socket.set_multicast_if_v4(&Ipv4Addr::new(192, 168, 0, 1))
+    ///       .expect("set_multicast_if_v4 call failed");
+    /// assert_eq!(socket.multicast_if_v4().unwrap(), Ipv4Addr::new(192, 168, 0, 1));
+    /// ```
+    #[unstable(feature = "udp_multicast_if", issue = "0")]
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        self.0.multicast_if_v4()
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    ///
+    /// Specifies the local interface, by its index, which outgoing multicast
+    /// packets are sent from. With `0`, the default, the system picks it
+    /// from the routing table. This only applies to IPv6 sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_multicast_if)]
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("[::]:34254").expect("couldn't bind to address");
+    /// socket.set_multicast_if_v6(2).expect("set_multicast_if_v6 call failed");
+    /// ```
+    #[unstable(feature = "udp_multicast_if", issue = "0")]
+    pub fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()> {
+        self.0.set_multicast_if_v6(interface)
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_multicast_if_v6`][link].
+    ///
+    /// [link]: #method.set_multicast_if_v6
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_multicast_if)]
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("[::]:34254").expect("couldn't bind to address");
+    /// socket.set_multicast_if_v6(2).expect("set_multicast_if_v6 call failed");
+    /// assert_eq!(socket.multicast_if_v6().unwrap(), 2);
+    /// ```
+    #[unstable(feature = "udp_multicast_if", issue = "0")]
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        self.0.multicast_if_v6()
+    }
+
     /// Sets the value for the `IP_TTL` option on this socket.
     ///
     /// This value sets the time-to-live field that is used in every packet sent
@@ -1092,6 +1181,16 @@ mod tests {
         assert_eq!(ttl, t!(stream.ttl()));
     }
 
+    #[test]
+    fn multicast_if_v4() {
+        let socket = t!(UdpSocket::bind(&next_test_ip4()));
+
+        t!(socket.set_multicast_if_v4(&Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(Ipv4Addr::new(127, 0, 0, 1), t!(socket.multicast_if_v4()));
+        t!(socket.set_multicast_if_v4(&Ipv4Addr::unspecified()));
+        assert_eq!(Ipv4Addr::unspecified(), t!(socket.multicast_if_v4()));
+    }
+
     #[test]
     fn set_nonblocking() {
         each_ip(&mut |addr, _| {
@@ -121,7 +121,7 @@ use io::prelude::*;
 use ffi::OsStr;
 use fmt;
 use fs;
-use io::{self, Initializer};
+use io::{self, Initializer, IoSlice, IoSliceMut};
 use path::Path;
 use str;
 use sys::pipe::{read2, AnonPipe};
@@ -187,6 +187,10 @@ impl AsInner<imp::Process> for Child {
     fn as_inner(&self) -> &imp::Process { &self.handle }
 }
 
+impl AsInnerMut<imp::Process> for Child {
+    fn as_inner_mut(&mut self) -> &mut imp::Process { &mut self.handle }
+}
+
 impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
     fn from_inner((handle, io): (imp::Process, imp::StdioPipes)) -> Child {
         Child {
@@ -235,6 +239,10 @@ impl Write for ChildStdin {
         self.inner.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -281,6 +289,11 @@ impl Read for ChildStdout {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -328,6 +341,11 @@ impl Read for ChildStderr {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -1535,6 +1553,122 @@ mod tests {
         assert!(Command::new("/bin/ls").uid(0).gid(0).spawn().is_err());
     }
 
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(all(unix, not(target_os = "fuchsia")))]
+    fn process_group_works() {
+        use os::unix::prelude::*;
+        use libc;
+
+        let mut p = Command::new("/bin/sh")
+                            .arg("-c").arg("read a")
+                            .stdin(Stdio::piped())
+                            .process_group(0)
+                            .spawn().unwrap();
+        let pid = p.id() as libc::pid_t;
+        assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+        p.signal_group(libc::SIGTERM).unwrap();
+        assert_eq!(p.wait().unwrap().signal(), Some(libc::SIGTERM));
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(all(unix, not(target_os = "fuchsia")))]
+    fn setsid_works() {
+        use os::unix::prelude::*;
+        use libc;
+
+        let mut p = Command::new("/bin/sh")
+                            .arg("-c").arg("read a")
+                            .stdin(Stdio::piped())
+                            .setsid(true)
+                            .spawn().unwrap();
+        let pid = p.id() as libc::pid_t;
+        assert_eq!(unsafe { libc::getsid(pid) }, pid);
+        assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+        p.kill().unwrap();
+        p.wait().unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(unix)]
+    fn inherit_fd_works() {
+        use os::unix::prelude::*;
+        use fs::{File, OpenOptions};
+        use sys_common::io::test::tmpdir;
+
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("out");
+        let out = OpenOptions::new().append(true).create(true).open(&path).unwrap();
+        // Map onto a number which is open in this process, so the mapping has
+        // to replace it in the child
+        let target = File::open("/dev/null").unwrap();
+        {
+            let script = format!("echo foobar >> /dev/fd/{}", target.as_raw_fd());
+            let mut cmd = Command::new("/bin/sh");
+            cmd.arg("-c").arg(script).inherit_fd(target.as_raw_fd(), out);
+            assert!(cmd.status().unwrap().success());
+            // the same mapping is handed to every spawn
+            assert!(cmd.status().unwrap().success());
+        }
+        let mut s = String::new();
+        File::open(&path).unwrap().read_to_string(&mut s).unwrap();
+        assert_eq!(s, "foobar\nfoobar\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn inherit_fd_sets_cloexec() {
+        use os::unix::prelude::*;
+        use fs::File;
+        use libc;
+
+        let file = File::open("/dev/null").unwrap();
+        let raw = unsafe { libc::dup(file.as_raw_fd()) };
+        assert!(raw >= 0);
+        assert_eq!(unsafe { libc::fcntl(raw, libc::F_GETFD) } & libc::FD_CLOEXEC, 0);
+        let mut cmd = Command::new("/bin/true");
+        cmd.inherit_fd(3, unsafe { File::from_raw_fd(raw) });
+        assert!(unsafe { libc::fcntl(raw, libc::F_GETFD) } & libc::FD_CLOEXEC != 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(all(unix, not(target_os = "fuchsia")))]
+    fn inherit_fd_keeps_spawn_errors() {
+        use os::unix::prelude::*;
+        use fs::File;
+
+        let sources = (0..4).map(|_| File::open("/dev/null").unwrap()).collect::<Vec<_>>();
+        // `spawn` reports a failed exec through a pipe which takes the lowest
+        // free numbers, so map onto those
+        let first = File::open("/dev/null").unwrap().as_raw_fd();
+        let mut cmd = Command::new("if-this-is-a-binary-then-the-world-has-ended");
+        for (i, source) in sources.into_iter().enumerate() {
+            cmd.inherit_fd(first + i as RawFd, source);
+        }
+        let err = cmd.spawn().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(all(unix, not(target_os = "fuchsia")))]
+    fn child_signal_works() {
+        use os::unix::prelude::*;
+        use libc;
+
+        let mut p = Command::new("/bin/sh")
+                            .arg("-c").arg("read a")
+                            .stdin(Stdio::piped())
+                            .spawn().unwrap();
+        p.signal(libc::SIGTERM).unwrap();
+        assert_eq!(p.wait().unwrap().signal(), Some(libc::SIGTERM));
+        let err = p.signal(libc::SIGTERM).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
     #[test]
     #[cfg_attr(target_os = "android", ignore)]
     fn test_process_status() {
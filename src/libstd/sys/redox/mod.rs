@@ -25,6 +25,7 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+pub mod io_slice;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
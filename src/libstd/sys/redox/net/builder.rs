@@ -0,0 +1,119 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use fmt;
+use io::{Error, ErrorKind, Result};
+use net::SocketAddr;
+use time::Duration;
+
+use super::{TcpListener, TcpStream, UdpSocket};
+
+// Sockets are opened by path all at once, so there's nothing to build them
+// with yet
+enum Void {}
+
+pub struct SocketBuilder(Void);
+
+impl SocketBuilder {
+    pub fn tcp(_v6: bool) -> Result<SocketBuilder> {
+        Err(Error::new(ErrorKind::Other, "SocketBuilder::tcp not implemented"))
+    }
+
+    pub fn udp(_v6: bool) -> Result<SocketBuilder> {
+        Err(Error::new(ErrorKind::Other, "SocketBuilder::udp not implemented"))
+    }
+
+    pub fn set_reuse_address(&self, _reuse: bool) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn reuse_address(&self) -> Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_only_v6(&self, _only_v6: bool) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn only_v6(&self) -> Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _size: usize) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn send_buffer_size(&self) -> Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _size: usize) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_buffer_size(&self) -> Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_keepalive(&self, _keepalive: Option<Duration>, _interval: Duration)
+                         -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn keepalive(&self) -> Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn keepalive_time(&self) -> Result<Duration> {
+        match self.0 {}
+    }
+
+    pub fn keepalive_interval(&self) -> Result<Duration> {
+        match self.0 {}
+    }
+
+    pub fn set_linger(&self, _linger: Option<Duration>) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn linger(&self) -> Result<Option<Duration>> {
+        match self.0 {}
+    }
+
+    pub fn bind(&self, _addr: &SocketAddr) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        match self.0 {}
+    }
+
+    pub fn listen(self, _backlog: i32) -> Result<TcpListener> {
+        match self.0 {}
+    }
+
+    pub fn connect(self, _addr: &SocketAddr) -> Result<TcpStream> {
+        match self.0 {}
+    }
+
+    pub fn into_udp_socket(self) -> Result<UdpSocket> {
+        match self.0 {}
+    }
+
+    pub fn take_error(&self) -> Result<Option<Error>> {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {}
+    }
+}
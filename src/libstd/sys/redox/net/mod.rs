@@ -20,11 +20,13 @@ use vec::{IntoIter, Vec};
 
 use self::dns::{Dns, DnsQuery};
 
+pub use self::builder::SocketBuilder;
 pub use self::tcp::{TcpStream, TcpListener};
 pub use self::udp::UdpSocket;
 
 pub mod netc;
 
+mod builder;
 mod dns;
 mod tcp;
 mod udp;
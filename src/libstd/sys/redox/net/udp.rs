@@ -99,6 +99,14 @@ impl UdpSocket {
         Err(Error::new(ErrorKind::Other, "UdpSocket::broadcast not implemented"))
     }
 
+    pub fn multicast_if_v4(&self) -> Result<Ipv4Addr> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::multicast_if_v4 not implemented"))
+    }
+
+    pub fn multicast_if_v6(&self) -> Result<u32> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::multicast_if_v6 not implemented"))
+    }
+
     pub fn multicast_loop_v4(&self) -> Result<bool> {
         Err(Error::new(ErrorKind::Other, "UdpSocket::multicast_loop_v4 not implemented"))
     }
@@ -150,6 +158,14 @@ impl UdpSocket {
         Err(Error::new(ErrorKind::Other, "UdpSocket::set_broadcast not implemented"))
     }
 
+    pub fn set_multicast_if_v4(&self, _interface: &Ipv4Addr) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_multicast_if_v4 not implemented"))
+    }
+
+    pub fn set_multicast_if_v6(&self, _interface: u32) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_multicast_if_v6 not implemented"))
+    }
+
     pub fn set_multicast_loop_v4(&self, _multicast_loop_v4: bool) -> Result<()> {
         Err(Error::new(ErrorKind::Other, "UdpSocket::set_multicast_loop_v4 not implemented"))
     }
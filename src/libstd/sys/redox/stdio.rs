@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use sys::{cvt, syscall};
 use sys::fd::FileDesc;
 
@@ -25,6 +25,10 @@ impl Stdin {
         fd.into_raw();
         ret
     }
+
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::default_read_vectored(|data| self.read(data), bufs)
+    }
 }
 
 impl Stdout {
@@ -37,6 +41,10 @@ impl Stdout {
         ret
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|data| self.write(data), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         cvt(syscall::fsync(1)).and(Ok(()))
     }
@@ -52,6 +60,10 @@ impl Stderr {
         ret
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|data| self.write(data), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         cvt(syscall::fsync(2)).and(Ok(()))
     }
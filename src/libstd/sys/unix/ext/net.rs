@@ -20,22 +20,31 @@ use libc;
 mod libc {
     pub use libc::c_int;
     pub type socklen_t = u32;
+    pub type pid_t = i32;
+    pub type uid_t = u32;
+    pub type gid_t = u32;
     pub struct sockaddr;
     #[derive(Clone)]
     pub struct sockaddr_un;
+    #[derive(Clone)]
+    pub struct ucred;
 }
 
 use ascii;
+use cmp;
 use ffi::OsStr;
 use fmt;
-use io::{self, Initializer};
+use io::{self, Initializer, IoSlice, IoSliceMut};
+use marker::PhantomData;
 use mem;
 use net::{self, Shutdown};
 use os::unix::ffi::OsStrExt;
 use os::unix::io::{RawFd, AsRawFd, FromRawFd, IntoRawFd};
 use path::Path;
+use ptr;
 use time::Duration;
 use sys::{self, cvt};
+use sys::fd::FileDesc;
 use sys::net::Socket;
 use sys_common::{self, AsInner, FromInner, IntoInner};
 
@@ -50,6 +59,13 @@ use libc::MSG_NOSIGNAL;
               target_os = "haiku", target_os = "bitrig")))]
 const MSG_NOSIGNAL: libc::c_int = 0x0;
 
+// Elsewhere the file descriptors received with `recvmsg` are made close-on-exec
+// right after it returns.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::MSG_CMSG_CLOEXEC;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const MSG_CMSG_CLOEXEC: libc::c_int = 0x0;
+
 fn sun_path_offset() -> usize {
     unsafe {
         // Work with an actual instance of the type since using a null pointer is UB
@@ -519,6 +535,124 @@ impl UnixStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.0.shutdown(how)
     }
+
+    /// Returns the credentials of the process on the other end of this
+    /// connection, as they were when it connected or created the pair of
+    /// sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(peer_credentials_unix_socket)]
+    /// use std::os::unix::net::UnixStream;
+    ///
+    /// let socket = UnixStream::connect("/tmp/sock").unwrap();
+    /// let cred = socket.peer_cred().expect("Couldn't get peer credentials");
+    /// println!("connected to a process of user {}", cred.uid);
+    /// ```
+    #[unstable(feature = "peer_credentials_unix_socket", issue = "0")]
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        peer_cred(&self.0)
+    }
+
+    /// Sets the `SO_PASSCRED` option, which makes the credentials of the
+    /// sender come with every message received, as [`ScmCredentials`].
+    ///
+    /// [`ScmCredentials`]: struct.ScmCredentials.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    /// use std::os::unix::net::UnixStream;
+    ///
+    /// let socket = UnixStream::connect("/tmp/sock").unwrap();
+    /// socket.set_passcred(true).expect("set_passcred function failed");
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        set_passcred(&self.0, passcred)
+    }
+
+    /// Returns the value of the `SO_PASSCRED` option, as set by
+    /// [`set_passcred`].
+    ///
+    /// [`set_passcred`]: #method.set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn passcred(&self) -> io::Result<bool> {
+        passcred(&self.0)
+    }
+
+    /// Sends the data in `bufs` along with the control messages of
+    /// `ancillary`, such as file descriptors.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    /// use std::fs::File;
+    /// use std::io::IoSlice;
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::{SocketAncillary, UnixStream};
+    ///
+    /// let socket = UnixStream::connect("/tmp/sock").unwrap();
+    /// let file = File::open("/etc/hosts").unwrap();
+    ///
+    /// let mut ancillary_buffer = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut ancillary_buffer[..]);
+    /// ancillary.add_fds(&[file.as_raw_fd()]);
+    /// socket.send_vectored_with_ancillary(&[IoSlice::new(b"hosts")], &mut ancillary)
+    ///       .expect("send_vectored_with_ancillary function failed");
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_vectored_with_ancillary(&self, bufs: &[IoSlice], ancillary: &mut SocketAncillary)
+                                        -> io::Result<usize> {
+        send_vectored_with_ancillary_to(&self.0, None, bufs, ancillary)
+    }
+
+    /// Receives data into `bufs` and control messages, such as file
+    /// descriptors, into `ancillary`.
+    ///
+    /// On success, returns the number of bytes read. The control messages
+    /// which didn't fit in `ancillary` are lost, and [`truncated`] tells if
+    /// there were any.
+    ///
+    /// [`truncated`]: struct.SocketAncillary.html#method.truncated
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    /// use std::fs::File;
+    /// use std::io::IoSliceMut;
+    /// use std::os::unix::io::FromRawFd;
+    /// use std::os::unix::net::{AncillaryData, SocketAncillary, UnixStream};
+    ///
+    /// let socket = UnixStream::connect("/tmp/sock").unwrap();
+    /// let mut buf = [0; 128];
+    /// let mut ancillary_buffer = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut ancillary_buffer[..]);
+    /// socket.recv_vectored_with_ancillary(&mut [IoSliceMut::new(&mut buf)], &mut ancillary)
+    ///       .expect("recv_vectored_with_ancillary function failed");
+    ///
+    /// for message in ancillary.messages() {
+    ///     if let Ok(AncillaryData::ScmRights(fds)) = message {
+    ///         for fd in fds {
+    ///             let file = unsafe { File::from_raw_fd(fd) };
+    ///             println!("received {:?}", file);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_vectored_with_ancillary(&self, bufs: &mut [IoSliceMut],
+                                        ancillary: &mut SocketAncillary) -> io::Result<usize> {
+        recv_vectored_with_ancillary_from(&self.0, bufs, ancillary).map(|(count, _, _, _)| count)
+    }
 }
 
 #[stable(feature = "unix_socket", since = "1.10.0")]
@@ -527,6 +661,10 @@ impl io::Read for UnixStream {
         io::Read::read(&mut &*self, buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::Read::read_vectored(&mut &*self, bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -539,6 +677,10 @@ impl<'a> io::Read for &'a UnixStream {
         self.0.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -551,6 +693,10 @@ impl io::Write for UnixStream {
         io::Write::write(&mut &*self, buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::Write::write_vectored(&mut &*self, bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         io::Write::flush(&mut &*self)
     }
@@ -562,6 +708,10 @@ impl<'a> io::Write for &'a UnixStream {
         self.0.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -646,6 +796,78 @@ impl IntoRawFd for net::UdpSocket {
     }
 }
 
+#[unstable(feature = "socket_builder", issue = "0")]
+impl AsRawFd for net::SocketBuilder {
+    fn as_raw_fd(&self) -> RawFd { *self.as_inner().socket().as_inner() }
+}
+
+#[unstable(feature = "socket_builder", issue = "0")]
+impl IntoRawFd for net::SocketBuilder {
+    fn into_raw_fd(self) -> RawFd {
+        self.into_inner().into_socket().into_inner()
+    }
+}
+
+/// Unix-specific extensions to [`net::SocketBuilder`].
+///
+/// [`net::SocketBuilder`]: ../../../net/struct.SocketBuilder.html
+#[cfg(any(target_os = "linux", target_os = "android",
+          target_os = "macos", target_os = "ios",
+          target_os = "freebsd", target_os = "dragonfly",
+          target_os = "openbsd", target_os = "netbsd",
+          target_os = "bitrig"))]
+#[unstable(feature = "socket_builder", issue = "0")]
+pub trait SocketBuilderExt {
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// If set on every one of them before they are bound, several sockets
+    /// can be bound to the same address and port. On Linux, the connections
+    /// or datagrams coming to it are then spread between them, which lets
+    /// several processes or threads each have their own listener for the
+    /// same port. The sockets have to belong to the same user.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(socket_builder)]
+    /// use std::net::SocketBuilder;
+    /// use std::os::unix::net::SocketBuilderExt;
+    ///
+    /// let builder = SocketBuilder::tcp_v4().unwrap();
+    /// builder.set_reuse_port(true).expect("set_reuse_port call failed");
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "0")]
+    fn set_reuse_port(&self, reuse: bool) -> io::Result<()>;
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuse_port`].
+    ///
+    /// [`set_reuse_port`]: #tymethod.set_reuse_port
+    #[unstable(feature = "socket_builder", issue = "0")]
+    fn reuse_port(&self) -> io::Result<bool>;
+}
+
+#[cfg(any(target_os = "linux", target_os = "android",
+          target_os = "macos", target_os = "ios",
+          target_os = "freebsd", target_os = "dragonfly",
+          target_os = "openbsd", target_os = "netbsd",
+          target_os = "bitrig"))]
+#[unstable(feature = "socket_builder", issue = "0")]
+impl SocketBuilderExt for net::SocketBuilder {
+    fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        sys_common::net::setsockopt(self.as_inner().socket(), libc::SOL_SOCKET,
+                                    libc::SO_REUSEPORT, reuse as libc::c_int)
+    }
+
+    fn reuse_port(&self) -> io::Result<bool> {
+        let raw: libc::c_int = sys_common::net::getsockopt(self.as_inner().socket(),
+                                                           libc::SOL_SOCKET,
+                                                           libc::SO_REUSEPORT)?;
+        Ok(raw != 0)
+    }
+}
+
 /// A structure representing a Unix domain socket server.
 ///
 /// # Examples
@@ -1384,6 +1606,128 @@ impl UnixDatagram {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.0.shutdown(how)
     }
+
+    /// Sets the `SO_PASSCRED` option, which makes the credentials of the
+    /// sender come with every datagram received, as [`ScmCredentials`].
+    ///
+    /// [`ScmCredentials`]: struct.ScmCredentials.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(unix_socket_ancillary_data)]
+    /// use std::os::unix::net::UnixDatagram;
+    ///
+    /// let sock = UnixDatagram::unbound().unwrap();
+    /// sock.set_passcred(true).expect("set_passcred function failed");
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        set_passcred(&self.0, passcred)
+    }
+
+    /// Returns the value of the `SO_PASSCRED` option, as set by
+    /// [`set_passcred`].
+    ///
+    /// [`set_passcred`]: #method.set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn passcred(&self) -> io::Result<bool> {
+        passcred(&self.0)
+    }
+
+    /// Sends a datagram made of the data in `bufs` to the socket's peer,
+    /// along with the control messages of `ancillary`.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    /// use std::io::IoSlice;
+    /// use std::os::unix::io::AsRawFd;
+    /// use std::os::unix::net::{SocketAncillary, UnixDatagram};
+    ///
+    /// let sock = UnixDatagram::unbound().unwrap();
+    /// sock.connect("/some/sock").expect("Couldn't connect");
+    ///
+    /// let mut ancillary_buffer = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut ancillary_buffer[..]);
+    /// ancillary.add_fds(&[sock.as_raw_fd()]);
+    /// sock.send_vectored_with_ancillary(&[IoSlice::new(b"myself")], &mut ancillary)
+    ///     .expect("send_vectored_with_ancillary function failed");
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_vectored_with_ancillary(&self, bufs: &[IoSlice], ancillary: &mut SocketAncillary)
+                                        -> io::Result<usize> {
+        send_vectored_with_ancillary_to(&self.0, None, bufs, ancillary)
+    }
+
+    /// Sends a datagram made of the data in `bufs` to the specified address,
+    /// along with the control messages of `ancillary`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_vectored_with_ancillary_to<P: AsRef<Path>>(&self,
+                                                           bufs: &[IoSlice],
+                                                           ancillary: &mut SocketAncillary,
+                                                           path: P)
+                                                           -> io::Result<usize> {
+        send_vectored_with_ancillary_to(&self.0, Some(path.as_ref()), bufs, ancillary)
+    }
+
+    /// Receives a datagram into `bufs` and its control messages into
+    /// `ancillary`.
+    ///
+    /// On success, returns the number of bytes read and whether the datagram
+    /// was cut short because it didn't fit in `bufs`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    /// use std::io::IoSliceMut;
+    /// use std::os::unix::net::{AncillaryData, SocketAncillary, UnixDatagram};
+    ///
+    /// let sock = UnixDatagram::bind("/path/to/the/socket").unwrap();
+    /// let mut buf = [0; 128];
+    /// let mut ancillary_buffer = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut ancillary_buffer[..]);
+    /// let (size, truncated) =
+    ///     sock.recv_vectored_with_ancillary(&mut [IoSliceMut::new(&mut buf)], &mut ancillary)
+    ///         .expect("recv_vectored_with_ancillary function failed");
+    /// println!("received {} bytes, truncated: {}", size, truncated);
+    ///
+    /// for message in ancillary.messages() {
+    ///     if let Ok(AncillaryData::ScmRights(fds)) = message {
+    ///         println!("received the file descriptors {:?}", fds.collect::<Vec<_>>());
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_vectored_with_ancillary(&self, bufs: &mut [IoSliceMut],
+                                        ancillary: &mut SocketAncillary)
+                                        -> io::Result<(usize, bool)> {
+        recv_vectored_with_ancillary_from(&self.0, bufs, ancillary)
+            .map(|(count, truncated, _, _)| (count, truncated))
+    }
+
+    /// Receives a datagram into `bufs` and its control messages into
+    /// `ancillary`.
+    ///
+    /// On success, returns the number of bytes read, whether the datagram was
+    /// cut short because it didn't fit in `bufs`, and the address it came
+    /// from.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_vectored_with_ancillary_from(&self, bufs: &mut [IoSliceMut],
+                                             ancillary: &mut SocketAncillary)
+                                             -> io::Result<(usize, bool, SocketAddr)> {
+        let (count, truncated, addr, len) =
+            recv_vectored_with_ancillary_from(&self.0, bufs, ancillary)?;
+        Ok((count, truncated, SocketAddr::from_parts(addr, len)?))
+    }
 }
 
 #[stable(feature = "unix_socket", since = "1.10.0")]
@@ -1407,82 +1751,1152 @@ impl IntoRawFd for UnixDatagram {
     }
 }
 
-#[cfg(all(test, not(target_os = "emscripten")))]
-mod test {
-    use thread;
-    use io;
-    use io::prelude::*;
-    use time::Duration;
-    use sys_common::io::test::tmpdir;
-
-    use super::*;
+/// A Unix sequenced-packet socket server, which connections are accepted on
+/// as [`UnixSeqpacket`]s.
+///
+/// [`UnixSeqpacket`]: struct.UnixSeqpacket.html
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_socket_seqpacket)]
+/// use std::os::unix::net::UnixSeqpacketListener;
+///
+/// let listener = UnixSeqpacketListener::bind("/path/to/the/socket").unwrap();
+/// let (socket, _) = listener.accept().unwrap();
+/// let mut buf = [0; 64];
+/// let size = socket.recv(&mut buf).unwrap();
+/// socket.send(&buf[..size]).unwrap();
+/// ```
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+pub struct UnixSeqpacketListener(Socket);
 
-    macro_rules! or_panic {
-        ($e:expr) => {
-            match $e {
-                Ok(e) => e,
-                Err(e) => panic!("{}", e),
-            }
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl fmt::Debug for UnixSeqpacketListener {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut builder = fmt.debug_struct("UnixSeqpacketListener");
+        builder.field("fd", self.0.as_inner());
+        if let Ok(addr) = self.local_addr() {
+            builder.field("local", &addr);
         }
+        builder.finish()
     }
+}
 
-    #[test]
-    fn basic() {
-        let dir = tmpdir();
-        let socket_path = dir.path().join("sock");
-        let msg1 = b"hello";
-        let msg2 = b"world!";
-
-        let listener = or_panic!(UnixListener::bind(&socket_path));
-        let thread = thread::spawn(move || {
-            let mut stream = or_panic!(listener.accept()).0;
-            let mut buf = [0; 5];
-            or_panic!(stream.read(&mut buf));
-            assert_eq!(&msg1[..], &buf[..]);
-            or_panic!(stream.write_all(msg2));
-        });
+impl UnixSeqpacketListener {
+    /// Creates a new `UnixSeqpacketListener` bound to the specified socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacketListener;
+    ///
+    /// let listener = match UnixSeqpacketListener::bind("/path/to/the/socket") {
+    ///     Ok(sock) => sock,
+    ///     Err(e) => {
+    ///         println!("Couldn't bind: {:?}", e);
+    ///         return
+    ///     }
+    /// };
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<UnixSeqpacketListener> {
+        fn inner(path: &Path) -> io::Result<UnixSeqpacketListener> {
+            unsafe {
+                let inner = Socket::new_raw(libc::AF_UNIX, libc::SOCK_SEQPACKET)?;
+                let (addr, len) = sockaddr_un(path)?;
 
-        let mut stream = or_panic!(UnixStream::connect(&socket_path));
-        assert_eq!(Some(&*socket_path),
-                   stream.peer_addr().unwrap().as_pathname());
-        or_panic!(stream.write_all(msg1));
-        let mut buf = vec![];
-        or_panic!(stream.read_to_end(&mut buf));
-        assert_eq!(&msg2[..], &buf[..]);
-        drop(stream);
+                cvt(libc::bind(*inner.as_inner(), &addr as *const _ as *const _, len as _))?;
+                cvt(libc::listen(*inner.as_inner(), 128))?;
 
-        thread.join().unwrap();
+                Ok(UnixSeqpacketListener(inner))
+            }
+        }
+        inner(path.as_ref())
     }
 
-    #[test]
-    fn pair() {
-        let msg1 = b"hello";
-        let msg2 = b"world!";
-
-        let (mut s1, mut s2) = or_panic!(UnixStream::pair());
-        let thread = thread::spawn(move || {
-            // s1 must be moved in or the test will hang!
-            let mut buf = [0; 5];
-            or_panic!(s1.read(&mut buf));
-            assert_eq!(&msg1[..], &buf[..]);
-            or_panic!(s1.write_all(msg2));
-        });
-
-        or_panic!(s2.write_all(msg1));
-        let mut buf = vec![];
-        or_panic!(s2.read_to_end(&mut buf));
-        assert_eq!(&msg2[..], &buf[..]);
-        drop(s2);
+    /// Accepts a new incoming connection to this listener.
+    ///
+    /// This function will block the calling thread until a new connection is
+    /// established. When established, the corresponding [`UnixSeqpacket`]
+    /// and the remote peer's address will be returned.
+    ///
+    /// [`UnixSeqpacket`]: struct.UnixSeqpacket.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacketListener;
+    ///
+    /// let listener = UnixSeqpacketListener::bind("/path/to/the/socket").unwrap();
+    ///
+    /// match listener.accept() {
+    ///     Ok((socket, addr)) => println!("Got a client: {:?}", addr),
+    ///     Err(e) => println!("accept function failed: {:?}", e),
+    /// }
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn accept(&self) -> io::Result<(UnixSeqpacket, SocketAddr)> {
+        let mut storage: libc::sockaddr_un = unsafe { mem::zeroed() };
+        let mut len = mem::size_of_val(&storage) as libc::socklen_t;
+        let sock = self.0.accept(&mut storage as *mut _ as *mut _, &mut len)?;
+        let addr = SocketAddr::from_parts(storage, len)?;
+        Ok((UnixSeqpacket(sock), addr))
+    }
 
-        thread.join().unwrap();
+    /// Creates a new independently owned handle to the underlying socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacketListener;
+    ///
+    /// let listener = UnixSeqpacketListener::bind("/path/to/the/socket").unwrap();
+    /// let listener_copy = listener.try_clone().expect("try_clone failed");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn try_clone(&self) -> io::Result<UnixSeqpacketListener> {
+        self.0.duplicate().map(UnixSeqpacketListener)
     }
 
-    #[test]
-    fn try_clone() {
-        let dir = tmpdir();
-        let socket_path = dir.path().join("sock");
-        let msg1 = b"hello";
-        let msg2 = b"world";
+    /// Returns the local socket address of this listener.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacketListener;
+    ///
+    /// let listener = UnixSeqpacketListener::bind("/path/to/the/socket").unwrap();
+    /// let addr = listener.local_addr().expect("Couldn't get local address");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        SocketAddr::new(|addr, len| unsafe { libc::getsockname(*self.0.as_inner(), addr, len) })
+    }
+
+    /// Moves the socket into or out of nonblocking mode.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacketListener;
+    ///
+    /// let listener = UnixSeqpacketListener::bind("/path/to/the/socket").unwrap();
+    /// listener.set_nonblocking(true).expect("Couldn't set non blocking");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacketListener;
+    ///
+    /// let listener = UnixSeqpacketListener::bind("/tmp/sock").unwrap();
+    ///
+    /// if let Ok(Some(err)) = listener.take_error() {
+    ///     println!("Got error: {:?}", err);
+    /// }
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+}
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl AsRawFd for UnixSeqpacketListener {
+    fn as_raw_fd(&self) -> RawFd {
+        *self.0.as_inner()
+    }
+}
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl FromRawFd for UnixSeqpacketListener {
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixSeqpacketListener {
+        UnixSeqpacketListener(Socket::from_inner(fd))
+    }
+}
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl IntoRawFd for UnixSeqpacketListener {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_inner()
+    }
+}
+
+/// A connected Unix sequenced-packet socket.
+///
+/// Like a [`UnixStream`], it is connected to a single peer and delivers data
+/// reliably and in order, but like a [`UnixDatagram`], the data is sent and
+/// received as messages: each call to [`recv`] returns what one call to
+/// [`send`] on the other end sent.
+///
+/// [`UnixStream`]: struct.UnixStream.html
+/// [`UnixDatagram`]: struct.UnixDatagram.html
+/// [`recv`]: #method.recv
+/// [`send`]: #method.send
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_socket_seqpacket)]
+/// use std::os::unix::net::UnixSeqpacket;
+///
+/// let socket = UnixSeqpacket::connect("/path/to/my/socket").unwrap();
+/// socket.send(b"hello").unwrap();
+/// socket.send(b"world").unwrap();
+/// let mut buf = [0; 64];
+/// let size = socket.recv(&mut buf).unwrap();
+/// println!("{:?}", &buf[..size]);
+/// ```
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+pub struct UnixSeqpacket(Socket);
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl fmt::Debug for UnixSeqpacket {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut builder = fmt.debug_struct("UnixSeqpacket");
+        builder.field("fd", self.0.as_inner());
+        if let Ok(addr) = self.local_addr() {
+            builder.field("local", &addr);
+        }
+        if let Ok(addr) = self.peer_addr() {
+            builder.field("peer", &addr);
+        }
+        builder.finish()
+    }
+}
+
+impl UnixSeqpacket {
+    /// Connects to the socket named by `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = match UnixSeqpacket::connect("/tmp/sock") {
+    ///     Ok(sock) => sock,
+    ///     Err(e) => {
+    ///         println!("Couldn't connect: {:?}", e);
+    ///         return
+    ///     }
+    /// };
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixSeqpacket> {
+        fn inner(path: &Path) -> io::Result<UnixSeqpacket> {
+            unsafe {
+                let inner = Socket::new_raw(libc::AF_UNIX, libc::SOCK_SEQPACKET)?;
+                let (addr, len) = sockaddr_un(path)?;
+
+                cvt(libc::connect(*inner.as_inner(), &addr as *const _ as *const _, len))?;
+                Ok(UnixSeqpacket(inner))
+            }
+        }
+        inner(path.as_ref())
+    }
+
+    /// Creates an unnamed pair of connected sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let (sock1, sock2) = match UnixSeqpacket::pair() {
+    ///     Ok((sock1, sock2)) => (sock1, sock2),
+    ///     Err(e) => {
+    ///         println!("Couldn't create a pair of sockets: {:?}", e);
+    ///         return
+    ///     }
+    /// };
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn pair() -> io::Result<(UnixSeqpacket, UnixSeqpacket)> {
+        let (i1, i2) = Socket::new_pair(libc::AF_UNIX, libc::SOCK_SEQPACKET)?;
+        Ok((UnixSeqpacket(i1), UnixSeqpacket(i2)))
+    }
+
+    /// Creates a new independently owned handle to the underlying socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = UnixSeqpacket::connect("/tmp/sock").unwrap();
+    /// let sock_copy = socket.try_clone().expect("Couldn't clone socket");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn try_clone(&self) -> io::Result<UnixSeqpacket> {
+        self.0.duplicate().map(UnixSeqpacket)
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = UnixSeqpacket::connect("/tmp/sock").unwrap();
+    /// let addr = socket.local_addr().expect("Couldn't get local address");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        SocketAddr::new(|addr, len| unsafe { libc::getsockname(*self.0.as_inner(), addr, len) })
+    }
+
+    /// Returns the socket address of the remote half of this connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = UnixSeqpacket::connect("/tmp/sock").unwrap();
+    /// let addr = socket.peer_addr().expect("Couldn't get peer address");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        SocketAddr::new(|addr, len| unsafe { libc::getpeername(*self.0.as_inner(), addr, len) })
+    }
+
+    /// Returns the credentials of the process on the other end of this
+    /// connection, as they were when it connected or created the pair of
+    /// sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket, peer_credentials_unix_socket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = UnixSeqpacket::connect("/tmp/sock").unwrap();
+    /// let cred = socket.peer_cred().expect("Couldn't get peer credentials");
+    /// ```
+    #[unstable(feature = "peer_credentials_unix_socket", issue = "0")]
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        peer_cred(&self.0)
+    }
+
+    /// Receives a message from the socket.
+    ///
+    /// On success, returns the number of bytes read. The part of the message
+    /// which doesn't fit in `buf` is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = UnixSeqpacket::connect("/tmp/sock").unwrap();
+    /// let mut buf = vec![0; 10];
+    /// socket.recv(buf.as_mut_slice()).expect("recv function failed");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    /// Sends a message on the socket.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_seqpacket)]
+    /// use std::os::unix::net::UnixSeqpacket;
+    ///
+    /// let socket = UnixSeqpacket::connect("/tmp/sock").unwrap();
+    /// socket.send(b"omelette au fromage").expect("send function failed");
+    /// ```
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        unsafe {
+            let count = cvt(libc::send(*self.0.as_inner(),
+                                       buf.as_ptr() as *const _,
+                                       buf.len(),
+                                       MSG_NOSIGNAL))?;
+            Ok(count as usize)
+        }
+    }
+
+    /// Sets the `SO_PASSCRED` option, which makes the credentials of the
+    /// sender come with every message received, as [`ScmCredentials`].
+    ///
+    /// [`ScmCredentials`]: struct.ScmCredentials.html
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        set_passcred(&self.0, passcred)
+    }
+
+    /// Returns the value of the `SO_PASSCRED` option, as set by
+    /// [`set_passcred`].
+    ///
+    /// [`set_passcred`]: #method.set_passcred
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn passcred(&self) -> io::Result<bool> {
+        passcred(&self.0)
+    }
+
+    /// Sends a message made of the data in `bufs`, along with the control
+    /// messages of `ancillary`.
+    ///
+    /// On success, returns the number of bytes written.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_vectored_with_ancillary(&self, bufs: &[IoSlice], ancillary: &mut SocketAncillary)
+                                        -> io::Result<usize> {
+        send_vectored_with_ancillary_to(&self.0, None, bufs, ancillary)
+    }
+
+    /// Receives a message into `bufs` and its control messages into
+    /// `ancillary`.
+    ///
+    /// On success, returns the number of bytes read and whether the message
+    /// was cut short because it didn't fit in `bufs`.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_vectored_with_ancillary(&self, bufs: &mut [IoSliceMut],
+                                        ancillary: &mut SocketAncillary)
+                                        -> io::Result<(usize, bool)> {
+        recv_vectored_with_ancillary_from(&self.0, bufs, ancillary)
+            .map(|(count, truncated, _, _)| (count, truncated))
+    }
+
+    /// Sets the read timeout for the socket.
+    ///
+    /// If the provided value is [`None`], then [`recv`] calls will block
+    /// indefinitely. It is an error to pass the zero [`Duration`] to this
+    /// method.
+    ///
+    /// [`None`]: ../../../../std/option/enum.Option.html#variant.None
+    /// [`recv`]: #method.recv
+    /// [`Duration`]: ../../../../std/time/struct.Duration.html
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_timeout(timeout, libc::SO_RCVTIMEO)
+    }
+
+    /// Sets the write timeout for the socket.
+    ///
+    /// If the provided value is [`None`], then [`send`] calls will block
+    /// indefinitely. It is an error to pass the zero [`Duration`] to this
+    /// method.
+    ///
+    /// [`None`]: ../../../../std/option/enum.Option.html#variant.None
+    /// [`send`]: #method.send
+    /// [`Duration`]: ../../../../std/time/struct.Duration.html
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.0.set_timeout(timeout, libc::SO_SNDTIMEO)
+    }
+
+    /// Returns the read timeout of this socket.
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.0.timeout(libc::SO_RCVTIMEO)
+    }
+
+    /// Returns the write timeout of this socket.
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        self.0.timeout(libc::SO_SNDTIMEO)
+    }
+
+    /// Moves the socket into or out of nonblocking mode.
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Returns the value of the `SO_ERROR` option.
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+
+    /// Shuts down the read, write, or both halves of this connection.
+    ///
+    /// This function will cause all pending and future I/O calls on the
+    /// specified portions to immediately return with an appropriate value
+    /// (see the documentation of [`Shutdown`]).
+    ///
+    /// [`Shutdown`]: ../../../../std/net/enum.Shutdown.html
+    #[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.0.shutdown(how)
+    }
+}
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl AsRawFd for UnixSeqpacket {
+    fn as_raw_fd(&self) -> RawFd {
+        *self.0.as_inner()
+    }
+}
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl FromRawFd for UnixSeqpacket {
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixSeqpacket {
+        UnixSeqpacket(Socket::from_inner(fd))
+    }
+}
+
+#[unstable(feature = "unix_socket_seqpacket", issue = "0")]
+impl IntoRawFd for UnixSeqpacket {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_inner()
+    }
+}
+
+/// The credentials of a process, as given by [`UnixStream::peer_cred`] for
+/// the process on the other end of a connection.
+///
+/// [`UnixStream::peer_cred`]: struct.UnixStream.html#method.peer_cred
+#[unstable(feature = "peer_credentials_unix_socket", issue = "0")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UCred {
+    /// The effective user ID of the process.
+    pub uid: libc::uid_t,
+    /// The effective group ID of the process.
+    pub gid: libc::gid_t,
+    /// The process ID, on the platforms which give it.
+    pub pid: Option<libc::pid_t>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn peer_cred(socket: &Socket) -> io::Result<UCred> {
+    let cred: libc::ucred = sys_common::net::getsockopt(socket, libc::SOL_SOCKET,
+                                                        libc::SO_PEERCRED)?;
+    Ok(UCred { uid: cred.uid, gid: cred.gid, pid: Some(cred.pid) })
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios",
+          target_os = "freebsd", target_os = "dragonfly",
+          target_os = "openbsd", target_os = "netbsd",
+          target_os = "bitrig"))]
+fn peer_cred(socket: &Socket) -> io::Result<UCred> {
+    extern {
+        fn getpeereid(socket: libc::c_int,
+                      euid: *mut libc::uid_t,
+                      egid: *mut libc::gid_t) -> libc::c_int;
+    }
+
+    let mut uid = 0;
+    let mut gid = 0;
+    cvt(unsafe { getpeereid(*socket.as_inner(), &mut uid, &mut gid) })?;
+    Ok(UCred { uid, gid, pid: None })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android",
+              target_os = "macos", target_os = "ios",
+              target_os = "freebsd", target_os = "dragonfly",
+              target_os = "openbsd", target_os = "netbsd",
+              target_os = "bitrig")))]
+fn peer_cred(_socket: &Socket) -> io::Result<UCred> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "peer credentials are not supported on this platform"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_passcred(socket: &Socket, passcred: bool) -> io::Result<()> {
+    sys_common::net::setsockopt(socket, libc::SOL_SOCKET, libc::SO_PASSCRED,
+                                passcred as libc::c_int)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn passcred(socket: &Socket) -> io::Result<bool> {
+    let passcred: libc::c_int = sys_common::net::getsockopt(socket, libc::SOL_SOCKET,
+                                                            libc::SO_PASSCRED)?;
+    Ok(passcred != 0)
+}
+
+// The control messages in the buffer of `sendmsg` and `recvmsg` are padded to
+// the size of a `long`, except on Apple platforms. This is what the `CMSG_*`
+// macros of C compute.
+#[cfg(target_vendor = "apple")]
+type CmsgAlign = u32;
+#[cfg(not(target_vendor = "apple"))]
+type CmsgAlign = usize;
+
+fn cmsg_align(len: usize) -> usize {
+    let align = mem::size_of::<CmsgAlign>();
+    (len + align - 1) & !(align - 1)
+}
+
+/// The length of the header of a control message, including its padding.
+fn cmsg_header_len() -> usize {
+    cmsg_align(mem::size_of::<libc::cmsghdr>())
+}
+
+fn send_vectored_with_ancillary_to(socket: &Socket,
+                                   path: Option<&Path>,
+                                   bufs: &[IoSlice],
+                                   ancillary: &mut SocketAncillary)
+                                   -> io::Result<usize> {
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        let (mut addr, len) = match path {
+            Some(path) => sockaddr_un(path)?,
+            None => (mem::zeroed(), 0),
+        };
+        if path.is_some() {
+            msg.msg_name = &mut addr as *mut _ as *mut _;
+            msg.msg_namelen = len;
+        }
+        // `IoSlice` is laid out like `iovec`
+        msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = bufs.len() as _;
+        // Some platforms refuse a control buffer which is there but empty
+        if ancillary.length > 0 {
+            msg.msg_control = ancillary.buffer.as_mut_ptr() as *mut _;
+            msg.msg_controllen = ancillary.length as _;
+        }
+
+        ancillary.truncated = false;
+        let count = cvt(libc::sendmsg(*socket.as_inner(), &msg, MSG_NOSIGNAL))?;
+        Ok(count as usize)
+    }
+}
+
+/// Receives into `bufs` and `ancillary`, returning the number of bytes read,
+/// whether the message was truncated, and the address it came from.
+fn recv_vectored_with_ancillary_from(socket: &Socket,
+                                     bufs: &mut [IoSliceMut],
+                                     ancillary: &mut SocketAncillary)
+                                     -> io::Result<(usize, bool,
+                                                    libc::sockaddr_un, libc::socklen_t)> {
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        let mut addr: libc::sockaddr_un = mem::zeroed();
+        msg.msg_name = &mut addr as *mut _ as *mut _;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_un>() as libc::socklen_t;
+        // `IoSliceMut` is laid out like `iovec`
+        msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = bufs.len() as _;
+        if !ancillary.buffer.is_empty() {
+            msg.msg_control = ancillary.buffer.as_mut_ptr() as *mut _;
+            msg.msg_controllen = ancillary.buffer.len() as _;
+        }
+
+        let count = cvt(libc::recvmsg(*socket.as_inner(), &mut msg, MSG_CMSG_CLOEXEC))?;
+        ancillary.length = msg.msg_controllen as usize;
+        ancillary.truncated = msg.msg_flags & libc::MSG_CTRUNC != 0;
+        if MSG_CMSG_CLOEXEC == 0 {
+            ancillary.set_cloexec();
+        }
+
+        Ok((count as usize, msg.msg_flags & libc::MSG_TRUNC != 0, addr, msg.msg_namelen))
+    }
+}
+
+/// A buffer for the control messages, or ancillary data, which are sent and
+/// received along with the data on a Unix socket.
+///
+/// Control messages carry things which the operating system interprets on
+/// the way, like file descriptors ([`ScmRights`]), which are duplicated into
+/// the receiving process, or the credentials of the sender
+/// ([`ScmCredentials`]), which are checked by the kernel.
+///
+/// The buffer is provided by the caller, and [`add_fds`] and [`add_creds`]
+/// report whether there was room for what they add.
+///
+/// [`ScmRights`]: struct.ScmRights.html
+/// [`ScmCredentials`]: struct.ScmCredentials.html
+/// [`add_fds`]: #method.add_fds
+/// [`add_creds`]: #method.add_creds
+///
+/// # Examples
+///
+/// ```
+/// #![feature(unix_socket_ancillary_data)]
+/// use std::io::{IoSlice, IoSliceMut};
+/// use std::os::unix::net::{AncillaryData, SocketAncillary, UnixStream};
+///
+/// let (sock1, sock2) = UnixStream::pair().unwrap();
+///
+/// let mut send_buffer = [0; 128];
+/// let mut ancillary = SocketAncillary::new(&mut send_buffer[..]);
+/// assert!(ancillary.add_fds(&[0]));
+/// sock1.send_vectored_with_ancillary(&[IoSlice::new(b"stdin")], &mut ancillary).unwrap();
+///
+/// let mut buf = [0; 5];
+/// let mut recv_buffer = [0; 128];
+/// let mut ancillary = SocketAncillary::new(&mut recv_buffer[..]);
+/// sock2.recv_vectored_with_ancillary(&mut [IoSliceMut::new(&mut buf)], &mut ancillary)
+///      .unwrap();
+///
+/// for message in ancillary.messages() {
+///     if let Ok(AncillaryData::ScmRights(fds)) = message {
+///         for fd in fds {
+///             println!("received a duplicate of stdin: {}", fd);
+///         }
+///     }
+/// }
+/// ```
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct SocketAncillary<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+    truncated: bool,
+}
+
+impl<'a> SocketAncillary<'a> {
+    /// Creates an empty set of control messages backed by `buffer`.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn new(buffer: &'a mut [u8]) -> SocketAncillary<'a> {
+        SocketAncillary { buffer, length: 0, truncated: false }
+    }
+
+    /// Returns the size of the buffer.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns whether there are no control messages.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the number of bytes of the buffer the control messages take up.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns an iterator over the control messages.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn messages(&self) -> Messages {
+        Messages { buffer: &self.buffer[..self.length] }
+    }
+
+    /// Returns whether control messages were received which didn't fit in the
+    /// buffer, and were lost.
+    ///
+    /// File descriptors which don't fit are closed by the kernel.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Adds a control message sending the file descriptors in `fds`.
+    ///
+    /// Returns `false` if there's no room for it in the buffer, in which
+    /// case nothing is added.
+    ///
+    /// The file descriptors are duplicated into the receiving process, and
+    /// can be closed in this one once the message is sent.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn add_fds(&mut self, fds: &[RawFd]) -> bool {
+        self.add_message(libc::SOL_SOCKET, libc::SCM_RIGHTS, fds)
+    }
+
+    /// Adds a control message sending the credentials in `creds`.
+    ///
+    /// Returns `false` if there's no room for it in the buffer, in which
+    /// case nothing is added.
+    ///
+    /// Unless the sending process is privileged, the kernel only lets it send
+    /// its own process ID and its real, effective or saved user and group
+    /// IDs.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn add_creds(&mut self, creds: &[SocketCred]) -> bool {
+        // `SocketCred` is a plain wrapper around `ucred`
+        let creds = unsafe {
+            ::slice::from_raw_parts(creds.as_ptr() as *const libc::ucred, creds.len())
+        };
+        self.add_message(libc::SOL_SOCKET, libc::SCM_CREDENTIALS, creds)
+    }
+
+    /// Removes all the control messages.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn clear(&mut self) {
+        self.length = 0;
+        self.truncated = false;
+    }
+
+    fn add_message<T>(&mut self, cmsg_level: libc::c_int, cmsg_type: libc::c_int,
+                      data: &[T]) -> bool {
+        // A slice is never near `usize::MAX` bytes long, so none of this overflows
+        let data_len = data.len() * mem::size_of::<T>();
+        let space = cmsg_header_len() + cmsg_align(data_len);
+        let end = match self.length.checked_add(space) {
+            Some(end) if end <= self.buffer.len() => end,
+            _ => return false,
+        };
+
+        let message = &mut self.buffer[self.length..end];
+        for byte in message.iter_mut() {
+            *byte = 0;
+        }
+        let mut header: libc::cmsghdr = unsafe { mem::zeroed() };
+        header.cmsg_len = (cmsg_header_len() + data_len) as _;
+        header.cmsg_level = cmsg_level;
+        header.cmsg_type = cmsg_type;
+        // The buffer isn't necessarily aligned for the header or the data
+        unsafe {
+            ptr::write_unaligned(message.as_mut_ptr() as *mut libc::cmsghdr, header);
+            ptr::copy_nonoverlapping(data.as_ptr() as *const u8,
+                                     message[cmsg_header_len()..].as_mut_ptr(),
+                                     data_len);
+        }
+        self.length = end;
+        true
+    }
+
+    /// Makes the file descriptors received close-on-exec, where `recvmsg`
+    /// can't be told to.
+    fn set_cloexec(&self) {
+        for message in self.messages() {
+            if let Ok(AncillaryData::ScmRights(fds)) = message {
+                for fd in fds {
+                    let fd = FileDesc::new(fd);
+                    let _ = fd.set_cloexec();
+                    fd.into_raw();
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over the control messages of a [`SocketAncillary`].
+///
+/// [`SocketAncillary`]: struct.SocketAncillary.html
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct Messages<'a> {
+    buffer: &'a [u8],
+}
+
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl<'a> Iterator for Messages<'a> {
+    type Item = Result<AncillaryData<'a>, AncillaryError>;
+
+    fn next(&mut self) -> Option<Result<AncillaryData<'a>, AncillaryError>> {
+        if self.buffer.len() < mem::size_of::<libc::cmsghdr>() {
+            return None;
+        }
+        let header = unsafe {
+            ptr::read_unaligned(self.buffer.as_ptr() as *const libc::cmsghdr)
+        };
+        let len = header.cmsg_len as usize;
+        if len < cmsg_header_len() || len > self.buffer.len() {
+            self.buffer = &[];
+            return None;
+        }
+        let data = &self.buffer[cmsg_header_len()..len];
+        // The padding of the last message may be left out
+        let next = cmp::min(cmsg_align(len), self.buffer.len());
+        self.buffer = &self.buffer[next..];
+
+        Some(AncillaryData::from_message(header.cmsg_level, header.cmsg_type, data))
+    }
+}
+
+/// A control message of a [`SocketAncillary`].
+///
+/// [`SocketAncillary`]: struct.SocketAncillary.html
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub enum AncillaryData<'a> {
+    /// File descriptors, sent with `SCM_RIGHTS`.
+    ScmRights(ScmRights<'a>),
+    /// Process credentials, sent with `SCM_CREDENTIALS`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    ScmCredentials(ScmCredentials<'a>),
+}
+
+impl<'a> AncillaryData<'a> {
+    fn from_message(cmsg_level: libc::c_int, cmsg_type: libc::c_int, data: &'a [u8])
+                    -> Result<AncillaryData<'a>, AncillaryError> {
+        match (cmsg_level, cmsg_type) {
+            (libc::SOL_SOCKET, libc::SCM_RIGHTS) => {
+                Ok(AncillaryData::ScmRights(ScmRights(AncillaryDataIter::new(data))))
+            }
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            (libc::SOL_SOCKET, libc::SCM_CREDENTIALS) => {
+                Ok(AncillaryData::ScmCredentials(ScmCredentials(AncillaryDataIter::new(data))))
+            }
+            (cmsg_level, cmsg_type) => Err(AncillaryError::Unknown { cmsg_level, cmsg_type }),
+        }
+    }
+}
+
+/// The error given by [`Messages`] for a control message of a kind it
+/// doesn't know.
+///
+/// [`Messages`]: struct.Messages.html
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub enum AncillaryError {
+    /// A control message with this level and type.
+    Unknown {
+        /// The `cmsg_level` of the message.
+        cmsg_level: i32,
+        /// The `cmsg_type` of the message.
+        cmsg_type: i32,
+    },
+}
+
+/// Reads the values of type `T` a control message is made of, which aren't
+/// necessarily aligned in its buffer.
+struct AncillaryDataIter<'a, T> {
+    data: &'a [u8],
+    _marker: PhantomData<T>,
+}
+
+// Not derived, as `libc::ucred` isn't `Debug`
+impl<'a, T> fmt::Debug for AncillaryDataIter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AncillaryDataIter").field("data", &self.data).finish()
+    }
+}
+
+impl<'a, T> AncillaryDataIter<'a, T> {
+    fn new(data: &'a [u8]) -> AncillaryDataIter<'a, T> {
+        AncillaryDataIter { data, _marker: PhantomData }
+    }
+}
+
+impl<'a, T> Iterator for AncillaryDataIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.data.len() < mem::size_of::<T>() {
+            return None;
+        }
+        let value = unsafe { ptr::read_unaligned(self.data.as_ptr() as *const T) };
+        self.data = &self.data[mem::size_of::<T>()..];
+        Some(value)
+    }
+}
+
+/// An iterator over the file descriptors of an [`AncillaryData::ScmRights`]
+/// control message.
+///
+/// The file descriptors received are owned by the receiving process, which
+/// is responsible for closing them, for instance by wrapping them with
+/// [`FromRawFd`].
+///
+/// [`AncillaryData::ScmRights`]: enum.AncillaryData.html#variant.ScmRights
+/// [`FromRawFd`]: ../io/trait.FromRawFd.html
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct ScmRights<'a>(AncillaryDataIter<'a, RawFd>);
+
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl<'a> Iterator for ScmRights<'a> {
+    type Item = RawFd;
+
+    fn next(&mut self) -> Option<RawFd> {
+        self.0.next()
+    }
+}
+
+/// An iterator over the credentials of an
+/// [`AncillaryData::ScmCredentials`] control message.
+///
+/// [`AncillaryData::ScmCredentials`]: enum.AncillaryData.html#variant.ScmCredentials
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct ScmCredentials<'a>(AncillaryDataIter<'a, libc::ucred>);
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl<'a> Iterator for ScmCredentials<'a> {
+    type Item = SocketCred;
+
+    fn next(&mut self) -> Option<SocketCred> {
+        self.0.next().map(SocketCred)
+    }
+}
+
+/// Process credentials, as sent in an [`AncillaryData::ScmCredentials`]
+/// control message.
+///
+/// [`AncillaryData::ScmCredentials`]: enum.AncillaryData.html#variant.ScmCredentials
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Clone)]
+pub struct SocketCred(libc::ucred);
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl SocketCred {
+    /// Creates credentials with a process, user and group ID of zero.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn new() -> SocketCred {
+        SocketCred(libc::ucred { pid: 0, uid: 0, gid: 0 })
+    }
+
+    /// Sets the process ID.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_pid(&mut self, pid: libc::pid_t) {
+        self.0.pid = pid;
+    }
+
+    /// Returns the process ID.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn pid(&self) -> libc::pid_t {
+        self.0.pid
+    }
+
+    /// Sets the user ID.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_uid(&mut self, uid: libc::uid_t) {
+        self.0.uid = uid;
+    }
+
+    /// Returns the user ID.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn uid(&self) -> libc::uid_t {
+        self.0.uid
+    }
+
+    /// Sets the group ID.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_gid(&mut self, gid: libc::gid_t) {
+        self.0.gid = gid;
+    }
+
+    /// Returns the group ID.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn gid(&self) -> libc::gid_t {
+        self.0.gid
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl fmt::Debug for SocketCred {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SocketCred")
+            .field("pid", &self.0.pid)
+            .field("uid", &self.0.uid)
+            .field("gid", &self.0.gid)
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod test {
+    use thread;
+    use io::{self, IoSlice, IoSliceMut};
+    use io::prelude::*;
+    use time::Duration;
+    use sys_common::io::test::tmpdir;
+
+    use super::*;
+
+    macro_rules! or_panic {
+        ($e:expr) => {
+            match $e {
+                Ok(e) => e,
+                Err(e) => panic!("{}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn basic() {
+        let dir = tmpdir();
+        let socket_path = dir.path().join("sock");
+        let msg1 = b"hello";
+        let msg2 = b"world!";
+
+        let listener = or_panic!(UnixListener::bind(&socket_path));
+        let thread = thread::spawn(move || {
+            let mut stream = or_panic!(listener.accept()).0;
+            let mut buf = [0; 5];
+            or_panic!(stream.read(&mut buf));
+            assert_eq!(&msg1[..], &buf[..]);
+            or_panic!(stream.write_all(msg2));
+        });
+
+        let mut stream = or_panic!(UnixStream::connect(&socket_path));
+        assert_eq!(Some(&*socket_path),
+                   stream.peer_addr().unwrap().as_pathname());
+        or_panic!(stream.write_all(msg1));
+        let mut buf = vec![];
+        or_panic!(stream.read_to_end(&mut buf));
+        assert_eq!(&msg2[..], &buf[..]);
+        drop(stream);
+
+        thread.join().unwrap();
+    }
+
+    #[test]
+    fn pair() {
+        let msg1 = b"hello";
+        let msg2 = b"world!";
+
+        let (mut s1, mut s2) = or_panic!(UnixStream::pair());
+        let thread = thread::spawn(move || {
+            // s1 must be moved in or the test will hang!
+            let mut buf = [0; 5];
+            or_panic!(s1.read(&mut buf));
+            assert_eq!(&msg1[..], &buf[..]);
+            or_panic!(s1.write_all(msg2));
+        });
+
+        or_panic!(s2.write_all(msg1));
+        let mut buf = vec![];
+        or_panic!(s2.read_to_end(&mut buf));
+        assert_eq!(&msg2[..], &buf[..]);
+        drop(s2);
+
+        thread.join().unwrap();
+    }
+
+    #[test]
+    fn vectored() {
+        let (mut s1, mut s2) = or_panic!(UnixStream::pair());
+
+        let len = or_panic!(s1.write_vectored(&[IoSlice::new(b"hello"),
+                                                IoSlice::new(b" "),
+                                                IoSlice::new(b"world!")]));
+        assert_eq!(len, 12);
+
+        let mut buf1 = [0; 6];
+        let mut buf2 = [0; 7];
+        let len = or_panic!(s2.read_vectored(&mut [IoSliceMut::new(&mut buf1),
+                                                   IoSliceMut::new(&mut buf2)]));
+        assert_eq!(len, 12);
+        assert_eq!(&buf1, b"hello ");
+        assert_eq!(&buf2, b"world!\0");
+    }
+
+    #[test]
+    fn try_clone() {
+        let dir = tmpdir();
+        let socket_path = dir.path().join("sock");
+        let msg1 = b"hello";
+        let msg2 = b"world";
 
         let listener = or_panic!(UnixListener::bind(&socket_path));
         let thread = thread::spawn(move || {
@@ -1716,4 +3130,158 @@ mod test {
     fn abstract_namespace_not_allowed() {
         assert!(UnixStream::connect("\0asdf").is_err());
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn socket_builder_reuse_port() {
+        let first = or_panic!(net::SocketBuilder::udp_v4());
+        or_panic!(first.set_reuse_port(true));
+        assert!(or_panic!(first.reuse_port()));
+        or_panic!(first.bind(&"127.0.0.1:0".parse().unwrap()));
+        let addr = or_panic!(first.local_addr());
+
+        let second = or_panic!(net::SocketBuilder::udp_v4());
+        or_panic!(second.set_reuse_port(true));
+        or_panic!(second.bind(&addr));
+        assert_eq!(or_panic!(second.local_addr()), addr);
+    }
+
+    #[test]
+    fn seqpacket() {
+        let dir = tmpdir();
+        let socket_path = dir.path().join("sock");
+
+        let listener = or_panic!(UnixSeqpacketListener::bind(&socket_path));
+        let thread = thread::spawn(move || {
+            let socket = or_panic!(listener.accept()).0;
+            let mut buf = [0; 64];
+            let size = or_panic!(socket.recv(&mut buf));
+            or_panic!(socket.send(&buf[..size]));
+        });
+
+        let socket = or_panic!(UnixSeqpacket::connect(&socket_path));
+        assert_eq!(Some(&*socket_path), socket.peer_addr().unwrap().as_pathname());
+        or_panic!(socket.send(b"hello"));
+        let mut buf = [0; 64];
+        assert_eq!(or_panic!(socket.recv(&mut buf)), 5);
+        assert_eq!(&buf[..5], b"hello");
+        thread.join().unwrap();
+    }
+
+    #[test]
+    fn seqpacket_message_boundaries() {
+        let (s1, s2) = or_panic!(UnixSeqpacket::pair());
+
+        or_panic!(s1.send(b"hello"));
+        or_panic!(s1.send(b"world!"));
+
+        let mut buf = [0; 64];
+        assert_eq!(or_panic!(s2.recv(&mut buf)), 5);
+        assert_eq!(&buf[..5], b"hello");
+        assert_eq!(or_panic!(s2.recv(&mut buf)), 6);
+        assert_eq!(&buf[..6], b"world!");
+    }
+
+    #[test]
+    fn peer_cred() {
+        let (s1, _s2) = or_panic!(UnixStream::pair());
+        let cred = match s1.peer_cred() {
+            Ok(cred) => cred,
+            Err(ref e) if e.kind() == io::ErrorKind::Other => return,
+            Err(e) => panic!("{}", e),
+        };
+
+        unsafe {
+            assert_eq!(cred.uid, libc::geteuid());
+            assert_eq!(cred.gid, libc::getegid());
+            if cfg!(any(target_os = "linux", target_os = "android")) {
+                assert_eq!(cred.pid, Some(libc::getpid()));
+            }
+        }
+    }
+
+    #[test]
+    fn send_recv_fds() {
+        let (s1, s2) = or_panic!(UnixStream::pair());
+        let (mut sent, passed) = or_panic!(UnixStream::pair());
+
+        let mut buffer = [0; 128];
+        let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+        assert!(ancillary.add_fds(&[passed.as_raw_fd()]));
+        assert_eq!(or_panic!(s1.send_vectored_with_ancillary(&[IoSlice::new(b"fd")],
+                                                             &mut ancillary)), 2);
+        drop(passed);
+
+        // Offset the buffer, as nothing makes it aligned
+        let mut buffer = [0; 129];
+        let mut ancillary = SocketAncillary::new(&mut buffer[1..]);
+        let mut buf = [0; 2];
+        assert_eq!(or_panic!(s2.recv_vectored_with_ancillary(&mut [IoSliceMut::new(&mut buf)],
+                                                             &mut ancillary)), 2);
+        assert_eq!(&buf, b"fd");
+        assert!(!ancillary.truncated());
+
+        let mut fds = Vec::new();
+        for message in ancillary.messages() {
+            match message {
+                Ok(AncillaryData::ScmRights(rights)) => fds.extend(rights),
+                other => panic!("unexpected control message: {:?}", other),
+            }
+        }
+        assert_eq!(fds.len(), 1);
+        let mut received = unsafe { UnixStream::from_raw_fd(fds[0]) };
+
+        or_panic!(received.write_all(b"hello"));
+        let mut buf = [0; 5];
+        or_panic!(sent.read_exact(&mut buf));
+        assert_eq!(&buf, b"hello");
+    }
+
+    #[test]
+    fn ancillary_buffer_full() {
+        let mut buffer = [0; 8];
+        let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+        assert!(!ancillary.add_fds(&[0]));
+        assert!(ancillary.is_empty());
+        assert_eq!(ancillary.messages().count(), 0);
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[test]
+    fn send_recv_creds() {
+        let (s1, s2) = or_panic!(UnixDatagram::pair());
+        or_panic!(s2.set_passcred(true));
+        assert!(or_panic!(s2.passcred()));
+
+        let mut cred = SocketCred::new();
+        unsafe {
+            cred.set_pid(libc::getpid());
+            cred.set_uid(libc::getuid());
+            cred.set_gid(libc::getgid());
+        }
+        let mut buffer = [0; 128];
+        let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+        assert!(ancillary.add_creds(&[cred.clone()]));
+        or_panic!(s1.send_vectored_with_ancillary(&[IoSlice::new(b"creds")], &mut ancillary));
+
+        let mut buffer = [0; 128];
+        let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+        let mut buf = [0; 64];
+        let (size, truncated) =
+            or_panic!(s2.recv_vectored_with_ancillary(&mut [IoSliceMut::new(&mut buf)],
+                                                      &mut ancillary));
+        assert_eq!(size, 5);
+        assert!(!truncated);
+
+        let mut received = Vec::new();
+        for message in ancillary.messages() {
+            if let Ok(AncillaryData::ScmCredentials(creds)) = message {
+                received.extend(creds);
+            }
+        }
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].pid(), cred.pid());
+        assert_eq!(received[0].uid(), cred.uid());
+        assert_eq!(received[0].gid(), cred.gid());
+    }
 }
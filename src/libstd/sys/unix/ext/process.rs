@@ -91,6 +91,61 @@ pub trait CommandExt {
     /// cross-platform `spawn` instead.
     #[stable(feature = "process_exec2", since = "1.9.0")]
     fn exec(&mut self) -> io::Error;
+
+    /// Puts the child process into the process group `pgroup`. This
+    /// translates to a `setpgid` call in the child process, and failure in
+    /// that call will cause the spawn to fail.
+    ///
+    /// A `pgroup` of 0 makes the child the leader of a new process group
+    /// whose id is the child's pid, which can then be signalled as a whole
+    /// with [`ChildExt::signal_group`]. Any other value names an existing
+    /// process group in the same session to join.
+    ///
+    /// This has no effect if [`setsid`] is also requested, as the new session
+    /// always comes with a new process group.
+    ///
+    /// [`ChildExt::signal_group`]: trait.ChildExt.html#tymethod.signal_group
+    /// [`setsid`]: #tymethod.setsid
+    #[unstable(feature = "process_group", issue = "0")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Makes the child process the leader of a new session, and of a new
+    /// process group within it, by calling `setsid` in the child process.
+    /// The child is detached from the controlling terminal of the parent.
+    #[unstable(feature = "process_group", issue = "0")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Makes `fd` available in the child process as file descriptor number
+    /// `child_fd`.
+    ///
+    /// The descriptor is owned by this `Command` from here on, and it's
+    /// duplicated into every child spawned from it. Only the duplicate in the
+    /// child has its close-on-exec flag cleared, so it doesn't leak into any
+    /// other process. Mapping the same `child_fd` twice replaces the earlier
+    /// mapping, and mapping one of 0, 1 or 2 overrides what was configured
+    /// through `stdin`, `stdout` or `stderr`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_inherit_fd)]
+    ///
+    /// use std::fs::File;
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let log = File::create("log.txt")?;
+    /// Command::new("sh")
+    ///         .arg("-c")
+    ///         .arg("echo hello >&3")
+    ///         .inherit_fd(3, log)
+    ///         .status()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "process_inherit_fd", issue = "0")]
+    fn inherit_fd<F: IntoRawFd>(&mut self, child_fd: RawFd, fd: F) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -115,6 +170,68 @@ impl CommandExt for process::Command {
     fn exec(&mut self) -> io::Error {
         self.as_inner_mut().exec(sys::process::Stdio::Inherit)
     }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn inherit_fd<F: IntoRawFd>(&mut self, child_fd: RawFd, fd: F) -> &mut process::Command {
+        let fd = sys::fd::FileDesc::new(fd.into_raw_fd());
+        // Keep it from leaking into the children of other commands. This
+        // only fails for a descriptor which isn't open, and spawning reports
+        // that anyway.
+        let _ = fd.set_cloexec();
+        self.as_inner_mut().fd(child_fd, fd);
+        self
+    }
+}
+
+/// Unix-specific extensions to `std::process::Child`
+#[unstable(feature = "process_signal", issue = "0")]
+pub trait ChildExt {
+    /// Sends the signal `signal` to the child process.
+    ///
+    /// Like [`kill`], this returns an `InvalidInput` error if the child has
+    /// already been waited on, since its pid may have been reused by then.
+    /// The signal's effect can be observed through
+    /// [`ExitStatusExt::signal`] once the child is waited on.
+    ///
+    /// [`kill`]: ../../../process/struct.Child.html#method.kill
+    /// [`ExitStatusExt::signal`]: trait.ExitStatusExt.html#tymethod.signal
+    #[unstable(feature = "process_signal", issue = "0")]
+    fn signal(&mut self, signal: i32) -> io::Result<()>;
+
+    /// Sends the signal `signal` to every process in the process group whose
+    /// id is the child's pid.
+    ///
+    /// That group only exists if the child was made its leader, through
+    /// [`CommandExt::process_group`] with 0 or through
+    /// [`CommandExt::setsid`]. It keeps existing after the child exits for
+    /// as long as any other member is alive, so unlike [`signal`] this may
+    /// still be used after the child has been waited on.
+    ///
+    /// [`CommandExt::process_group`]: trait.CommandExt.html#tymethod.process_group
+    /// [`CommandExt::setsid`]: trait.CommandExt.html#tymethod.setsid
+    /// [`signal`]: #tymethod.signal
+    #[unstable(feature = "process_group", issue = "0")]
+    fn signal_group(&mut self, signal: i32) -> io::Result<()>;
+}
+
+#[unstable(feature = "process_signal", issue = "0")]
+impl ChildExt for process::Child {
+    fn signal(&mut self, signal: i32) -> io::Result<()> {
+        self.as_inner_mut().signal(signal)
+    }
+
+    fn signal_group(&mut self, signal: i32) -> io::Result<()> {
+        self.as_inner_mut().signal_group(signal)
+    }
 }
 
 /// Unix-specific extensions to `std::process::ExitStatus`
@@ -12,7 +12,7 @@ use os::unix::prelude::*;
 
 use ffi::{CString, CStr, OsString, OsStr};
 use fmt;
-use io::{self, Error, ErrorKind, SeekFrom};
+use io::{self, Error, ErrorKind, IoSlice, IoSliceMut, SeekFrom};
 use libc::{self, c_int, mode_t};
 use mem;
 use path::{Path, PathBuf};
@@ -496,6 +496,10 @@ impl File {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
         self.0.read_at(buf, offset)
     }
@@ -504,6 +508,10 @@ impl File {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.0.write_at(buf, offset)
     }
@@ -772,6 +780,7 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     let mut writer = File::create(to)?;
     let perm = reader.metadata()?.permissions();
 
+    // On Linux and Android this is done by the kernel, see `sys::kernel_copy`
     let ret = io::copy(&mut reader, &mut writer)?;
     set_permissions(to, perm)?;
     Ok(ret)
@@ -0,0 +1,292 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Copying between file descriptors in the kernel, without going through a
+//! buffer in user space.
+//!
+//! `io::copy` comes here first on Linux and Android. When both its reader and
+//! writer are backed by a file descriptor with nothing buffered in front of
+//! it, like a `File`, a `TcpStream` or the pipes of a child process, the data
+//! is moved with:
+//!
+//! * `copy_file_range` between regular files, which lets the file system
+//!   share the data between them instead of copying it where it can,
+//! * `sendfile` from a regular file to anything else,
+//! * `splice` to or from a pipe.
+//!
+//! Each of them falls back to the next one, and finally to the read/write
+//! loop of `io::copy`, when the kernel doesn't have it or won't use it for
+//! these file descriptors.
+
+use fs::File;
+use io;
+use libc;
+use mem;
+use net::TcpStream;
+use os::unix::io::{AsRawFd, RawFd};
+use os::unix::net::UnixStream;
+use process::{ChildStderr, ChildStdin, ChildStdout};
+use ptr;
+use sync::atomic::{AtomicBool, Ordering};
+use sys::cvt;
+
+#[cfg(target_os = "linux")]
+use libc::{fstat64, off64_t, stat64};
+#[cfg(target_os = "android")]
+use libc::{fstat as fstat64, off64_t, stat as stat64};
+
+/// The most a single system call is asked to copy, which is below what
+/// `sendfile` can do at once anywhere.
+const MAX_CHUNK: usize = 0x4000_0000;
+
+/// Gives the file descriptor a reader or writer reads from or writes to
+/// directly, without any buffer in between, if it has one.
+pub trait CopyFd {
+    fn copy_fd(&self) -> Option<RawFd>;
+}
+
+impl<T: ?Sized> CopyFd for T {
+    default fn copy_fd(&self) -> Option<RawFd> {
+        None
+    }
+}
+
+macro_rules! impl_copy_fd {
+    ($($t:ty),*) => ($(
+        impl CopyFd for $t {
+            fn copy_fd(&self) -> Option<RawFd> {
+                Some(self.as_raw_fd())
+            }
+        }
+
+        impl<'a> CopyFd for &'a $t {
+            fn copy_fd(&self) -> Option<RawFd> {
+                Some(self.as_raw_fd())
+            }
+        }
+    )*)
+}
+
+impl_copy_fd!(File, TcpStream, UnixStream, ChildStdin, ChildStdout, ChildStderr);
+
+/// What came of trying to copy in the kernel.
+pub enum CopyResult {
+    /// The copy is over, whether it reached the end of the reader or failed.
+    Ended(io::Result<u64>),
+    /// The kernel can't copy between these file descriptors, after having
+    /// copied this many bytes. The rest is left to be copied in user space.
+    Fallback(u64),
+}
+
+/// Copies from `reader` to `writer` in the kernel if both have a file
+/// descriptor which allows it.
+pub fn copy_spec<R: ?Sized, W: ?Sized>(reader: &R, writer: &W) -> CopyResult {
+    let (reader, writer) = match (reader.copy_fd(), writer.copy_fd()) {
+        (Some(reader), Some(writer)) => (reader, writer),
+        _ => return CopyResult::Fallback(0),
+    };
+    let (reader_kind, writer_kind) = match (FdKind::of(reader), FdKind::of(writer)) {
+        (Some(reader_kind), Some(writer_kind)) => (reader_kind, writer_kind),
+        _ => return CopyResult::Fallback(0),
+    };
+
+    let mut written = 0;
+    let methods = [
+        (Method::CopyFileRange, reader_kind == FdKind::File && writer_kind == FdKind::File),
+        (Method::Sendfile, reader_kind == FdKind::File),
+        (Method::Splice, reader_kind == FdKind::Pipe || writer_kind == FdKind::Pipe),
+    ];
+    for &(method, applies) in methods.iter() {
+        if !applies {
+            continue;
+        }
+        match copy_with(method, reader, writer) {
+            CopyResult::Ended(result) => return CopyResult::Ended(result.map(|n| written + n)),
+            CopyResult::Fallback(n) => written += n,
+        }
+    }
+    CopyResult::Fallback(written)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum FdKind {
+    File,
+    Pipe,
+    Other,
+}
+
+impl FdKind {
+    fn of(fd: RawFd) -> Option<FdKind> {
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        if unsafe { fstat64(fd, &mut stat) } == -1 {
+            return None;
+        }
+        Some(match stat.st_mode & libc::S_IFMT {
+            libc::S_IFREG => FdKind::File,
+            libc::S_IFIFO => FdKind::Pipe,
+            _ => FdKind::Other,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Method {
+    CopyFileRange,
+    Sendfile,
+    Splice,
+}
+
+// `copy_file_range` came with Linux 4.5, so remember if it's missing to
+// spare the failing system call on every copy.
+static HAS_COPY_FILE_RANGE: AtomicBool = AtomicBool::new(true);
+
+syscall! {
+    fn copy_file_range(fd_in: libc::c_int, off_in: *mut off64_t,
+                       fd_out: libc::c_int, off_out: *mut off64_t,
+                       len: libc::size_t, flags: libc::c_uint) -> libc::ssize_t
+        = libc::SYS_copy_file_range
+}
+
+syscall! {
+    fn sendfile(out_fd: libc::c_int, in_fd: libc::c_int, offset: *mut off64_t,
+                count: libc::size_t) -> libc::ssize_t
+        = libc::SYS_sendfile
+}
+
+syscall! {
+    fn splice(fd_in: libc::c_int, off_in: *mut off64_t,
+              fd_out: libc::c_int, off_out: *mut off64_t,
+              len: libc::size_t, flags: libc::c_uint) -> libc::ssize_t
+        = libc::SYS_splice
+}
+
+/// Copies with `method` until the end of `reader`.
+///
+/// The file offsets are used and moved along like `read` and `write` would,
+/// so that a fallback picks up where the kernel stopped.
+fn copy_with(method: Method, reader: RawFd, writer: RawFd) -> CopyResult {
+    if method == Method::CopyFileRange && !HAS_COPY_FILE_RANGE.load(Ordering::Relaxed) {
+        return CopyResult::Fallback(0);
+    }
+
+    let mut written = 0u64;
+    loop {
+        let result = cvt(unsafe {
+            match method {
+                Method::CopyFileRange => {
+                    copy_file_range(reader, ptr::null_mut(), writer, ptr::null_mut(),
+                                    MAX_CHUNK, 0)
+                }
+                Method::Sendfile => sendfile(writer, reader, ptr::null_mut(), MAX_CHUNK),
+                Method::Splice => {
+                    splice(reader, ptr::null_mut(), writer, ptr::null_mut(), MAX_CHUNK, 0)
+                }
+            }
+        });
+        match result {
+            // Files like the ones in /proc have a size of zero, which is all
+            // the kernel may copy of them, so let `read` find out if there's
+            // more to them
+            Ok(0) if written == 0 => return CopyResult::Fallback(0),
+            Ok(0) => return CopyResult::Ended(Ok(written)),
+            Ok(n) => written += n as u64,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => match e.raw_os_error() {
+                Some(libc::ENOSYS) => {
+                    if method == Method::CopyFileRange {
+                        HAS_COPY_FILE_RANGE.store(false, Ordering::Relaxed);
+                    }
+                    return CopyResult::Fallback(written);
+                }
+                // The files are on different file systems (before Linux 5.3),
+                // the writer is in append mode, the file system doesn't
+                // support it, or a seccomp filter forbids the system call
+                Some(libc::EXDEV) | Some(libc::EBADF) | Some(libc::EINVAL) |
+                Some(libc::EOPNOTSUPP) | Some(libc::EPERM) if written == 0 => {
+                    return CopyResult::Fallback(0);
+                }
+                _ => return CopyResult::Ended(Err(e)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fs::{File, OpenOptions};
+    use io::{self, Read, Seek, SeekFrom, Write};
+    use os::unix::net::UnixStream;
+    use sys_common::io::test::tmpdir;
+
+    fn contents(mut file: &File) -> Vec<u8> {
+        let mut v = Vec::new();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_end(&mut v).unwrap();
+        v
+    }
+
+    #[test]
+    fn copy_between_files_from_offset() {
+        let tmpdir = tmpdir();
+        let data = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+        let mut reader = File::create(tmpdir.join("in")).unwrap();
+        reader.write_all(&data).unwrap();
+        let mut reader = File::open(tmpdir.join("in")).unwrap();
+        reader.seek(SeekFrom::Start(1000)).unwrap();
+        let mut writer = OpenOptions::new().read(true).write(true).create(true)
+                                           .open(tmpdir.join("out")).unwrap();
+        writer.write_all(b"start").unwrap();
+
+        assert_eq!(io::copy(&mut reader, &mut writer).unwrap(), 99_000);
+        assert_eq!(reader.seek(SeekFrom::Current(0)).unwrap(), 100_000);
+        assert_eq!(writer.seek(SeekFrom::Current(0)).unwrap(), 99_005);
+        assert_eq!(&contents(&writer)[..5], b"start");
+        assert_eq!(&contents(&writer)[5..], &data[1000..]);
+    }
+
+    #[test]
+    fn copy_to_append_only_file() {
+        let tmpdir = tmpdir();
+        File::create(tmpdir.join("in")).unwrap().write_all(b"world").unwrap();
+        File::create(tmpdir.join("out")).unwrap().write_all(b"hello ").unwrap();
+        let mut reader = File::open(tmpdir.join("in")).unwrap();
+        let mut writer = OpenOptions::new().append(true).open(tmpdir.join("out")).unwrap();
+
+        assert_eq!(io::copy(&mut reader, &mut writer).unwrap(), 5);
+        assert_eq!(contents(&File::open(tmpdir.join("out")).unwrap()), b"hello world");
+    }
+
+    #[test]
+    fn copy_file_to_socket() {
+        let tmpdir = tmpdir();
+        File::create(tmpdir.join("in")).unwrap().write_all(b"hello").unwrap();
+        let mut reader = File::open(tmpdir.join("in")).unwrap();
+        let (mut a, mut b) = UnixStream::pair().unwrap();
+
+        assert_eq!(io::copy(&mut reader, &mut a).unwrap(), 5);
+        drop(a);
+        let mut v = Vec::new();
+        b.read_to_end(&mut v).unwrap();
+        assert_eq!(v, b"hello");
+    }
+
+    #[test]
+    fn copy_proc_file() {
+        // Its size is zero but it isn't empty
+        let mut reader = match File::open("/proc/self/cmdline") {
+            Ok(file) => file,
+            Err(..) => return,
+        };
+        let tmpdir = tmpdir();
+        let mut writer = File::create(tmpdir.join("out")).unwrap();
+
+        assert!(io::copy(&mut reader, &mut writer).unwrap() > 0);
+    }
+}
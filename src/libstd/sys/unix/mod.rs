@@ -46,6 +46,9 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+pub mod io_slice;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod kernel_copy;
 pub mod memchr;
 pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use ffi::CStr;
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use libc::{self, c_int, c_void, size_t, sockaddr, socklen_t, EAI_SYSTEM, MSG_PEEK};
 use mem;
 use net::{SocketAddr, Shutdown};
@@ -43,6 +43,15 @@ use libc::SO_NOSIGPIPE;
 #[cfg(not(target_vendor = "apple"))]
 const SO_NOSIGPIPE: c_int = 0;
 
+// The options for the idle time before the first keepalive probe and the
+// interval between the next ones, where they can be set for a single socket.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use libc::{TCP_KEEPIDLE as KEEPALIVE_TIME, TCP_KEEPINTVL as KEEPALIVE_INTERVAL};
+#[cfg(target_vendor = "apple")]
+use libc::TCP_KEEPALIVE as KEEPALIVE_TIME;
+#[cfg(target_vendor = "apple")]
+const KEEPALIVE_INTERVAL: c_int = 0x101;
+
 pub struct Socket(FileDesc);
 
 pub fn init() {}
@@ -240,6 +249,10 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, MSG_PEEK)
     }
@@ -272,6 +285,10 @@ impl Socket {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn set_timeout(&self, dur: Option<Duration>, kind: libc::c_int) -> io::Result<()> {
         let timeout = match dur {
             Some(dur) => {
@@ -334,6 +351,28 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>, interval: Duration)
+                         -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_KEEPALIVE, keepalive.is_some() as c_int)?;
+        match keepalive {
+            Some(time) => set_keepalive_times(self, time, interval),
+            None => Ok(()),
+        }
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_KEEPALIVE)?;
+        Ok(raw != 0)
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        keepalive_times(self).map(|(time, _)| time)
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        keepalive_times(self).map(|(_, interval)| interval)
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking = nonblocking as libc::c_int;
         cvt(unsafe { libc::ioctl(*self.as_inner(), libc::FIONBIO, &mut nonblocking) }).map(|_| ())
@@ -349,6 +388,41 @@ impl Socket {
     }
 }
 
+// The idle time before the first keepalive probe and the interval between the
+// next ones can only be set for a single socket on some platforms, and are
+// system-wide settings elsewhere.
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+fn set_keepalive_times(socket: &Socket, time: Duration, interval: Duration) -> io::Result<()> {
+    setsockopt(socket, libc::IPPROTO_TCP, KEEPALIVE_TIME, keepalive_secs(time))?;
+    setsockopt(socket, libc::IPPROTO_TCP, KEEPALIVE_INTERVAL, keepalive_secs(interval))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+fn keepalive_times(socket: &Socket) -> io::Result<(Duration, Duration)> {
+    let time: c_int = getsockopt(socket, libc::IPPROTO_TCP, KEEPALIVE_TIME)?;
+    let interval: c_int = getsockopt(socket, libc::IPPROTO_TCP, KEEPALIVE_INTERVAL)?;
+    Ok((Duration::from_secs(time as u64), Duration::from_secs(interval as u64)))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+fn keepalive_secs(dur: Duration) -> c_int {
+    // Rounded up, as the times are whole seconds and zero is refused
+    let secs = dur.as_secs() + if dur.subsec_nanos() > 0 { 1 } else { 0 };
+    cmp::max(1, cmp::min(secs, c_int::max_value() as u64)) as c_int
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+fn set_keepalive_times(_socket: &Socket, _time: Duration, _interval: Duration)
+                       -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+fn keepalive_times(_socket: &Socket) -> io::Result<(Duration, Duration)> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "keepalive times are system-wide settings on this platform"))
+}
+
 impl AsInner<c_int> for Socket {
     fn as_inner(&self) -> &c_int { self.0.as_inner() }
 }
@@ -15,7 +15,7 @@ use env;
 use ffi::{OsString, OsStr, CString, CStr};
 use fmt;
 use io;
-use libc::{self, c_int, gid_t, uid_t, c_char};
+use libc::{self, c_int, gid_t, pid_t, uid_t, c_char};
 use ptr;
 use sys::fd::FileDesc;
 use sys::fs::{File, OpenOptions};
@@ -52,8 +52,13 @@ pub struct Command {
     cwd: Option<CString>,
     uid: Option<uid_t>,
     gid: Option<gid_t>,
+    pgroup: Option<pid_t>,
+    setsid: bool,
     saw_nul: bool,
     closures: Vec<Box<FnMut() -> io::Result<()> + Send + Sync>>,
+    // The descriptors given to the child besides stdio, with the number each
+    // one gets in the child
+    fds: Vec<(c_int, FileDesc)>,
     stdin: Option<Stdio>,
     stdout: Option<Stdio>,
     stderr: Option<Stdio>,
@@ -101,8 +106,11 @@ impl Command {
             cwd: None,
             uid: None,
             gid: None,
+            pgroup: None,
+            setsid: false,
             saw_nul,
             closures: Vec::new(),
+            fds: Vec::new(),
             stdin: None,
             stdout: None,
             stderr: None,
@@ -192,6 +200,17 @@ impl Command {
     pub fn gid(&mut self, id: gid_t) {
         self.gid = Some(id);
     }
+    pub fn pgroup(&mut self, pgroup: pid_t) {
+        self.pgroup = Some(pgroup);
+    }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    pub fn fd(&mut self, child_fd: c_int, fd: FileDesc) {
+        // A later mapping to the same number replaces the earlier one
+        self.fds.retain(|&(n, _)| n != child_fd);
+        self.fds.push((child_fd, fd));
+    }
 
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
@@ -215,6 +234,18 @@ impl Command {
     pub fn get_gid(&self) -> Option<gid_t> {
         self.gid
     }
+    pub fn get_pgroup(&self) -> Option<pid_t> {
+        self.pgroup
+    }
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+    pub fn get_fds(&self) -> &Vec<(c_int, FileDesc)> {
+        &self.fds
+    }
+    pub fn get_fds_mut(&mut self) -> &mut Vec<(c_int, FileDesc)> {
+        &mut self.fds
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -102,6 +102,18 @@ impl Command {
             zx_cvt(launchpad_clone_fd(launchpad, 2, 2))?;
         }
 
+        // Clone the other inherited descriptors, these stay open in the
+        // parent so the command can be spawned again
+        for &(child_fd, ref fd) in self.get_fds().iter() {
+            zx_cvt(launchpad_clone_fd(launchpad, fd.raw(), child_fd))?;
+        }
+
+        // There are no process groups or sessions on Fuchsia
+        if self.get_pgroup().is_some() || self.get_setsid() {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                                      "process groups are not supported on Fuchsia"));
+        }
+
         // We don't want FileDesc::drop to be called on any stdio. It would close their fds. The
         // fds will be closed once the child process finishes.
         mem::forget(stdio);
@@ -143,6 +155,16 @@ impl Process {
         Ok(())
     }
 
+    pub fn signal(&mut self, _signal: libc::c_int) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "signals are not supported on Fuchsia"))
+    }
+
+    pub fn signal_group(&mut self, _signal: libc::c_int) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "signals are not supported on Fuchsia"))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use default::Default;
         use sys::process::zircon::*;
@@ -8,11 +8,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use cmp;
 use io::{self, Error, ErrorKind};
 use libc::{self, c_int, gid_t, pid_t, uid_t};
 use ptr;
 
-use sys::cvt;
+use sys::{cvt, cvt_r};
+use sys::fd::FileDesc;
 use sys::process::process_common::*;
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -38,7 +40,17 @@ impl Command {
             match cvt(libc::fork())? {
                 0 => {
                     drop(input);
-                    let err = self.do_exec(theirs);
+                    // The descriptors mapped into the child may take the
+                    // number of the error pipe, so move it above all of them
+                    // first and it's still there if the exec fails.
+                    let mut output = output.into_fd();
+                    let err = match dup_above(&output, mapped_fds_end(self.get_fds())) {
+                        Ok(fd) => {
+                            output = fd;
+                            self.do_exec(theirs)
+                        }
+                        Err(e) => e,
+                    };
                     let errno = err.raw_os_error().unwrap_or(libc::EINVAL) as u32;
                     let bytes = [
                         (errno >> 24) as u8,
@@ -58,6 +70,15 @@ impl Command {
             }
         };
 
+        // The child puts itself into its process group as well, but do it
+        // here too so the group is already in place by the time `spawn`
+        // returns and the caller may want to signal it. This can fail if the
+        // child has already exec'd, in which case the child's own call has
+        // taken care of it.
+        if let (Some(pgroup), false) = (self.get_pgroup(), self.get_setsid()) {
+            unsafe { libc::setpgid(pid, pgroup); }
+        }
+
         let mut p = Process { pid: pid, status: None };
         drop(output);
         let mut bytes = [0; 8];
@@ -150,6 +171,17 @@ impl Command {
             })
         }
 
+        // Move the descriptors that are to be inherited above every number
+        // they're going to be mapped to, so neither the stdio setup nor the
+        // mappings themselves can clobber one before it's been put in place.
+        // The moved copies are close-on-exec, the originals are closed when
+        // they get replaced.
+        let min = mapped_fds_end(self.get_fds());
+        for &mut (_, ref mut fd) in self.get_fds_mut().iter_mut() {
+            let new = t!(dup_above(fd, min));
+            *fd = new;
+        }
+
         if let Some(fd) = stdio.stdin.fd() {
             t!(cvt_r(|| libc::dup2(fd, libc::STDIN_FILENO)));
         }
@@ -159,8 +191,16 @@ impl Command {
         if let Some(fd) = stdio.stderr.fd() {
             t!(cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO)));
         }
+        for &(child_fd, ref fd) in self.get_fds().iter() {
+            t!(cvt_r(|| libc::dup2(fd.raw(), child_fd)));
+        }
 
         if cfg!(not(any(target_os = "l4re"))) {
+            if self.get_setsid() {
+                t!(cvt(libc::setsid()));
+            } else if let Some(pgroup) = self.get_pgroup() {
+                t!(cvt(libc::setpgid(0, pgroup)));
+            }
             if let Some(u) = self.get_gid() {
                 t!(cvt(libc::setgid(u as gid_t)));
             }
@@ -223,6 +263,21 @@ impl Command {
     }
 }
 
+// The lowest number above stdio and every number a descriptor is mapped to in
+// the child.
+fn mapped_fds_end(fds: &[(c_int, FileDesc)]) -> c_int {
+    fds.iter().fold(libc::STDERR_FILENO, |m, &(n, _)| cmp::max(m, n)) + 1
+}
+
+// Duplicates `fd` to the lowest free number from `min` up, close-on-exec. The
+// original is left open. Like `do_exec`, this doesn't allocate.
+fn dup_above(fd: &FileDesc, min: c_int) -> io::Result<FileDesc> {
+    let new = cvt_r(|| unsafe { libc::fcntl(fd.raw(), libc::F_DUPFD, min) })?;
+    let new = FileDesc::new(new);
+    new.set_cloexec()?;
+    Ok(new)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Processes
 ////////////////////////////////////////////////////////////////////////////////
@@ -250,6 +305,23 @@ impl Process {
         }
     }
 
+    pub fn signal(&mut self, signal: c_int) -> io::Result<()> {
+        // Same as `kill`, the pid may have been recycled once we've waited.
+        if self.status.is_some() {
+            Err(Error::new(ErrorKind::InvalidInput,
+                           "invalid argument: can't signal an exited process"))
+        } else {
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(|_| ())
+        }
+    }
+
+    pub fn signal_group(&mut self, signal: c_int) -> io::Result<()> {
+        // The group outlives its leader as long as any member is still
+        // around, and its id can't be reused until then, so this is fine to
+        // do even after the child itself has been waited on.
+        cvt(unsafe { libc::kill(-self.pid, signal) }).map(|_| ())
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use sys::cvt_r;
         if let Some(status) = self.status {
@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use libc;
 use sys::fd::FileDesc;
 
@@ -25,6 +25,13 @@ impl Stdin {
         fd.into_raw();
         ret
     }
+
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let fd = FileDesc::new(libc::STDIN_FILENO);
+        let ret = fd.read_vectored(bufs);
+        fd.into_raw();
+        ret
+    }
 }
 
 impl Stdout {
@@ -37,6 +44,13 @@ impl Stdout {
         ret
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let fd = FileDesc::new(libc::STDOUT_FILENO);
+        let ret = fd.write_vectored(bufs);
+        fd.into_raw();
+        ret
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -52,6 +66,13 @@ impl Stderr {
         ret
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let fd = FileDesc::new(libc::STDERR_FILENO);
+        let ret = fd.write_vectored(bufs);
+        fd.into_raw();
+        ret
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -40,6 +40,27 @@ macro_rules! weak {
     )
 }
 
+/// Declares a function making the system call `$nr`, going through the libc
+/// function of the same name when libc has it.
+///
+/// Newer system calls may be missing from the libc we're linked to even
+/// though the kernel has them, so the call is made directly in that case. A
+/// kernel which doesn't have it fails with `ENOSYS`, like libc would.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+macro_rules! syscall {
+    (fn $name:ident($($arg_name:ident: $t:ty),*) -> $ret:ty = $nr:expr) => (
+        unsafe fn $name($($arg_name: $t),*) -> $ret {
+            weak! { fn $name($($t),*) -> $ret }
+
+            if let Some(fun) = $name.get() {
+                fun($($arg_name),*)
+            } else {
+                ::libc::syscall($nr, $($arg_name),*) as $ret
+            }
+        }
+    )
+}
+
 pub struct Weak<F> {
     name: &'static str,
     addr: AtomicUsize,
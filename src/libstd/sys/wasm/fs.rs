@@ -11,7 +11,7 @@
 use ffi::OsString;
 use fmt;
 use hash::{Hash, Hasher};
-use io::{self, SeekFrom};
+use io::{self, IoSlice, IoSliceMut, SeekFrom};
 use path::{Path, PathBuf};
 use sys::time::SystemTime;
 use sys::{unsupported, Void};
@@ -210,10 +210,18 @@ impl File {
         match self.0 {}
     }
 
+    pub fn read_vectored(&self, _: &mut [IoSliceMut]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn write(&self, _buf: &[u8]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn write_vectored(&self, _: &[IoSlice]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         match self.0 {}
     }
@@ -44,6 +44,7 @@ pub mod cmath;
 pub mod condvar;
 pub mod env;
 pub mod fs;
+pub mod io_slice;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use fmt;
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
 use time::Duration;
 use sys::{unsupported, Void};
@@ -49,10 +49,18 @@ impl TcpStream {
         match self.0 {}
     }
 
+    pub fn read_vectored(&self, _: &mut [IoSliceMut]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn write(&self, _: &[u8]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn write_vectored(&self, _: &[IoSlice]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         match self.0 {}
     }
@@ -225,6 +233,22 @@ impl UdpSocket {
         match self.0 {}
     }
 
+    pub fn set_multicast_if_v4(&self, _: &Ipv4Addr) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        match self.0 {}
+    }
+
+    pub fn set_multicast_if_v6(&self, _: u32) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        match self.0 {}
+    }
+
     pub fn join_multicast_v4(&self, _: &Ipv4Addr, _: &Ipv4Addr)
                          -> io::Result<()> {
         match self.0 {}
@@ -284,6 +308,104 @@ impl fmt::Debug for UdpSocket {
     }
 }
 
+pub struct SocketBuilder(Void);
+
+impl SocketBuilder {
+    pub fn tcp(_: bool) -> io::Result<SocketBuilder> {
+        unsupported()
+    }
+
+    pub fn udp(_: bool) -> io::Result<SocketBuilder> {
+        unsupported()
+    }
+
+    pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_keepalive(&self, _: Option<Duration>, _: Duration) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        match self.0 {}
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        match self.0 {}
+    }
+
+    pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        match self.0 {}
+    }
+
+    pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        match self.0 {}
+    }
+
+    pub fn listen(self, _: i32) -> io::Result<TcpListener> {
+        match self.0 {}
+    }
+
+    pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+        match self.0 {}
+    }
+
+    pub fn into_udp_socket(self) -> io::Result<UdpSocket> {
+        match self.0 {}
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
 pub struct LookupHost(Void);
 
 impl Iterator for LookupHost {
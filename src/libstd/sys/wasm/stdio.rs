@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use sys::{Void, unsupported};
 
 pub struct Stdin(Void);
@@ -23,6 +23,10 @@ impl Stdin {
     pub fn read(&self, _data: &mut [u8]) -> io::Result<usize> {
         match self.0 {}
     }
+
+    pub fn read_vectored(&self, _: &mut [IoSliceMut]) -> io::Result<usize> {
+        match self.0 {}
+    }
 }
 
 impl Stdout {
@@ -47,6 +51,10 @@ impl Stdout {
         Ok(data.len())
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|data| self.write(data), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -71,6 +79,10 @@ impl Stderr {
         Ok(data.len())
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|data| self.write(data), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -207,3 +207,17 @@ impl IntoRawSocket for net::UdpSocket {
         self.into_inner().into_socket().into_inner()
     }
 }
+
+#[unstable(feature = "socket_builder", issue = "0")]
+impl AsRawSocket for net::SocketBuilder {
+    fn as_raw_socket(&self) -> RawSocket {
+        *self.as_inner().socket().as_inner()
+    }
+}
+
+#[unstable(feature = "socket_builder", issue = "0")]
+impl IntoRawSocket for net::SocketBuilder {
+    fn into_raw_socket(self) -> RawSocket {
+        self.into_inner().into_socket().into_inner()
+    }
+}
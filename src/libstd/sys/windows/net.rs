@@ -11,7 +11,7 @@
 #![unstable(issue = "0", feature = "windows_net")]
 
 use cmp;
-use io::{self, Read};
+use io::{self, IoSlice, IoSliceMut, Read};
 use libc::{c_int, c_void, c_ulong, c_long};
 use mem;
 use net::{SocketAddr, Shutdown};
@@ -104,6 +104,10 @@ impl Socket {
             SocketAddr::V4(..) => c::AF_INET,
             SocketAddr::V6(..) => c::AF_INET6,
         };
+        Socket::new_raw(fam, ty)
+    }
+
+    pub fn new_raw(fam: c_int, ty: c_int) -> io::Result<Socket> {
         let socket = unsafe {
             match c::WSASocketW(fam, ty, 0, ptr::null_mut(), 0,
                                 c::WSA_FLAG_OVERLAPPED) {
@@ -217,6 +221,20 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::default_read_vectored(|buf| self.read(buf), bufs)
+    }
+
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|buf| {
+            let len = cmp::min(buf.len(), i32::max_value() as usize) as i32;
+            let ret = cvt(unsafe {
+                c::send(self.0, buf.as_ptr() as *const c_void, len, 0)
+            })?;
+            Ok(ret as usize)
+        }, bufs)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, c::MSG_PEEK)
     }
@@ -316,6 +334,44 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>, interval: Duration)
+                         -> io::Result<()> {
+        // The idle time before the first probe and the interval between the
+        // next ones are given along with turning keepalive on or off
+        let ms = |dur| cmp::max(1, sys::dur2timeout(dur));
+        let keepalive = c::tcp_keepalive {
+            onoff: keepalive.is_some() as c_ulong,
+            keepalivetime: keepalive.map(&ms).unwrap_or(0),
+            keepaliveinterval: keepalive.map(|_| ms(interval)).unwrap_or(0),
+        };
+        let mut returned = 0;
+        cvt(unsafe {
+            c::WSAIoctl(self.0, c::SIO_KEEPALIVE_VALS,
+                        &keepalive as *const _ as *mut _,
+                        mem::size_of_val(&keepalive) as c::DWORD,
+                        ptr::null_mut(), 0, &mut returned,
+                        ptr::null_mut(), ptr::null_mut())
+        })?;
+        Ok(())
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        let raw: c::BOOL = net::getsockopt(self, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        Ok(raw != 0)
+    }
+
+    // These options can only be read since Windows 10 version 1709, and are
+    // in seconds
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        let raw: c::DWORD = net::getsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPIDLE)?;
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        let raw: c::DWORD = net::getsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPINTVL)?;
+        Ok(Duration::from_secs(raw as u64))
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = net::getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)?;
         if raw == 0 {
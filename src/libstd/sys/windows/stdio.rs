@@ -13,7 +13,7 @@
 use io::prelude::*;
 
 use cmp;
-use io::{self, Cursor};
+use io::{self, Cursor, IoSlice, IoSliceMut};
 use ptr;
 use str;
 use sync::Mutex;
@@ -139,6 +139,10 @@ impl Stdin {
         utf8.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::default_read_vectored(|buf| self.read(buf), bufs)
+    }
+
     pub fn read_to_end(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
         let mut me = self;
         (&mut me).read_to_end(buf)
@@ -161,6 +165,10 @@ impl Stdout {
         write(c::STD_OUTPUT_HANDLE, data)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|data| self.write(data), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -175,6 +183,10 @@ impl Stderr {
         write(c::STD_ERROR_HANDLE, data)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|data| self.write(data), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -11,7 +11,7 @@
 use cmp;
 use ffi::CString;
 use fmt;
-use io::{self, Error, ErrorKind};
+use io::{self, Error, ErrorKind, IoSlice, IoSliceMut};
 use libc::{c_int, c_void};
 use mem;
 use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
@@ -53,6 +53,12 @@ use libc::MSG_NOSIGNAL;
               target_os = "haiku", target_os = "bitrig")))]
 const MSG_NOSIGNAL: c_int = 0x0;
 
+// `SO_LINGER` is in clock ticks on macOS and iOS, `SO_LINGER_SEC` in seconds
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+const SO_LINGER: c_int = 0x1080;
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+use sys::net::netc::SO_LINGER;
+
 ////////////////////////////////////////////////////////////////////////////////
 // sockaddr and misc bindings
 ////////////////////////////////////////////////////////////////////////////////
@@ -245,6 +251,10 @@ impl TcpStream {
         self.inner.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         let len = cmp::min(buf.len(), <wrlen_t>::max_value() as usize) as wrlen_t;
         let ret = cvt(unsafe {
@@ -256,6 +266,10 @@ impl TcpStream {
         Ok(ret as usize)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         sockname(|buf, len| unsafe {
             c::getpeername(*self.inner.as_inner(), buf, len)
@@ -530,6 +544,24 @@ impl UdpSocket {
         Ok(raw != 0)
     }
 
+    pub fn set_multicast_if_v4(&self, interface: &Ipv4Addr) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IP, c::IP_MULTICAST_IF, *interface.as_inner())
+    }
+
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        let raw: c::in_addr = getsockopt(&self.inner, c::IPPROTO_IP, c::IP_MULTICAST_IF)?;
+        Ok(Ipv4Addr::from_inner(raw))
+    }
+
+    pub fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_MULTICAST_IF, interface as c_int)
+    }
+
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_MULTICAST_IF)?;
+        Ok(raw as u32)
+    }
+
     pub fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr)
                          -> io::Result<()> {
         let mreq = c::ip_mreq {
@@ -628,6 +660,172 @@ impl fmt::Debug for UdpSocket {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Socket builder
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct SocketBuilder {
+    inner: Socket,
+    // `SOCK_STREAM` or `SOCK_DGRAM`, checked before the socket is turned into
+    // a TCP or UDP one
+    ty: c_int,
+}
+
+impl SocketBuilder {
+    pub fn tcp(v6: bool) -> io::Result<SocketBuilder> {
+        SocketBuilder::new(v6, c::SOCK_STREAM)
+    }
+
+    pub fn udp(v6: bool) -> io::Result<SocketBuilder> {
+        SocketBuilder::new(v6, c::SOCK_DGRAM)
+    }
+
+    fn new(v6: bool, ty: c_int) -> io::Result<SocketBuilder> {
+        init();
+
+        let fam = if v6 { c::AF_INET6 } else { c::AF_INET };
+        Ok(SocketBuilder { inner: Socket::new_raw(fam, ty)?, ty })
+    }
+
+    fn check_type(&self, ty: c_int, msg: &'static str) -> io::Result<()> {
+        if self.ty == ty {
+            Ok(())
+        } else {
+            Err(io::Error::new(ErrorKind::InvalidInput, msg))
+        }
+    }
+
+    pub fn socket(&self) -> &Socket { &self.inner }
+
+    pub fn into_socket(self) -> Socket { self.inner }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_keepalive(&self, keepalive: Option<Duration>, interval: Duration)
+                         -> io::Result<()> {
+        self.inner.set_keepalive(keepalive, interval)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.inner.keepalive()
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        self.inner.keepalive_time()
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.inner.keepalive_interval()
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        // Rounded up, as a zero linger time resets the connection, and
+        // Windows takes at most a `u_short` of seconds
+        let secs = linger.map(|dur| {
+            let secs = dur.as_secs() + if dur.subsec_nanos() > 0 { 1 } else { 0 };
+            cmp::min(secs, u16::max_value() as u64)
+        });
+        let linger = c::linger {
+            l_onoff: linger.is_some() as _,
+            l_linger: secs.unwrap_or(0) as _,
+        };
+        setsockopt(&self.inner, c::SOL_SOCKET, SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        let raw: c::linger = getsockopt(&self.inner, c::SOL_SOCKET, SO_LINGER)?;
+        if raw.l_onoff != 0 {
+            Ok(Some(Duration::from_secs(raw.l_linger as u64)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addrp, len) = addr.into_inner();
+        cvt(unsafe { c::bind(*self.inner.as_inner(), addrp, len as _) })?;
+        Ok(())
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe {
+            c::getsockname(*self.inner.as_inner(), buf, len)
+        })
+    }
+
+    pub fn listen(self, backlog: i32) -> io::Result<TcpListener> {
+        self.check_type(c::SOCK_STREAM, "cannot listen on a UDP socket")?;
+        cvt(unsafe { c::listen(*self.inner.as_inner(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.check_type(c::SOCK_STREAM, "cannot connect a UDP socket as a TCP stream")?;
+        let (addrp, len) = addr.into_inner();
+        cvt_r(|| unsafe { c::connect(*self.inner.as_inner(), addrp, len) })?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn into_udp_socket(self) -> io::Result<UdpSocket> {
+        self.check_type(c::SOCK_DGRAM, "cannot turn a TCP socket into a UDP socket")?;
+        Ok(UdpSocket { inner: self.inner })
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut res = f.debug_struct("SocketBuilder");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) {"socket"} else {"fd"};
+        res.field(name, &self.inner.as_inner())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
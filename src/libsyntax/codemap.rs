@@ -131,6 +131,9 @@ pub struct CodeMap {
     // -Zremap-path-prefix to all FileMaps allocated within this CodeMap.
     path_mapping: FilePathMapping,
     stable_id_to_filemap: RefCell<FxHashMap<StableFilemapId, Rc<FileMap>>>,
+    /// In case we are in a doctest, the file it is compiled as and the offset
+    /// of its lines from the lines of the doc comment it comes from
+    doctest_offset: Option<(FileName, isize)>,
 }
 
 impl CodeMap {
@@ -140,6 +143,17 @@ impl CodeMap {
             file_loader: Box::new(RealFileLoader),
             path_mapping,
             stable_id_to_filemap: RefCell::new(FxHashMap()),
+            doctest_offset: None,
+        }
+    }
+
+    /// A codemap for compiling a doctest, whose lines are reported as the
+    /// lines of the doc comment they were taken from.
+    pub fn new_doctest(path_mapping: FilePathMapping,
+                       file: FileName, line: isize) -> CodeMap {
+        CodeMap {
+            doctest_offset: Some((file, line)),
+            ..CodeMap::new(path_mapping)
         }
     }
 
@@ -151,6 +165,7 @@ impl CodeMap {
             file_loader,
             path_mapping,
             stable_id_to_filemap: RefCell::new(FxHashMap()),
+            doctest_offset: None,
         }
     }
 
@@ -664,6 +679,14 @@ impl CodeMapper for CodeMap {
         }
         sp
     }
+    fn doctest_offset_line(&self, file: &FileName, line: usize) -> usize {
+        match self.doctest_offset {
+            Some((ref doctest_file, offset)) if doctest_file == file => {
+                (line as isize + offset) as usize
+            }
+            _ => line,
+        }
+    }
     fn ensure_filemap_source_present(&self, file_map: Rc<FileMap>) -> bool {
         file_map.add_external_src(
             || match file_map.name {
@@ -435,6 +435,12 @@ declare_features! (
 
     // Resolve absolute paths as paths from other crates
     (active, extern_absolute_paths, "1.24.0", Some(44660)),
+
+    // Allows a test to set its own timeout with #[test_timeout = "seconds"]
+    (active, test_timeout, "1.24.0", None),
+
+    // #[test_case(...)] and #[test_fixture(...)]
+    (active, parameterized_tests, "1.24.0", None),
 );
 
 declare_features! (
@@ -939,6 +945,21 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                  "allow_fail attribute is currently unstable",
                                  cfg_fn!(allow_fail))),
 
+    ("test_timeout", Normal, Gated(Stability::Unstable,
+                                   "test_timeout",
+                                   "the test_timeout attribute is currently unstable",
+                                   cfg_fn!(test_timeout))),
+
+    ("test_case", Normal, Gated(Stability::Unstable,
+                                "parameterized_tests",
+                                "the test_case attribute is currently unstable",
+                                cfg_fn!(parameterized_tests))),
+
+    ("test_fixture", Normal, Gated(Stability::Unstable,
+                                   "parameterized_tests",
+                                   "the test_fixture attribute is currently unstable",
+                                   cfg_fn!(parameterized_tests))),
+
     ("rustc_std_internal_symbol", Whitelisted, Gated(Stability::Unstable,
                                      "rustc_attrs",
                                      "this is an internal attribute that will \
@@ -1331,6 +1352,11 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
             return
         }
 
+        // The arguments of these are expressions and paths rather than meta items
+        if attr.check_name("test_case") || attr.check_name("test_fixture") {
+            return
+        }
+
         let meta = panictry!(attr.parse_meta(self.context.parse_sess));
         if contains_novel_literal(&meta) {
             gate_feature_post!(&self, attr_literals, attr.span,
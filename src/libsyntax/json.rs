@@ -23,6 +23,7 @@ use codemap::{CodeMap, FilePathMapping};
 use syntax_pos::{self, MacroBacktrace, Span, SpanLabel, MultiSpan};
 use errors::registry::Registry;
 use errors::{DiagnosticBuilder, SubDiagnostic, CodeSuggestion, CodeMapper};
+use errors::Applicability;
 use errors::DiagnosticId;
 use errors::emitter::{Emitter, EmitterWriter};
 
@@ -121,6 +122,12 @@ struct DiagnosticSpan {
     /// If we are suggesting a replacement, this will contain text
     /// that should be sliced in atop this span.
     suggested_replacement: Option<String>,
+    /// How confident we are in the suggested replacement, if there is one:
+    /// `MachineApplicable` if it can be applied automatically,
+    /// `HasPlaceholders` if it needs to be filled in by the user,
+    /// `MaybeIncorrect` if it's valid code but may not be what the user
+    /// intended, or `Unspecified` if nobody said.
+    suggestion_applicability: Option<Applicability>,
     /// Macro invocations that created the code at this span, if any.
     expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
 }
@@ -220,7 +227,7 @@ impl Diagnostic {
 
 impl DiagnosticSpan {
     fn from_span_label(span: SpanLabel,
-                       suggestion: Option<&String>,
+                       suggestion: Option<(&String, Applicability)>,
                        je: &JsonEmitter)
                        -> DiagnosticSpan {
         Self::from_span_etc(span.span,
@@ -233,7 +240,7 @@ impl DiagnosticSpan {
     fn from_span_etc(span: Span,
                      is_primary: bool,
                      label: Option<String>,
-                     suggestion: Option<&String>,
+                     suggestion: Option<(&String, Applicability)>,
                      je: &JsonEmitter)
                      -> DiagnosticSpan {
         // obtain the full backtrace from the `macro_backtrace`
@@ -253,7 +260,7 @@ impl DiagnosticSpan {
     fn from_span_full(span: Span,
                       is_primary: bool,
                       label: Option<String>,
-                      suggestion: Option<&String>,
+                      suggestion: Option<(&String, Applicability)>,
                       mut backtrace: vec::IntoIter<MacroBacktrace>,
                       je: &JsonEmitter)
                       -> DiagnosticSpan {
@@ -285,13 +292,14 @@ impl DiagnosticSpan {
             file_name: start.file.name.to_string(),
             byte_start: span.lo().0 - start.file.start_pos.0,
             byte_end: span.hi().0 - start.file.start_pos.0,
-            line_start: start.line,
-            line_end: end.line,
+            line_start: je.cm.doctest_offset_line(&start.file.name, start.line),
+            line_end: je.cm.doctest_offset_line(&end.file.name, end.line),
             column_start: start.col.0 + 1,
             column_end: end.col.0 + 1,
             is_primary,
             text: DiagnosticSpanLine::from_span(span, je),
-            suggested_replacement: suggestion.cloned(),
+            suggested_replacement: suggestion.map(|x| x.0.clone()),
+            suggestion_applicability: suggestion.map(|x| x.1),
             expansion: backtrace_step,
             label,
         }
@@ -304,9 +312,9 @@ impl DiagnosticSpan {
            .collect()
     }
 
-    fn from_suggestion(suggestion: &CodeSuggestion, je: &JsonEmitter)
+    fn from_suggestion(sugg: &CodeSuggestion, je: &JsonEmitter)
                        -> Vec<DiagnosticSpan> {
-        suggestion.substitutions
+        sugg.substitutions
                       .iter()
                       .flat_map(|substitution| {
                           substitution.parts.iter().map(move |suggestion| {
@@ -315,9 +323,10 @@ impl DiagnosticSpan {
                                   is_primary: true,
                                   label: None,
                               };
-                              DiagnosticSpan::from_span_label(span_label,
-                                                              Some(&suggestion.snippet),
-                                                              je)
+                              DiagnosticSpan::from_span_label(
+                                  span_label,
+                                  Some((&suggestion.snippet, sugg.applicability)),
+                                  je)
                           })
                       })
                       .collect()
@@ -35,24 +35,29 @@ use fold::Folder;
 use util::move_map::MoveMap;
 use fold;
 use parse::{token, ParseSess};
+use parse::parser::PathStyle;
 use print::pprust;
 use ast::{self, Ident};
 use ptr::P;
 use symbol::{self, Symbol, keywords};
 use util::small_vector::SmallVector;
 
+#[derive(Clone)]
 enum ShouldPanic {
     No,
     Yes(Option<Symbol>),
 }
 
+#[derive(Clone)]
 struct Test {
     span: Span,
     path: Vec<Ident> ,
+    name: String,
     bench: bool,
     ignore: bool,
     should_panic: ShouldPanic,
     allow_fail: bool,
+    timeout: Option<u64>,
 }
 
 struct TestCtxt<'a> {
@@ -93,6 +98,8 @@ pub fn modify_for_testing(sess: &ParseSess,
 struct TestHarnessGenerator<'a> {
     cx: TestCtxt<'a>,
     tests: Vec<Ident>,
+    // the module whose items are being folded
+    current_mod: ast::NodeId,
 
     // submodule name, gensym'd identifier for re-exports
     tested_submods: Vec<(Ident, Ident)>,
@@ -119,6 +126,7 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
         }
         debug!("current path: {}", path_name_i(&self.cx.path));
 
+        let mut test_cases = Vec::new();
         if is_test_fn(&self.cx, &i) || is_bench_fn(&self.cx, &i) {
             match i.node {
                 ast::ItemKind::Fn(_, ast::Unsafety::Unsafe, _, _, _, _) => {
@@ -130,13 +138,33 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
                     let test = Test {
                         span: i.span,
                         path: self.cx.path.clone(),
+                        name: path_name_i(&self.cx.path),
                         bench: is_bench_fn(&self.cx, &i),
                         ignore: is_ignored(&i),
                         should_panic: should_panic(&i, &self.cx),
                         allow_fail: is_allowed_fail(&i),
+                        timeout: test_timeout(&i, &self.cx),
                     };
-                    self.cx.testfns.push(test);
-                    self.tests.push(i.ident);
+                    if takes_test_args(&i) && !test.bench {
+                        // Only the functions calling the test with its
+                        // arguments are run, once per case.
+                        test_cases = mk_test_cases(&mut self.cx, &i, self.current_mod);
+                        let mut path = self.cx.path.clone();
+                        path.pop();
+                        for &(ref name, ref case) in &test_cases {
+                            let mut case_path = path.clone();
+                            case_path.push(case.ident);
+                            self.cx.testfns.push(Test {
+                                path: case_path,
+                                name: name.clone(),
+                                ..test.clone()
+                            });
+                            self.tests.push(case.ident);
+                        }
+                    } else {
+                        self.cx.testfns.push(test);
+                        self.tests.push(i.ident);
+                    }
                 }
             }
         }
@@ -147,9 +175,11 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
         if let ast::ItemKind::Mod(module) = item.node {
             let tests = mem::replace(&mut self.tests, Vec::new());
             let tested_submods = mem::replace(&mut self.tested_submods, Vec::new());
+            let parent_mod = mem::replace(&mut self.current_mod, item.id);
             let mut mod_folded = fold::noop_fold_mod(module, self);
             let tests = mem::replace(&mut self.tests, tests);
             let tested_submods = mem::replace(&mut self.tested_submods, tested_submods);
+            self.current_mod = parent_mod;
 
             if !tests.is_empty() || !tested_submods.is_empty() {
                 let (it, sym) = mk_reexport_mod(&mut self.cx, item.id, tests, tested_submods);
@@ -167,7 +197,13 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
         if ident.name != keywords::Invalid.name() {
             self.cx.path.pop();
         }
-        SmallVector::one(P(item))
+        if test_cases.is_empty() {
+            SmallVector::one(P(item))
+        } else {
+            let mut items = vec![P(item)];
+            items.extend(test_cases.into_iter().map(|(_, case)| case));
+            SmallVector::many(items)
+        }
     }
 
     fn fold_mac(&mut self, mac: ast::Mac) -> ast::Mac { mac }
@@ -297,6 +333,7 @@ fn generate_test_harness(sess: &ParseSess,
     TestHarnessGenerator {
         cx,
         tests: Vec::new(),
+        current_mod: ast::CRATE_NODE_ID,
         tested_submods: Vec::new(),
     }.fold_crate(krate)
 }
@@ -319,6 +356,7 @@ fn is_test_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
     let has_test_attr = attr::contains_name(&i.attrs, "test");
 
     fn has_test_signature(i: &ast::Item) -> HasTestSignature {
+        let takes_args = takes_test_args(i);
         match i.node {
           ast::ItemKind::Fn(ref decl, _, _, _, ref generics, _) => {
             let no_output = match decl.output {
@@ -326,7 +364,7 @@ fn is_test_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
                 ast::FunctionRetTy::Ty(ref t) if t.node == ast::TyKind::Tup(vec![]) => true,
                 _ => false
             };
-            if decl.inputs.is_empty()
+            if (decl.inputs.is_empty() || takes_args)
                    && no_output
                    && !generics.is_parameterized() {
                 Yes
@@ -382,6 +420,95 @@ fn is_bench_fn(cx: &TestCtxt, i: &ast::Item) -> bool {
     has_bench_attr && has_test_signature(i)
 }
 
+// Whether the test gets its arguments from `#[test_case(...)]` or
+// `#[test_fixture(...)]` attributes.
+fn takes_test_args(i: &ast::Item) -> bool {
+    i.attrs.iter().any(|attr| attr.check_name("test_case") || attr.check_name("test_fixture"))
+}
+
+// Creates a function for every `#[test_case(args...)]` of a test, which calls
+// the test with the value returned by its `#[test_fixture(setup)]` function, if
+// any, followed by the arguments of the case. The fixture is dropped once the
+// test returns, which is where any teardown happens.
+//
+// Returns the wrapper functions together with the names of the tests they run:
+// the name of the test followed by `[case_N]`.
+fn mk_test_cases(cx: &mut TestCtxt, i: &ast::Item, parent: ast::NodeId)
+                 -> Vec<(String, P<ast::Item>)> {
+    let sd = cx.span_diagnostic;
+    let sess = cx.ext_cx.parse_sess;
+
+    let fixture = match i.attrs.iter().find(|attr| attr.check_name("test_fixture")) {
+        Some(attr) => match attr.parse_list(sess, |parser| parser.parse_path(PathStyle::Expr)) {
+            Ok(mut paths) => {
+                if paths.len() != 1 {
+                    sd.span_err(attr.span(),
+                                "attribute must be of the form: `#[test_fixture(setup_fn)]`");
+                    return Vec::new();
+                }
+                paths.pop()
+            }
+            Err(mut e) => {
+                e.emit();
+                return Vec::new();
+            }
+        },
+        None => None,
+    };
+
+    let mut cases = Vec::new();
+    for attr in i.attrs.iter().filter(|attr| attr.check_name("test_case")) {
+        match attr.parse_list(sess, |parser| parser.parse_expr()) {
+            Ok(args) => cases.push((attr.span(), args)),
+            Err(mut e) => {
+                e.emit();
+                return Vec::new();
+            }
+        }
+    }
+    // A test with a fixture but no cases is run once, under its own name
+    let has_cases = !cases.is_empty();
+    if !has_cases {
+        cases.push((i.span, Vec::new()));
+    }
+
+    let input_cnt = match i.node {
+        ast::ItemKind::Fn(ref decl, ..) => decl.inputs.len(),
+        _ => 0,
+    };
+    let name = path_name_i(&cx.path);
+
+    let mut wrappers = Vec::new();
+    for (n, (span, mut args)) in cases.into_iter().enumerate() {
+        if let Some(ref setup) = fixture {
+            let setup_call = cx.ext_cx.expr_call(setup.span,
+                                                 cx.ext_cx.expr_path(setup.clone()),
+                                                 vec![]);
+            args.insert(0, setup_call);
+        }
+        if args.len() != input_cnt {
+            sd.span_err(span, &format!("this test case supplies {} arguments, but the \
+                                        test function takes {}", args.len(), input_cnt));
+            continue;
+        }
+
+        let ecx = &cx.ext_cx;
+        let call = ecx.expr_call(span, ecx.expr_ident(span, i.ident), args);
+        let body = ecx.block(span, vec![ecx.stmt_semi(call)]);
+        let ident = Ident::with_empty_ctxt(Symbol::gensym(&format!("{}_case_{}", i.ident, n)));
+        let unit = ecx.ty(span, ast::TyKind::Tup(vec![]));
+        wrappers.push((n, ecx.item_fn(span, ident, vec![], unit, body)));
+    }
+
+    let parent = if parent == ast::DUMMY_NODE_ID { ast::CRATE_NODE_ID } else { parent };
+    wrappers.into_iter().map(|(n, wrapper)| {
+        cx.ext_cx.current_expansion.mark = cx.ext_cx.resolver.get_module_scope(parent);
+        let wrapper = cx.ext_cx.monotonic_expander().fold_item(wrapper).pop().unwrap();
+        let case_name = if has_cases { format!("{}[case_{}]", name, n) } else { name.clone() };
+        (case_name, wrapper)
+    }).collect()
+}
+
 fn is_ignored(i: &ast::Item) -> bool {
     i.attrs.iter().any(|attr| attr.check_name("ignore"))
 }
@@ -390,6 +517,21 @@ fn is_allowed_fail(i: &ast::Item) -> bool {
     i.attrs.iter().any(|attr| attr.check_name("allow_fail"))
 }
 
+fn test_timeout(i: &ast::Item, cx: &TestCtxt) -> Option<u64> {
+    match i.attrs.iter().find(|attr| attr.check_name("test_timeout")) {
+        Some(attr) => {
+            let secs = attr.value_str().and_then(|s| s.as_str().parse::<u64>().ok());
+            if secs.is_none() {
+                cx.span_diagnostic.span_err(attr.span(),
+                                            "attribute must be of the form: \
+                                             `#[test_timeout = \"seconds\"]`");
+            }
+            secs
+        }
+        None => None,
+    }
+}
+
 fn should_panic(i: &ast::Item, cx: &TestCtxt) -> ShouldPanic {
     match i.attrs.iter().find(|attr| attr.check_name("should_panic")) {
         Some(attr) => {
@@ -650,9 +792,8 @@ fn mk_test_desc_and_fn_rec(cx: &TestCtxt, test: &Test) -> P<ast::Expr> {
 
     debug!("encoding {}", path_name_i(&path[..]));
 
-    // path to the #[test] function: "foo::bar::baz"
-    let path_string = path_name_i(&path[..]);
-    let name_expr = ecx.expr_str(span, Symbol::intern(&path_string));
+    // name of the test: "foo::bar::baz", or "foo::bar::baz[case_0]"
+    let name_expr = ecx.expr_str(span, Symbol::intern(&test.name));
 
     // self::test::StaticTestName($name_expr)
     let name_expr = ecx.expr_call(span,
@@ -677,6 +818,14 @@ fn mk_test_desc_and_fn_rec(cx: &TestCtxt, test: &Test) -> P<ast::Expr> {
         }
     };
     let allow_fail_expr = ecx.expr_bool(span, test.allow_fail);
+    let timeout_expr = match test.timeout {
+        Some(secs) => {
+            let lit = ast::LitKind::Int(secs as u128,
+                                        ast::LitIntType::Unsigned(ast::UintTy::U64));
+            ecx.expr_some(span, ecx.expr_lit(span, lit))
+        }
+        None => ecx.expr_none(span),
+    };
 
     // self::test::TestDesc { ... }
     let desc_expr = ecx.expr_struct(
@@ -685,7 +834,8 @@ fn mk_test_desc_and_fn_rec(cx: &TestCtxt, test: &Test) -> P<ast::Expr> {
         vec![field("name", name_expr),
              field("ignore", ignore_expr),
              field("should_panic", fail_expr),
-             field("allow_fail", allow_fail_expr)]);
+             field("allow_fail", allow_fail_expr),
+             field("timeout", timeout_expr)]);
 
 
     let mut visible_path = match cx.toplevel_reexport {
@@ -0,0 +1,291 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Named benchmark baselines, saved with `--save-baseline` and compared
+//! against with `--baseline`.
+
+use super::*;
+use std::fs;
+use std::path::Path;
+
+// Changes of the mean smaller than this fraction are reported as noise
+// even when they are statistically significant.
+const NOISE_THRESHOLD: f64 = 0.01;
+const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BaselineEntry {
+    median: f64,
+    mean: f64,
+    var: f64,
+    samples: usize,
+    mb_s: usize,
+}
+
+impl<'a> From<&'a BenchSamples> for BaselineEntry {
+    fn from(bs: &'a BenchSamples) -> BaselineEntry {
+        BaselineEntry {
+            median: bs.ns_iter_summ.median,
+            mean: bs.ns_iter_summ.mean,
+            var: bs.ns_iter_summ.var,
+            samples: bs.samples,
+            mb_s: bs.mb_s,
+        }
+    }
+}
+
+/// The results of the benchmarks of one run, keyed by benchmark name.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Baseline(BTreeMap<String, BaselineEntry>);
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline(BTreeMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn insert(&mut self, name: &str, bs: &BenchSamples) {
+        self.0.insert(name.to_owned(), BaselineEntry::from(bs));
+    }
+
+    /// Compares a new result of the named benchmark against this baseline.
+    /// Returns `None` if the benchmark isn't part of the baseline, or if
+    /// either of the runs didn't measure anything.
+    pub fn compare(&self, name: &str, bs: &BenchSamples) -> Option<BenchComparison> {
+        let old = self.0.get(name)?;
+        let new = BaselineEntry::from(bs);
+        if old.samples < 2 || new.samples < 2 || old.mean <= 0.0 {
+            return None;
+        }
+
+        Some(BenchComparison {
+            change: (new.mean - old.mean) / old.mean,
+            p_value: welch_p_value(old, &new),
+            old_mb_s: old.mb_s,
+            new_mb_s: new.mb_s,
+        })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let mut contents = String::new();
+        File::open(path)?.read_to_string(&mut contents)?;
+
+        let mut baseline = Baseline::new();
+        for line in contents.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = parse_entry(line).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData,
+                               format!("malformed line in benchmark baseline: {}", line))
+            })?;
+            baseline.0.insert(entry.0, entry.1);
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(path)?;
+        writeln!(file, "# median mean variance samples mb/s name")?;
+        for (name, entry) in &self.0 {
+            writeln!(file, "{} {} {} {} {} {}",
+                     entry.median, entry.mean, entry.var, entry.samples, entry.mb_s, name)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(String, BaselineEntry)> {
+    let mut fields = line.splitn(6, ' ');
+    let entry = BaselineEntry {
+        median: fields.next()?.parse().ok()?,
+        mean: fields.next()?.parse().ok()?,
+        var: fields.next()?.parse().ok()?,
+        samples: fields.next()?.parse().ok()?,
+        mb_s: fields.next()?.parse().ok()?,
+    };
+    Some((fields.next()?.to_owned(), entry))
+}
+
+/// Whether `name` can be used as the name of a baseline, which becomes the
+/// name of a directory.
+pub(crate) fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('/') && !name.contains('\\') && !name.contains("..")
+}
+
+/// Where the baseline of the given name is kept for this test binary: in
+/// `RUST_BENCH_BASELINE_DIR` if set, or else next to the test binary.
+pub(crate) fn baseline_path(name: &str) -> io::Result<PathBuf> {
+    if !is_valid_baseline_name(name) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                  format!("invalid benchmark baseline name `{}`", name)));
+    }
+    let dir = match env::var_os("RUST_BENCH_BASELINE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let exe = env::current_exe()?;
+            exe.parent().unwrap_or(Path::new(".")).join("bench-baselines")
+        }
+    };
+    Ok(dir.join(name).join(suite_name()))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Verdict {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+/// How a benchmark result compares to its baseline.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BenchComparison {
+    /// Relative change of the mean time per iteration.
+    pub change: f64,
+    /// Probability of seeing a change of the mean time per iteration at least
+    /// this large if nothing had actually changed.
+    pub p_value: f64,
+    pub old_mb_s: usize,
+    pub new_mb_s: usize,
+}
+
+impl BenchComparison {
+    pub fn verdict(&self) -> Verdict {
+        if self.p_value >= SIGNIFICANCE_LEVEL || self.change.abs() < NOISE_THRESHOLD {
+            Verdict::NoChange
+        } else if self.change > 0.0 {
+            Verdict::Regressed
+        } else {
+            Verdict::Improved
+        }
+    }
+}
+
+impl fmt::Display for BenchComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+.2}% (p = {:.3}", self.change * 100.0, self.p_value)?;
+        if self.old_mb_s != 0 && self.new_mb_s != 0 {
+            write!(f, ", was {} MB/s", self.old_mb_s)?;
+        }
+        write!(f, ")")
+    }
+}
+
+// Two-sided p-value of Welch's t-test on the mean time per iteration. The
+// benchmarks take enough samples that the t distribution is close enough to
+// the normal distribution to use that instead.
+fn welch_p_value(old: &BaselineEntry, new: &BaselineEntry) -> f64 {
+    let std_err = (old.var / old.samples as f64 + new.var / new.samples as f64).sqrt();
+    if std_err == 0.0 {
+        return if old.mean == new.mean { 1.0 } else { 0.0 };
+    }
+    let t = (new.mean - old.mean) / std_err;
+    erfc(t.abs() / 2f64.sqrt())
+}
+
+// The complementary error function for `x >= 0`, using the approximation
+// 7.1.26 from Abramowitz and Stegun, which is accurate to about 1e-7.
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 +
+               t * (-0.284496736 +
+               t * (1.421413741 +
+               t * (-1.453152027 +
+               t * 1.061405429))));
+    poly * (-x * x).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stats;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> TempDir {
+            let path = env::temp_dir().join(format!("libtest-baseline-{:x}", random_seed()));
+            fs::create_dir(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn bench_samples(samples: &[f64], mb_s: usize) -> BenchSamples {
+        BenchSamples {
+            ns_iter_summ: stats::Summary::new(samples),
+            samples: samples.len(),
+            mb_s,
+        }
+    }
+
+    #[test]
+    fn baseline_roundtrips_through_a_file() {
+        let mut baseline = Baseline::new();
+        baseline.insert("bench::small", &bench_samples(&[1.0, 2.0, 3.5], 0));
+        baseline.insert("bench::with spaces", &bench_samples(&[1.0e9 / 3.0, 7.25], 12));
+
+        let dir = TempDir::new();
+        let path = dir.0.join("roundtrip");
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+
+        assert_eq!(loaded, baseline);
+    }
+
+    #[test]
+    fn comparison_flags_significant_changes() {
+        let old: Vec<f64> = (0..50).map(|i| 100.0 + (i % 5) as f64).collect();
+        let mut baseline = Baseline::new();
+        baseline.insert("b", &bench_samples(&old, 0));
+
+        let same = baseline.compare("b", &bench_samples(&old, 0)).unwrap();
+        assert_eq!(same.verdict(), Verdict::NoChange);
+        assert!(same.p_value > 0.99);
+
+        let slower: Vec<f64> = old.iter().map(|x| x * 1.1).collect();
+        let slower = baseline.compare("b", &bench_samples(&slower, 0)).unwrap();
+        assert_eq!(slower.verdict(), Verdict::Regressed);
+        assert!(slower.p_value < 0.001);
+
+        let faster: Vec<f64> = old.iter().map(|x| x * 0.9).collect();
+        let faster = baseline.compare("b", &bench_samples(&faster, 0)).unwrap();
+        assert_eq!(faster.verdict(), Verdict::Improved);
+
+        // Noisy samples hide a small change
+        let noisy: Vec<f64> = (0..50).map(|i| if i % 2 == 0 { 50.0 } else { 160.0 }).collect();
+        let noisy = baseline.compare("b", &bench_samples(&noisy, 0)).unwrap();
+        assert_eq!(noisy.verdict(), Verdict::NoChange);
+
+        assert!(baseline.compare("missing", &bench_samples(&old, 0)).is_none());
+    }
+
+    #[test]
+    fn baseline_names_stay_in_their_directory() {
+        assert!(is_valid_baseline_name("main"));
+        assert!(is_valid_baseline_name("before-fix.2"));
+        assert!(!is_valid_baseline_name(""));
+        assert!(!is_valid_baseline_name("a/b"));
+        assert!(!is_valid_baseline_name("a\\b"));
+        assert!(!is_valid_baseline_name(".."));
+        assert!(baseline_path("../elsewhere").is_err());
+    }
+}
@@ -0,0 +1,662 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::*;
+use baseline::Verdict;
+
+pub(crate) trait OutputFormatter {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>)
+                       -> io::Result<()>;
+    fn write_test_start(&mut self, desc: &TestDesc, align: NamePadding) -> io::Result<()>;
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()>;
+    fn write_result(&mut self,
+                    desc: &TestDesc,
+                    result: &TestResult,
+                    exec_time: Duration,
+                    stdout: &[u8],
+                    state: &ConsoleTestState) -> io::Result<()>;
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool>;
+}
+
+pub(crate) struct HumanFormatter<T> {
+    out: OutputLocation<T>,
+    terse: bool,
+    use_color: bool,
+    test_count: usize,
+    max_name_len: usize, // number of columns to fill when aligning names
+}
+
+impl<T: Write> HumanFormatter<T> {
+    pub fn new(out: OutputLocation<T>,
+               use_color: bool,
+               terse: bool,
+               max_name_len: usize) -> Self {
+        HumanFormatter {
+            out,
+            terse,
+            use_color,
+            test_count: 0,
+            max_name_len,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    pub fn write_ok(&mut self) -> io::Result<()> {
+        self.write_short_result("ok", ".", term::color::GREEN)
+    }
+
+    pub fn write_failed(&mut self) -> io::Result<()> {
+        self.write_short_result("FAILED", "F", term::color::RED)
+    }
+
+    pub fn write_ignored(&mut self) -> io::Result<()> {
+        self.write_short_result("ignored", "i", term::color::YELLOW)
+    }
+
+    pub fn write_allowed_fail(&mut self) -> io::Result<()> {
+        self.write_short_result("FAILED (allowed)", "a", term::color::YELLOW)
+    }
+
+    pub fn write_bench(&mut self) -> io::Result<()> {
+        self.write_pretty("bench", term::color::CYAN)
+    }
+
+    pub fn write_short_result(&mut self, verbose: &str, quiet: &str, color: term::color::Color)
+                              -> io::Result<()> {
+        if self.terse {
+            self.write_pretty(quiet, color)?;
+            if self.test_count % QUIET_MODE_MAX_COLUMN == QUIET_MODE_MAX_COLUMN - 1 {
+                // we insert a new line every 100 dots in order to flush the
+                // screen when dealing with line-buffered output (e.g. piping to
+                // `stamp` in the rust CI).
+                self.write_plain("\n")?;
+            }
+            self.test_count += 1;
+            Ok(())
+        } else {
+            self.write_pretty(verbose, color)
+        }
+    }
+
+    pub fn write_pretty(&mut self, word: &str, color: term::color::Color) -> io::Result<()> {
+        match self.out {
+            Pretty(ref mut term) => {
+                if self.use_color {
+                    term.fg(color)?;
+                }
+                term.write_all(word.as_bytes())?;
+                if self.use_color {
+                    term.reset()?;
+                }
+                term.flush()
+            }
+            Raw(ref mut stdout) => {
+                stdout.write_all(word.as_bytes())?;
+                stdout.flush()
+            }
+        }
+    }
+
+    pub fn write_plain<S: AsRef<str>>(&mut self, s: S) -> io::Result<()> {
+        let s = s.as_ref();
+        self.out.write_all(s.as_bytes())?;
+        self.out.flush()
+    }
+
+    pub fn write_outputs(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nsuccesses:\n")?;
+        let mut successes = Vec::new();
+        let mut stdouts = String::new();
+        for &(ref f, ref stdout) in &state.not_failures {
+            successes.push(f.name.to_string());
+            if !stdout.is_empty() {
+                stdouts.push_str(&format!("---- {} stdout ----\n\t", f.name));
+                let output = String::from_utf8_lossy(stdout);
+                stdouts.push_str(&output);
+                stdouts.push_str("\n");
+            }
+        }
+        if !stdouts.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&stdouts)?;
+        }
+
+        self.write_plain("\nsuccesses:\n")?;
+        successes.sort();
+        for name in &successes {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_slow_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nslow tests:\n")?;
+        let mut slow = state.slow.iter().collect::<Vec<_>>();
+        slow.sort_by(|a, b| b.1.cmp(&a.1));
+        for &&(ref desc, exec_time) in &slow {
+            self.write_plain(&format!("    {} ({:.3}s)\n",
+                                      desc.name,
+                                      secs_from_dur(exec_time)))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfailures:\n")?;
+        let mut failures = Vec::new();
+        let mut fail_out = String::new();
+        for &(ref f, ref stdout) in &state.failures {
+            failures.push(f.name.to_string());
+            if !stdout.is_empty() {
+                fail_out.push_str(&format!("---- {} stdout ----\n\t", f.name));
+                let output = String::from_utf8_lossy(stdout);
+                fail_out.push_str(&output);
+                fail_out.push_str("\n");
+            }
+        }
+        if !fail_out.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&fail_out)?;
+        }
+
+        self.write_plain("\nfailures:\n")?;
+        failures.sort();
+        for name in &failures {
+            self.write_plain(&format!("    {}\n", name))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write> OutputFormatter for HumanFormatter<T> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>)
+                       -> io::Result<()> {
+        let noun = if test_count != 1 {
+            "tests"
+        } else {
+            "test"
+        };
+        self.write_plain(&format!("\nrunning {} {}\n", test_count, noun))?;
+        if let Some(seed) = shuffle_seed {
+            self.write_plain(&format!("shuffling tests with seed {} \
+                                       (rerun with --shuffle={})\n",
+                                      seed, seed))?;
+        }
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, desc: &TestDesc, align: NamePadding) -> io::Result<()> {
+        if self.terse && align != PadOnRight {
+            Ok(())
+        } else {
+            let name = desc.padded_name(self.max_name_len, align);
+            self.write_plain(&format!("test {} ... ", name))
+        }
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_plain(&format!("test {} has been running for over {} seconds\n",
+                                  desc.name,
+                                  TEST_WARN_TIMEOUT_S))
+    }
+
+    fn write_result(&mut self,
+                    desc: &TestDesc,
+                    result: &TestResult,
+                    exec_time: Duration,
+                    _stdout: &[u8],
+                    state: &ConsoleTestState) -> io::Result<()> {
+        match *result {
+            TrOk => self.write_ok()?,
+            TrFailed | TrFailedMsg(_) => self.write_failed()?,
+            TrIgnored => self.write_ignored()?,
+            TrAllowedFail => self.write_allowed_fail()?,
+            TrBench(ref bs) => {
+                self.write_bench()?;
+                self.write_plain(&format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(comparison) = state.compare_to_baseline(desc, bs) {
+                    self.write_plain(&format!(", change: {}", comparison))?;
+                    match comparison.verdict() {
+                        Verdict::Regressed => self.write_pretty(" regressed", term::color::RED)?,
+                        Verdict::Improved => self.write_pretty(" improved", term::color::GREEN)?,
+                        Verdict::NoChange => self.write_plain(" no change")?,
+                    }
+                }
+                return self.write_plain("\n");
+            }
+        }
+
+        if self.terse {
+            return Ok(());
+        }
+        if state.report_time && *result != TrIgnored {
+            self.write_plain(&format!(" <{:.3}s>", secs_from_dur(exec_time)))?;
+        }
+        if state.is_slow(result, exec_time) {
+            self.write_pretty(" (slow)", term::color::YELLOW)?;
+        }
+        self.write_plain("\n")
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        if state.options.display_output {
+            self.write_outputs(state)?;
+        }
+        let success = state.failed == 0;
+        if !success {
+            self.write_failures(state)?;
+        }
+        if !state.slow.is_empty() {
+            self.write_slow_tests(state)?;
+        }
+
+        self.write_plain("\ntest result: ")?;
+        if success {
+            // There's no parallelism at this point so it's safe to use color
+            self.write_pretty("ok", term::color::GREEN)?;
+        } else {
+            self.write_pretty("FAILED", term::color::RED)?;
+        }
+        let s = if state.allowed_fail > 0 {
+            format!(
+                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
+                state.passed,
+                state.failed + state.allowed_fail,
+                state.allowed_fail,
+                state.ignored,
+                state.measured,
+                state.filtered_out)
+        } else {
+            format!(
+                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out\n\n",
+                state.passed,
+                state.failed,
+                state.ignored,
+                state.measured,
+                state.filtered_out)
+        };
+        self.write_plain(&s)?;
+        Ok(success)
+    }
+}
+
+/// Writes one JSON object per line for every event of the test run, so that
+/// other tools can consume the results without parsing the human output.
+pub(crate) struct JsonFormatter<T> {
+    out: OutputLocation<T>,
+}
+
+impl<T: Write> JsonFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        JsonFormatter { out }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_message(&mut self, s: &str) -> io::Result<()> {
+        assert!(!s.contains('\n'));
+
+        self.out.write_all(s.as_bytes())?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+
+    fn write_event(&mut self,
+                   ty: &str,
+                   name: &str,
+                   evt: &str,
+                   extra: Option<String>) -> io::Result<()> {
+        if let Some(extras) = extra {
+            self.write_message(&*format!(
+                r#"{{ "type": "{}", "name": "{}", "event": "{}", {} }}"#,
+                ty,
+                EscapedString(name),
+                evt,
+                extras))
+        } else {
+            self.write_message(&*format!(
+                r#"{{ "type": "{}", "name": "{}", "event": "{}" }}"#,
+                ty,
+                EscapedString(name),
+                evt))
+        }
+    }
+}
+
+impl<T: Write> OutputFormatter for JsonFormatter<T> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>)
+                       -> io::Result<()> {
+        if let Some(seed) = shuffle_seed {
+            self.write_message(&*format!("{{ \"type\": \"suite\", \
+                                          \"event\": \"started\", \
+                                          \"test_count\": {}, \
+                                          \"shuffle_seed\": {} }}",
+                                         test_count,
+                                         seed))
+        } else {
+            self.write_message(&*format!(
+                r#"{{ "type": "suite", "event": "started", "test_count": {} }}"#,
+                test_count))
+        }
+    }
+
+    fn write_test_start(&mut self, desc: &TestDesc, _align: NamePadding) -> io::Result<()> {
+        self.write_message(&*format!(
+            r#"{{ "type": "test", "event": "started", "name": "{}" }}"#,
+            EscapedString(desc.name.as_slice())))
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_message(&*format!(
+            r#"{{ "type": "test", "event": "timeout", "name": "{}" }}"#,
+            EscapedString(desc.name.as_slice())))
+    }
+
+    fn write_result(&mut self,
+                    desc: &TestDesc,
+                    result: &TestResult,
+                    exec_time: Duration,
+                    stdout: &[u8],
+                    state: &ConsoleTestState) -> io::Result<()> {
+        let name = desc.name.as_slice();
+
+        let mut extra = Vec::new();
+        if !stdout.is_empty() {
+            extra.push(format!(r#""stdout": "{}""#,
+                               EscapedString(String::from_utf8_lossy(stdout))));
+        }
+        if let TrFailedMsg(ref m) = *result {
+            extra.push(format!(r#""message": "{}""#, EscapedString(m)));
+        }
+        if state.report_time && *result != TrIgnored {
+            extra.push(format!(r#""exec_time": {:.3}"#, secs_from_dur(exec_time)));
+        }
+        if state.is_slow(result, exec_time) {
+            extra.push(r#""slow": true"#.to_owned());
+        }
+        let extra = if extra.is_empty() {
+            None
+        } else {
+            Some(extra.join(", "))
+        };
+
+        match *result {
+            TrOk => self.write_event("test", name, "ok", extra),
+
+            TrFailed | TrFailedMsg(_) => self.write_event("test", name, "failed", extra),
+
+            TrIgnored => self.write_event("test", name, "ignored", extra),
+
+            TrAllowedFail => self.write_event("test", name, "allowed_failure", extra),
+
+            TrBench(ref bs) => {
+                let median = bs.ns_iter_summ.median as usize;
+                let deviation = (bs.ns_iter_summ.max - bs.ns_iter_summ.min) as usize;
+
+                let baseline = match state.compare_to_baseline(desc, bs) {
+                    Some(comparison) => {
+                        let verdict = match comparison.verdict() {
+                            Verdict::Regressed => "regressed",
+                            Verdict::Improved => "improved",
+                            Verdict::NoChange => "no_change",
+                        };
+                        format!(", \"change\": {}, \"p_value\": {}, \"verdict\": \"{}\"",
+                                comparison.change,
+                                comparison.p_value,
+                                verdict)
+                    }
+                    None => String::new(),
+                };
+                let line = format!("{{ \"type\": \"bench\", \
+                                    \"name\": \"{}\", \
+                                    \"median\": {}, \
+                                    \"deviation\": {}, \
+                                    \"mb_per_second\": {}{} }}",
+                                   EscapedString(name),
+                                   median,
+                                   deviation,
+                                   bs.mb_s,
+                                   baseline);
+
+                self.write_message(&*line)
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let success = state.failed == 0;
+        self.write_message(&*format!("{{ \"type\": \"suite\", \
+                                      \"event\": \"{}\", \
+                                      \"passed\": {}, \
+                                      \"failed\": {}, \
+                                      \"allowed_fail\": {}, \
+                                      \"ignored\": {}, \
+                                      \"measured\": {}, \
+                                      \"filtered_out\": {} }}",
+                                     if success { "ok" } else { "failed" },
+                                     state.passed,
+                                     state.failed,
+                                     state.allowed_fail,
+                                     state.ignored,
+                                     state.measured,
+                                     state.filtered_out))?;
+        Ok(success)
+    }
+}
+
+/// Collects the results of the run and writes them out as a JUnit XML
+/// document once the run has finished.
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    suite_name: String,
+    results: Vec<(TestDesc, TestResult, Duration, Vec<u8>)>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>, suite_name: String) -> Self {
+        JunitFormatter {
+            out,
+            suite_name,
+            results: Vec::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(&mut self, _test_count: usize, _shuffle_seed: Option<u64>)
+                       -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc, _align: NamePadding) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_result(&mut self,
+                    desc: &TestDesc,
+                    result: &TestResult,
+                    exec_time: Duration,
+                    stdout: &[u8],
+                    _state: &ConsoleTestState) -> io::Result<()> {
+        self.results.push((desc.clone(), result.clone(), exec_time, stdout.to_vec()));
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        writeln!(self.out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(self.out, "<testsuites>")?;
+        writeln!(self.out,
+                 r#"<testsuite name="{}" errors="0" failures="{}" skipped="{}" tests="{}">"#,
+                 XmlEscaped(&self.suite_name),
+                 state.failed,
+                 state.ignored + state.allowed_fail,
+                 state.total)?;
+
+        for &(ref desc, ref result, exec_time, ref stdout) in &self.results {
+            // The module path of the test is reported as its class, which is
+            // how most JUnit consumers group the test cases.
+            let name = desc.name.as_slice();
+            let (class_name, test_name) = match name.rfind("::") {
+                Some(i) => (&name[..i], &name[i + 2..]),
+                None => (&self.suite_name[..], name),
+            };
+            writeln!(self.out,
+                     r#"<testcase classname="{}" name="{}" time="{:.3}">"#,
+                     XmlEscaped(class_name),
+                     XmlEscaped(test_name),
+                     secs_from_dur(exec_time))?;
+            match *result {
+                TrOk | TrBench(_) => {}
+                TrFailed => writeln!(self.out, r#"<failure type="assert"/>"#)?,
+                TrFailedMsg(ref m) => {
+                    writeln!(self.out,
+                             r#"<failure type="assert" message="{}"/>"#,
+                             XmlEscaped(m))?
+                }
+                TrIgnored => writeln!(self.out, "<skipped/>")?,
+                TrAllowedFail => writeln!(self.out, r#"<skipped message="failed (allowed)"/>"#)?,
+            }
+            if !stdout.is_empty() {
+                writeln!(self.out,
+                         "<system-out>{}</system-out>",
+                         XmlEscaped(String::from_utf8_lossy(stdout)))?;
+            }
+            writeln!(self.out, "</testcase>")?;
+        }
+
+        writeln!(self.out, "</testsuite>")?;
+        writeln!(self.out, "</testsuites>")?;
+        self.out.flush()?;
+
+        Ok(state.failed == 0)
+    }
+}
+
+/// A formatting utility used to print strings with characters in need of escaping.
+/// Base code taken form `libserialize::json::escape_str`
+pub(crate) struct EscapedString<S: AsRef<str>>(pub S);
+
+impl<S: AsRef<str>> fmt::Display for EscapedString<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut start = 0;
+
+        for (i, byte) in self.0.as_ref().bytes().enumerate() {
+            let escaped = match byte {
+                b'"' => "\\\"",
+                b'\\' => "\\\\",
+                b'\x00' => "\\u0000",
+                b'\x01' => "\\u0001",
+                b'\x02' => "\\u0002",
+                b'\x03' => "\\u0003",
+                b'\x04' => "\\u0004",
+                b'\x05' => "\\u0005",
+                b'\x06' => "\\u0006",
+                b'\x07' => "\\u0007",
+                b'\x08' => "\\b",
+                b'\t' => "\\t",
+                b'\n' => "\\n",
+                b'\x0b' => "\\u000b",
+                b'\x0c' => "\\f",
+                b'\r' => "\\r",
+                b'\x0e' => "\\u000e",
+                b'\x0f' => "\\u000f",
+                b'\x10' => "\\u0010",
+                b'\x11' => "\\u0011",
+                b'\x12' => "\\u0012",
+                b'\x13' => "\\u0013",
+                b'\x14' => "\\u0014",
+                b'\x15' => "\\u0015",
+                b'\x16' => "\\u0016",
+                b'\x17' => "\\u0017",
+                b'\x18' => "\\u0018",
+                b'\x19' => "\\u0019",
+                b'\x1a' => "\\u001a",
+                b'\x1b' => "\\u001b",
+                b'\x1c' => "\\u001c",
+                b'\x1d' => "\\u001d",
+                b'\x1e' => "\\u001e",
+                b'\x1f' => "\\u001f",
+                b'\x7f' => "\\u007f",
+                _ => {
+                    continue;
+                }
+            };
+
+            if start < i {
+                f.write_str(&self.0.as_ref()[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + 1;
+        }
+
+        if start != self.0.as_ref().len() {
+            f.write_str(&self.0.as_ref()[start..])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Like `EscapedString`, but escapes for XML attribute values and character
+/// data. Control characters which may not appear in an XML document at all
+/// are replaced with U+FFFD.
+pub(crate) struct XmlEscaped<S: AsRef<str>>(pub S);
+
+impl<S: AsRef<str>> fmt::Display for XmlEscaped<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self.0.as_ref();
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' => "&quot;",
+                '\'' => "&apos;",
+                '\t' | '\n' | '\r' => continue,
+                c if c < ' ' => "\u{fffd}",
+                _ => continue,
+            };
+
+            if start < i {
+                f.write_str(&s[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + c.len_utf8();
+        }
+
+        if start != s.len() {
+            f.write_str(&s[start..])?;
+        }
+
+        Ok(())
+    }
+}
@@ -37,14 +37,12 @@
 #![feature(asm)]
 #![cfg_attr(unix, feature(libc))]
 #![feature(set_stdio)]
-#![feature(panic_unwind)]
 #![feature(staged_api)]
 
 extern crate getopts;
 extern crate term;
 #[cfg(unix)]
 extern crate libc;
-extern crate panic_unwind;
 
 pub use self::TestFn::*;
 pub use self::ColorConfig::*;
@@ -54,7 +52,7 @@ use self::TestEvent::*;
 use self::NamePadding::*;
 use self::OutputLocation::*;
 
-use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
 use std::any::Any;
 use std::cmp;
 use std::collections::BTreeMap;
@@ -65,23 +63,41 @@ use std::io::prelude::*;
 use std::io;
 use std::iter::repeat;
 use std::path::PathBuf;
+use std::process::{self, Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
 use std::sync::mpsc::{channel, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Instant, Duration};
 
+use formatters::{OutputFormatter, HumanFormatter, JsonFormatter, JunitFormatter};
+use baseline::{Baseline, BenchComparison, baseline_path, is_valid_baseline_name};
+
 const TEST_WARN_TIMEOUT_S: u64 = 60;
 const QUIET_MODE_MAX_COLUMN: usize = 100; // insert a '\n' after 100 tests in quiet mode
 
+// Names the test to run in a child process spawned by `run_test`
+const SECONDARY_TEST_INVOKER_VAR: &'static str = "__RUST_TEST_INVOKE";
+// Exit codes of such a child process, used to report the result of the test
+const TR_OK: i32 = 50;
+const TR_FAILED: i32 = 51;
+
+// Set when a test running on a thread of this process exceeded its timeout,
+// which leaves that thread running in the background
+static TESTS_ABANDONED: AtomicBool = ATOMIC_BOOL_INIT;
+
 // to be used by rustc to compile tests in libtest
 pub mod test {
     pub use {Bencher, TestName, TestResult, TestDesc, TestDescAndFn, TestOpts, TrFailed,
              TrFailedMsg, TrIgnored, TrOk, Metric, MetricMap, StaticTestFn, StaticTestName,
              DynTestName, DynTestFn, run_test, test_main, test_main_static, filter_tests,
-             parse_opts, StaticBenchFn, ShouldPanic, Options};
+             parse_opts, StaticBenchFn, ShouldPanic, Options, OutputFormat,
+             ReportFormat};
 }
 
 pub mod stats;
+mod formatters;
+mod baseline;
 
 // The name of a test. By convention this follows the rules for rust
 // paths; i.e. it should be a series of identifiers separated by double
@@ -209,6 +225,7 @@ pub struct TestDesc {
     pub ignore: bool,
     pub should_panic: ShouldPanic,
     pub allow_fail: bool,
+    pub timeout: Option<u64>, // in seconds, overrides --test-timeout
 }
 
 #[derive(Clone)]
@@ -243,12 +260,17 @@ impl Metric {
 #[derive(Copy, Clone, Debug)]
 pub struct Options {
     display_output: bool,
+    // Whether the tests are those of the running executable, as is the case
+    // for the test harness generated by `--test`. Only then can a test be run
+    // in a child process by executing this binary again.
+    harness: bool,
 }
 
 impl Options {
     pub fn new() -> Options {
         Options {
             display_output: false,
+            harness: false,
         }
     }
 
@@ -261,18 +283,39 @@ impl Options {
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Options) {
+    let invoked_test = if options.harness {
+        env::var(SECONDARY_TEST_INVOKER_VAR).ok()
+    } else {
+        None
+    };
+    if let Some(name) = invoked_test {
+        // This is a child process started by `run_test` to run a single
+        // test. Tests which spawn the test binary themselves shouldn't
+        // end up running this test again.
+        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+        let test = tests.into_iter()
+                        .find(|test| test.desc.name.as_slice() == name)
+                        .unwrap_or_else(|| panic!("no test named '{}' to run", name));
+        run_test_in_spawned_subprocess(test);
+    }
     let mut opts = match parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => panic!("{:?}", msg),
         None => return,
     };
     opts.options = options;
+    if opts.process_per_test && !options.harness {
+        panic!("--process-per-test is only supported by test binaries built with --test");
+    }
     if opts.list {
         if let Err(e) = list_tests_console(&opts, tests) {
             panic!("io error when listing tests: {:?}", e);
         }
     } else {
         match run_tests_console(&opts, tests) {
+            // Exit straight away rather than leave it to the caller, which
+            // may end up waiting for the hung tests.
+            Ok(true) if TESTS_ABANDONED.load(Ordering::SeqCst) => std::process::exit(0),
             Ok(true) => {}
             Ok(false) => std::process::exit(101),
             Err(e) => panic!("io error when running tests: {:?}", e),
@@ -308,7 +351,9 @@ pub fn test_main_static(tests: &[TestDescAndFn]) {
                                }
                            })
                            .collect();
-    test_main(&args, owned_tests, Options::new())
+    let mut options = Options::new();
+    options.harness = true;
+    test_main(&args, owned_tests, options)
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -318,6 +363,19 @@ pub enum ColorConfig {
     NeverColor,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Pretty,
+    Terse,
+    Json,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Json,
+}
+
 #[derive(Debug)]
 pub struct TestOpts {
     pub list: bool,
@@ -326,12 +384,24 @@ pub struct TestOpts {
     pub run_ignored: bool,
     pub run_tests: bool,
     pub bench_benchmarks: bool,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
     pub logfile: Option<PathBuf>,
+    pub report: Option<PathBuf>,
+    pub report_format: ReportFormat,
     pub nocapture: bool,
     pub color: ColorConfig,
-    pub quiet: bool,
+    pub format: OutputFormat,
     pub test_threads: Option<usize>,
+    pub test_timeout: Option<Duration>,
     pub skip: Vec<String>,
+    pub shard_index: usize,
+    pub shard_count: usize,
+    pub shuffle_seed: Option<u64>,
+    pub report_time: bool,
+    pub slow_threshold: Option<Duration>,
+    pub fail_slow: bool,
+    pub process_per_test: bool,
     pub options: Options,
 }
 
@@ -345,12 +415,24 @@ impl TestOpts {
             run_ignored: false,
             run_tests: false,
             bench_benchmarks: false,
+            baseline: None,
+            save_baseline: None,
             logfile: None,
+            report: None,
+            report_format: ReportFormat::Junit,
             nocapture: false,
             color: AutoColor,
-            quiet: false,
+            format: OutputFormat::Pretty,
             test_threads: None,
+            test_timeout: None,
             skip: vec![],
+            shard_index: 0,
+            shard_count: 1,
+            shuffle_seed: None,
+            report_time: false,
+            slow_threshold: None,
+            fail_slow: false,
+            process_per_test: false,
             options: Options::new(),
         }
     }
@@ -365,21 +447,49 @@ fn optgroups() -> getopts::Options {
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
         .optflag("", "list", "List all tests and benchmarks")
+        .optopt("", "save-baseline", "Save the benchmark results under the given name \
+                                      to compare later runs against", "NAME")
+        .optopt("", "baseline", "Compare the benchmark results against the ones saved \
+                                 under the given name", "NAME")
         .optflag("h", "help", "Display this message (longer with --help)")
         .optopt("", "logfile", "Write logs to the specified file instead \
                                 of stdout", "PATH")
+        .optopt("", "report", "Write a report of the test run to the specified \
+                               file, in addition to the console output", "PATH")
+        .optopt("", "report-format", "Configure the format of the --report file:
+            junit = JUnit XML document (default);
+            json  = one json object per line, as with --format=json", "junit|json")
         .optflag("", "nocapture", "don't capture stdout/stderr of each \
                                    task, allow printing directly")
         .optopt("", "test-threads", "Number of threads used for running tests \
                                      in parallel", "n_threads")
+        .optopt("", "test-timeout", "Fail tests which run for longer than the given number \
+                                     of seconds", "SECONDS")
         .optmulti("", "skip", "Skip tests whose names contain FILTER (this flag can \
                                be used multiple times)","FILTER")
-        .optflag("q", "quiet", "Display one character per test instead of one line")
+        .optflag("q", "quiet", "Display one character per test instead of one line. \
+                                Alias to --format=terse")
         .optflag("", "exact", "Exactly match filters rather than by substring")
+        .optopt("", "shard-count", "Split the tests into the given number of shards \
+                                    and only run one of them", "N")
+        .optopt("", "shard-index", "Zero-based index of the shard to run when \
+                                    --shard-count is given", "N")
+        .optflagopt("", "shuffle", "Run the tests in a random order, using the given \
+                                    seed or a randomly chosen one", "SEED")
+        .optflag("", "report-time", "Show the execution time of each test")
+        .optopt("", "slow-threshold", "Mark tests which run for longer than the given \
+                                       number of milliseconds as slow", "MILLISECONDS")
+        .optflag("", "fail-slow", "Treat tests exceeding --slow-threshold as failures")
+        .optflag("", "process-per-test", "Run each test in a separate process of this \
+                                          test binary")
         .optopt("", "color", "Configure coloring of output:
             auto   = colorize if stdout is a tty and tests are run on serially (default);
             always = always colorize output;
-            never  = never colorize output;", "auto|always|never");
+            never  = never colorize output;", "auto|always|never")
+        .optopt("", "format", "Configure formatting of output:
+            pretty = Print verbose output;
+            terse  = Display one character per test;
+            json   = Output a json document", "pretty|terse|json");
     return opts
 }
 
@@ -398,6 +508,32 @@ All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
 
+Tests which run for longer than the --slow-threshold flag or the
+RUST_TEST_SLOW_THRESHOLD environment variable (in milliseconds) are reported
+as slow, and fail the run if --fail-slow is also given. The --report-time flag
+shows the execution time of every test.
+
+Tests which run for longer than the --test-timeout flag (in seconds) or their
+own #[test_timeout = "seconds"] attribute are reported as failed, and the rest
+of the tests keep running. Tests with a timeout run in a separate process of
+the test binary, which is killed once the timeout is exceeded.
+
+Benchmark results can be saved with --save-baseline NAME, and a later run
+given --baseline NAME reports how each benchmark changed since then, and
+whether that change is significant given the noise of the measurements.
+Baselines are kept in a bench-baselines directory next to the test binary,
+or in the RUST_BENCH_BASELINE_DIR environment variable if it is set.
+
+The --process-per-test flag runs every test in a new process of the test
+binary. A test which crashes or aborts the process then only fails itself,
+and #[should_panic] tests also work when compiled with -C panic=abort.
+
+The --shard-count and --shard-index flags split the tests between several
+invocations of the test binary, each of which runs one shard. Tests are
+assigned to shards by name, so a test stays in its shard when others are added.
+The --shuffle flag runs the tests in a random order and prints the seed used,
+which can be passed back to --shuffle to reproduce that order.
+
 Test Attributes:
 
     #[test]        - Indicates a function is a test to be run. This function
@@ -411,7 +547,13 @@ Test Attributes:
     #[ignore]      - When applied to a function which is already attributed as a
                      test, then the test runner will ignore these tests during
                      normal test runs. Running with --ignored will run these
-                     tests."#,
+                     tests.
+    #[test_case(args...)] - Runs the test, which takes arguments, once with
+                     each of the given arguments. The cases are named after
+                     the test followed by [case_0], [case_1], and so on.
+    #[test_fixture(setup)] - Passes the value returned by the function setup
+                     as the first argument of the test, and drops it once the
+                     test has finished."#,
              usage = options.usage(&message));
 }
 
@@ -443,6 +585,20 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
     let logfile = matches.opt_str("logfile");
     let logfile = logfile.map(|s| PathBuf::from(&s));
 
+    let report = matches.opt_str("report");
+    let report = report.map(|s| PathBuf::from(&s));
+
+    let report_format = match matches.opt_str("report-format").as_ref().map(|s| &**s) {
+        Some("junit") | None => ReportFormat::Junit,
+        Some("json") => ReportFormat::Json,
+
+        Some(v) => {
+            return Some(Err(format!("argument for --report-format must be junit or json (was \
+                                     {})",
+                                    v)))
+        }
+    };
+
     let bench_benchmarks = matches.opt_present("bench");
     let run_tests = !bench_benchmarks || matches.opt_present("test");
 
@@ -468,6 +624,85 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
             None,
     };
 
+    let test_timeout = match matches.opt_str("test-timeout") {
+        Some(secs_str) =>
+            match secs_str.parse::<u64>() {
+                Ok(0) =>
+                    return Some(Err(format!("argument for --test-timeout must not be 0"))),
+                Ok(secs) => Some(Duration::from_secs(secs)),
+                Err(e) =>
+                    return Some(Err(format!("argument for --test-timeout must be a number \
+                                             of seconds > 0 (error: {})", e)))
+            },
+        None =>
+            None,
+    };
+
+    let slow_threshold = match matches.opt_str("slow-threshold")
+                                      .or_else(|| env::var("RUST_TEST_SLOW_THRESHOLD").ok()) {
+        Some(ms_str) =>
+            match ms_str.parse::<u64>() {
+                Ok(ms) => Some(Duration::from_millis(ms)),
+                Err(e) =>
+                    return Some(Err(format!("argument for --slow-threshold must be a number \
+                                             of milliseconds (error: {})", e)))
+            },
+        None =>
+            None,
+    };
+
+    let fail_slow = matches.opt_present("fail-slow");
+    if fail_slow && slow_threshold.is_none() {
+        return Some(Err(format!("--fail-slow requires a --slow-threshold to be set")));
+    }
+
+    let shard_count = match matches.opt_str("shard-count") {
+        Some(n_str) =>
+            match n_str.parse::<usize>() {
+                Ok(0) =>
+                    return Some(Err(format!("argument for --shard-count must not be 0"))),
+                Ok(n) => n,
+                Err(e) =>
+                    return Some(Err(format!("argument for --shard-count must be a number > 0 \
+                                             (error: {})", e)))
+            },
+        None =>
+            1,
+    };
+
+    let shard_index = match matches.opt_str("shard-index") {
+        Some(n_str) =>
+            match n_str.parse::<usize>() {
+                Ok(n) if n < shard_count => n,
+                Ok(n) =>
+                    return Some(Err(format!("argument for --shard-index must be less than \
+                                             the shard count {} (was {})", shard_count, n))),
+                Err(e) =>
+                    return Some(Err(format!("argument for --shard-index must be a number \
+                                             (error: {})", e)))
+            },
+        None if shard_count > 1 =>
+            return Some(Err(format!("--shard-count requires a --shard-index to be set"))),
+        None =>
+            0,
+    };
+
+    let shuffle_seed = if matches.opt_present("shuffle") {
+        match matches.opt_str("shuffle") {
+            Some(seed_str) =>
+                match seed_str.parse::<u64>() {
+                    Ok(seed) => Some(seed),
+                    Err(e) =>
+                        return Some(Err(format!("argument for --shuffle must be a number \
+                                                 (error: {})", e)))
+                },
+            None =>
+                Some(random_seed()),
+        }
+    } else {
+        None
+    };
+
     let color = match matches.opt_str("color").as_ref().map(|s| &**s) {
         Some("auto") | None => AutoColor,
         Some("always") => AlwaysColor,
@@ -480,6 +715,28 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         }
     };
 
+    let format = match matches.opt_str("format").as_ref().map(|s| &**s) {
+        None if quiet => OutputFormat::Terse,
+        Some("pretty") | None => OutputFormat::Pretty,
+        Some("terse") => OutputFormat::Terse,
+        Some("json") => OutputFormat::Json,
+
+        Some(v) => {
+            return Some(Err(format!("argument for --format must be pretty, terse, or json (was \
+                                     {})",
+                                    v)))
+        }
+    };
+
+    let baseline = matches.opt_str("baseline");
+    let save_baseline = matches.opt_str("save-baseline");
+    for name in baseline.iter().chain(save_baseline.iter()) {
+        if !is_valid_baseline_name(name) {
+            return Some(Err(format!("invalid benchmark baseline name `{}`: it must not be \
+                                     empty or contain `/`, `\\` or `..`", name)));
+        }
+    }
+
     let test_opts = TestOpts {
         list,
         filter,
@@ -487,21 +744,44 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         run_ignored,
         run_tests,
         bench_benchmarks,
+        baseline,
+        save_baseline,
         logfile,
+        report,
+        report_format,
         nocapture,
         color,
-        quiet,
+        format,
         test_threads,
+        test_timeout,
         skip: matches.opt_strs("skip"),
+        shard_index,
+        shard_count,
+        shuffle_seed,
+        report_time: matches.opt_present("report-time"),
+        slow_threshold,
+        fail_slow,
+        process_per_test: matches.opt_present("process-per-test"),
         options: Options::new(),
     };
 
     Some(Ok(test_opts))
 }
 
+// Picks a seed for `--shuffle` when none was given on the command line.
+fn random_seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // `RandomState` is seeded from the OS's source of randomness, so hashing
+    // nothing with it is as good a random number as we need here.
+    RandomState::new().build_hasher().finish()
+}
+
 #[derive(Clone, PartialEq)]
 pub struct BenchSamples {
     ns_iter_summ: stats::Summary,
+    samples: usize,
     mb_s: usize,
 }
 
@@ -522,11 +802,24 @@ enum OutputLocation<T> {
     Raw(T),
 }
 
-struct ConsoleTestState<T> {
+impl<T: Write> Write for OutputLocation<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match *self {
+            Pretty(ref mut term) => term.write(buf),
+            Raw(ref mut stdout) => stdout.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            Pretty(ref mut term) => term.flush(),
+            Raw(ref mut stdout) => stdout.flush(),
+        }
+    }
+}
+
+struct ConsoleTestState {
     log_out: Option<File>,
-    out: OutputLocation<T>,
-    use_color: bool,
-    quiet: bool,
     total: usize,
     passed: usize,
     failed: usize,
@@ -537,26 +830,23 @@ struct ConsoleTestState<T> {
     metrics: MetricMap,
     failures: Vec<(TestDesc, Vec<u8>)>,
     not_failures: Vec<(TestDesc, Vec<u8>)>,
-    max_name_len: usize, // number of columns to fill when aligning names
+    slow: Vec<(TestDesc, Duration)>,
+    report_time: bool,
+    slow_threshold: Option<Duration>,
+    shuffle_seed: Option<u64>,
+    baseline: Option<Baseline>,
+    bench_results: Baseline,
     options: Options,
 }
 
-impl<T: Write> ConsoleTestState<T> {
-    pub fn new(opts: &TestOpts, _: Option<T>) -> io::Result<ConsoleTestState<io::Stdout>> {
+impl ConsoleTestState {
+    pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
         let log_out = match opts.logfile {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
-        let out = match term::stdout() {
-            None => Raw(io::stdout()),
-            Some(t) => Pretty(t),
-        };
-
         Ok(ConsoleTestState {
-            out,
             log_out,
-            use_color: use_color(opts),
-            quiet: opts.quiet,
             total: 0,
             passed: 0,
             failed: 0,
@@ -567,119 +857,31 @@ impl<T: Write> ConsoleTestState<T> {
             metrics: MetricMap::new(),
             failures: Vec::new(),
             not_failures: Vec::new(),
-            max_name_len: 0,
+            slow: Vec::new(),
+            report_time: opts.report_time,
+            slow_threshold: opts.slow_threshold,
+            shuffle_seed: opts.shuffle_seed,
+            baseline: None,
+            bench_results: Baseline::new(),
             options: opts.options,
         })
     }
 
-    pub fn write_ok(&mut self) -> io::Result<()> {
-        self.write_short_result("ok", ".", term::color::GREEN)
-    }
-
-    pub fn write_failed(&mut self) -> io::Result<()> {
-        self.write_short_result("FAILED", "F", term::color::RED)
-    }
-
-    pub fn write_ignored(&mut self) -> io::Result<()> {
-        self.write_short_result("ignored", "i", term::color::YELLOW)
+    pub fn compare_to_baseline(&self, test: &TestDesc, bs: &BenchSamples)
+                               -> Option<BenchComparison> {
+        self.baseline.as_ref().and_then(|baseline| baseline.compare(test.name.as_slice(), bs))
     }
 
-    pub fn write_allowed_fail(&mut self) -> io::Result<()> {
-        self.write_short_result("FAILED (allowed)", "a", term::color::YELLOW)
-    }
-
-    pub fn write_bench(&mut self) -> io::Result<()> {
-        self.write_pretty("bench", term::color::CYAN)
-    }
-
-    pub fn write_short_result(&mut self, verbose: &str, quiet: &str, color: term::color::Color)
-                              -> io::Result<()> {
-        if self.quiet {
-            self.write_pretty(quiet, color)?;
-            if self.current_test_count() % QUIET_MODE_MAX_COLUMN == QUIET_MODE_MAX_COLUMN - 1 {
-                // we insert a new line every 100 dots in order to flush the
-                // screen when dealing with line-buffered output (e.g. piping to
-                // `stamp` in the rust CI).
-                self.write_plain("\n")?;
-            }
-            Ok(())
-        } else {
-            self.write_pretty(verbose, color)?;
-            self.write_plain("\n")
+    // Whether a test which finished with `result` after `exec_time` should be
+    // reported as slow. Ignored tests and benchmarks never are.
+    pub fn is_slow(&self, result: &TestResult, exec_time: Duration) -> bool {
+        match (self.slow_threshold, result) {
+            (_, &TrIgnored) | (_, &TrBench(_)) => false,
+            (Some(threshold), _) => exec_time > threshold,
+            (None, _) => false,
         }
     }
 
-    pub fn write_pretty(&mut self, word: &str, color: term::color::Color) -> io::Result<()> {
-        match self.out {
-            Pretty(ref mut term) => {
-                if self.use_color {
-                    term.fg(color)?;
-                }
-                term.write_all(word.as_bytes())?;
-                if self.use_color {
-                    term.reset()?;
-                }
-                term.flush()
-            }
-            Raw(ref mut stdout) => {
-                stdout.write_all(word.as_bytes())?;
-                stdout.flush()
-            }
-        }
-    }
-
-    pub fn write_plain<S: AsRef<str>>(&mut self, s: S) -> io::Result<()> {
-        let s = s.as_ref();
-        match self.out {
-            Pretty(ref mut term) => {
-                term.write_all(s.as_bytes())?;
-                term.flush()
-            }
-            Raw(ref mut stdout) => {
-                stdout.write_all(s.as_bytes())?;
-                stdout.flush()
-            }
-        }
-    }
-
-    pub fn write_run_start(&mut self, len: usize) -> io::Result<()> {
-        self.total = len;
-        let noun = if len != 1 {
-            "tests"
-        } else {
-            "test"
-        };
-        self.write_plain(&format!("\nrunning {} {}\n", len, noun))
-    }
-
-    pub fn write_test_start(&mut self, test: &TestDesc, align: NamePadding) -> io::Result<()> {
-        if self.quiet && align != PadOnRight {
-            Ok(())
-        } else {
-            let name = test.padded_name(self.max_name_len, align);
-            self.write_plain(&format!("test {} ... ", name))
-        }
-    }
-
-    pub fn write_result(&mut self, result: &TestResult) -> io::Result<()> {
-        match *result {
-            TrOk => self.write_ok(),
-            TrFailed | TrFailedMsg(_) => self.write_failed(),
-            TrIgnored => self.write_ignored(),
-            TrAllowedFail => self.write_allowed_fail(),
-            TrBench(ref bs) => {
-                self.write_bench()?;
-                self.write_plain(&format!(": {}\n", fmt_bench_samples(bs)))
-            }
-        }
-    }
-
-    pub fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
-        self.write_plain(&format!("test {} has been running for over {} seconds\n",
-                                  desc.name,
-                                  TEST_WARN_TIMEOUT_S))
-    }
-
     pub fn write_log<S: AsRef<str>>(&mut self, msg: S) -> io::Result<()> {
         let msg = msg.as_ref();
         match self.log_out {
@@ -702,101 +904,9 @@ impl<T: Write> ConsoleTestState<T> {
                     test.name))
     }
 
-    pub fn write_failures(&mut self) -> io::Result<()> {
-        self.write_plain("\nfailures:\n")?;
-        let mut failures = Vec::new();
-        let mut fail_out = String::new();
-        for &(ref f, ref stdout) in &self.failures {
-            failures.push(f.name.to_string());
-            if !stdout.is_empty() {
-                fail_out.push_str(&format!("---- {} stdout ----\n\t", f.name));
-                let output = String::from_utf8_lossy(stdout);
-                fail_out.push_str(&output);
-                fail_out.push_str("\n");
-            }
-        }
-        if !fail_out.is_empty() {
-            self.write_plain("\n")?;
-            self.write_plain(&fail_out)?;
-        }
-
-        self.write_plain("\nfailures:\n")?;
-        failures.sort();
-        for name in &failures {
-            self.write_plain(&format!("    {}\n", name))?;
-        }
-        Ok(())
-    }
-
-    pub fn write_outputs(&mut self) -> io::Result<()> {
-        self.write_plain("\nsuccesses:\n")?;
-        let mut successes = Vec::new();
-        let mut stdouts = String::new();
-        for &(ref f, ref stdout) in &self.not_failures {
-            successes.push(f.name.to_string());
-            if !stdout.is_empty() {
-                stdouts.push_str(&format!("---- {} stdout ----\n\t", f.name));
-                let output = String::from_utf8_lossy(stdout);
-                stdouts.push_str(&output);
-                stdouts.push_str("\n");
-            }
-        }
-        if !stdouts.is_empty() {
-            self.write_plain("\n")?;
-            self.write_plain(&stdouts)?;
-        }
-
-        self.write_plain("\nsuccesses:\n")?;
-        successes.sort();
-        for name in &successes {
-            self.write_plain(&format!("    {}\n", name))?;
-        }
-        Ok(())
-    }
-
     fn current_test_count(&self) -> usize {
         self.passed + self.failed + self.ignored + self.measured + self.allowed_fail
     }
-
-    pub fn write_run_finish(&mut self) -> io::Result<bool> {
-        assert!(self.current_test_count() == self.total);
-
-        if self.options.display_output {
-            self.write_outputs()?;
-        }
-        let success = self.failed == 0;
-        if !success {
-            self.write_failures()?;
-        }
-
-        self.write_plain("\ntest result: ")?;
-        if success {
-            // There's no parallelism at this point so it's safe to use color
-            self.write_pretty("ok", term::color::GREEN)?;
-        } else {
-            self.write_pretty("FAILED", term::color::RED)?;
-        }
-        let s = if self.allowed_fail > 0 {
-            format!(
-                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
-                self.passed,
-                self.failed + self.allowed_fail,
-                self.allowed_fail,
-                self.ignored,
-                self.measured,
-                self.filtered_out)
-        } else {
-            format!(
-                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out\n\n",
-                self.passed,
-                self.failed,
-                self.ignored,
-                self.measured,
-                self.filtered_out)
-        };
-        self.write_plain(&s)?;
-        return Ok(success);
-    }
 }
 
 // Format a number with thousands separators
@@ -842,7 +952,13 @@ pub fn fmt_bench_samples(bs: &BenchSamples) -> String {
 
 // List the tests to console, and optionally to logfile. Filters are honored.
 pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<()> {
-    let mut st = ConsoleTestState::new(opts, None::<io::Stdout>)?;
+    let mut output = match term::stdout() {
+        None => Raw(io::stdout()),
+        Some(t) => Pretty(t),
+    };
+
+    let quiet = opts.format == OutputFormat::Terse;
+    let mut st = ConsoleTestState::new(opts)?;
 
     let mut ntest = 0;
     let mut nbench = 0;
@@ -857,7 +973,7 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
             StaticBenchFn(..) | DynBenchFn(..) => { nbench += 1; "benchmark" },
         };
 
-        st.write_plain(format!("{}: {}\n", name, fntype))?;
+        writeln!(output, "{}: {}", name, fntype)?;
         st.write_log(format!("{} {}\n", fntype, name))?;
     }
 
@@ -868,13 +984,13 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
         }
     }
 
-    if !opts.quiet {
+    if !quiet {
         if ntest != 0 || nbench != 0 {
-            st.write_plain("\n")?;
+            writeln!(output, "")?;
         }
-        st.write_plain(format!("{}, {}\n",
+        writeln!(output, "{}, {}",
             plural(ntest, "test"),
-            plural(nbench, "benchmark")))?;
+            plural(nbench, "benchmark"))?;
     }
 
     Ok(())
@@ -883,15 +999,38 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
 // A simple console test runner
 pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<bool> {
 
-    fn callback<T: Write>(event: &TestEvent, st: &mut ConsoleTestState<T>) -> io::Result<()> {
+    fn callback(event: &TestEvent,
+                st: &mut ConsoleTestState,
+                outs: &mut [Box<OutputFormatter>]) -> io::Result<()> {
         match (*event).clone() {
-            TeFiltered(ref filtered_tests) => st.write_run_start(filtered_tests.len()),
+            TeFiltered(ref filtered_tests) => {
+                st.total = filtered_tests.len();
+                for out in outs.iter_mut() {
+                    out.write_run_start(filtered_tests.len(), st.shuffle_seed)?;
+                }
+                Ok(())
+            }
             TeFilteredOut(filtered_out) => Ok(st.filtered_out = filtered_out),
-            TeWait(ref test, padding) => st.write_test_start(test, padding),
-            TeTimeout(ref test) => st.write_timeout(test),
-            TeResult(test, result, stdout) => {
+            TeWait(ref test, padding) => {
+                for out in outs.iter_mut() {
+                    out.write_test_start(test, padding)?;
+                }
+                Ok(())
+            }
+            TeTimeout(ref test) => {
+                for out in outs.iter_mut() {
+                    out.write_timeout(test)?;
+                }
+                Ok(())
+            }
+            TeResult(test, result, exec_time, stdout) => {
                 st.write_log_result(&test, &result)?;
-                st.write_result(&result)?;
+                for out in outs.iter_mut() {
+                    out.write_result(&test, &result, exec_time, &*stdout, st)?;
+                }
+                if st.is_slow(&result, exec_time) {
+                    st.slow.push((test.clone(), exec_time));
+                }
                 match result {
                     TrOk => {
                         st.passed += 1;
@@ -900,6 +1039,7 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
                     TrIgnored => st.ignored += 1,
                     TrAllowedFail => st.allowed_fail += 1,
                     TrBench(bs) => {
+                        st.bench_results.insert(test.name.as_slice(), &bs);
                         st.metrics.insert_metric(test.name.as_slice(),
                                                  bs.ns_iter_summ.median,
                                                  bs.ns_iter_summ.max - bs.ns_iter_summ.min);
@@ -923,19 +1063,80 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         }
     }
 
-    let mut st = ConsoleTestState::new(opts, None::<io::Stdout>)?;
+    let output = match term::stdout() {
+        None => Raw(io::stdout()),
+        Some(t) => Pretty(t),
+    };
+
     fn len_if_padded(t: &TestDescAndFn) -> usize {
         match t.testfn.padding() {
             PadNone => 0,
             PadOnRight => t.desc.name.as_slice().len(),
         }
     }
-    if let Some(t) = tests.iter().max_by_key(|t| len_if_padded(*t)) {
-        let n = t.desc.name.as_slice();
-        st.max_name_len = n.len();
+    let max_name_len = match tests.iter().max_by_key(|t| len_if_padded(*t)) {
+        Some(t) => t.desc.name.as_slice().len(),
+        None => 0,
+    };
+
+    let out: Box<OutputFormatter> = match opts.format {
+        OutputFormat::Pretty => Box::new(HumanFormatter::new(output,
+                                                             use_color(opts),
+                                                             false,
+                                                             max_name_len)),
+        OutputFormat::Terse => Box::new(HumanFormatter::new(output,
+                                                            use_color(opts),
+                                                            true,
+                                                            max_name_len)),
+        OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+    };
+    let mut outs = vec![out];
+
+    if let Some(ref path) = opts.report {
+        let file = Raw(File::create(path)?);
+        let report: Box<OutputFormatter> = match opts.report_format {
+            ReportFormat::Junit => Box::new(JunitFormatter::new(file, suite_name())),
+            ReportFormat::Json => Box::new(JsonFormatter::new(file)),
+        };
+        outs.push(report);
+    }
+
+    let mut st = ConsoleTestState::new(opts)?;
+    if let Some(ref name) = opts.baseline {
+        let path = baseline_path(name)?;
+        st.baseline = Some(Baseline::load(&path).map_err(|e| {
+            io::Error::new(e.kind(), format!("could not read benchmark baseline {}: {}",
+                                             path.display(), e))
+        })?);
+    }
+
+    run_tests(opts, tests, |x| callback(&x, &mut st, &mut outs))?;
+
+    assert!(st.current_test_count() == st.total);
+
+    // The first formatter is the console one; reports are written after it
+    // has printed its summary.
+    let mut success = true;
+    for out in &mut outs {
+        success &= out.write_run_finish(&st)?;
+    }
+
+    if let Some(ref name) = opts.save_baseline {
+        if !st.bench_results.is_empty() {
+            st.bench_results.save(&baseline_path(name)?)?;
+        }
+    }
+    return Ok(success);
+}
+
+// The name of the test suite as shown in reports, taken from the name of
+// the test binary.
+fn suite_name() -> String {
+    let exe = env::current_exe().unwrap_or_default();
+    match exe.file_stem() {
+        Some(stem) => stem.to_string_lossy().into_owned(),
+        None => "test".to_owned(),
     }
-    run_tests(opts, tests, |x| callback(&x, &mut st))?;
-    return st.write_run_finish();
 }
 
 #[test]
@@ -945,6 +1146,7 @@ fn should_sort_failures_before_printing_them() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
     };
 
     let test_b = TestDesc {
@@ -952,13 +1154,13 @@ fn should_sort_failures_before_printing_them() {
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
+        timeout: None,
     };
 
-    let mut st = ConsoleTestState {
+    let mut out = HumanFormatter::new(Raw(Vec::new()), false, false, 10);
+
+    let st = ConsoleTestState {
         log_out: None,
-        out: Raw(Vec::new()),
-        use_color: false,
-        quiet: false,
         total: 0,
         passed: 0,
         failed: 0,
@@ -966,17 +1168,22 @@ fn should_sort_failures_before_printing_them() {
         allowed_fail: 0,
         filtered_out: 0,
         measured: 0,
-        max_name_len: 10,
         metrics: MetricMap::new(),
         failures: vec![(test_b, Vec::new()), (test_a, Vec::new())],
         options: Options::new(),
         not_failures: Vec::new(),
+        slow: Vec::new(),
+        report_time: false,
+        slow_threshold: None,
+        shuffle_seed: None,
+        baseline: None,
+        bench_results: Baseline::new(),
     };
 
-    st.write_failures().unwrap();
-    let s = match st.out {
-        Raw(ref m) => String::from_utf8_lossy(&m[..]),
-        Pretty(_) => unreachable!(),
+    out.write_failures(&st).unwrap();
+    let s = match out.output_location() {
+        &Raw(ref m) => String::from_utf8_lossy(&m[..]),
+        &Pretty(_) => unreachable!(),
     };
 
     let apos = s.find("a").unwrap();
@@ -1027,7 +1234,7 @@ fn stdout_isatty() -> bool {
 pub enum TestEvent {
     TeFiltered(Vec<TestDesc>),
     TeWait(TestDesc, NamePadding),
-    TeResult(TestDesc, TestResult, Vec<u8>),
+    TeResult(TestDesc, TestResult, Duration, Vec<u8>),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
 }
@@ -1038,8 +1245,8 @@ pub type MonitorMsg = (TestDesc, TestResult, Vec<u8>);
 pub fn run_tests<F>(opts: &TestOpts, tests: Vec<TestDescAndFn>, mut callback: F) -> io::Result<()>
     where F: FnMut(TestEvent) -> io::Result<()>
 {
-    use std::collections::HashMap;
-    use std::sync::mpsc::RecvTimeoutError;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::mpsc::{Receiver, RecvTimeoutError};
 
     let tests_len = tests.len();
 
@@ -1048,6 +1255,10 @@ pub fn run_tests<F>(opts: &TestOpts, tests: Vec<TestDescAndFn>, mut callback: F)
         filtered_tests = convert_benchmarks_to_tests(filtered_tests);
     }
 
+    if let Some(seed) = opts.shuffle_seed {
+        shuffle_tests(seed, &mut filtered_tests);
+    }
+
     let filtered_out = tests_len - filtered_tests.len();
     callback(TeFilteredOut(filtered_out))?;
 
@@ -1077,6 +1288,14 @@ pub fn run_tests<F>(opts: &TestOpts, tests: Vec<TestDescAndFn>, mut callback: F)
     let (tx, rx) = channel::<MonitorMsg>();
 
     let mut running_tests: HashMap<TestDesc, Instant> = HashMap::new();
+    let mut start_times: HashMap<TestDesc, Instant> = HashMap::new();
+    // Hard timeouts of the tests running on a thread of this process, and the
+    // tests which were given up on after exceeding theirs. Such a test may
+    // never finish, so it no longer counts towards the running tests, and its
+    // result is dropped if it shows up. Tests run in a child process are
+    // killed by `run_test` instead.
+    let mut deadlines: HashMap<TestDesc, Instant> = HashMap::new();
+    let mut abandoned: HashSet<TestDesc> = HashSet::new();
 
     fn get_timed_out_tests(running_tests: &mut HashMap<TestDesc, Instant>) -> Vec<TestDesc> {
         let now = Instant::now();
@@ -1089,55 +1308,106 @@ pub fn run_tests<F>(opts: &TestOpts, tests: Vec<TestDescAndFn>, mut callback: F)
         timed_out
     };
 
-    fn calc_timeout(running_tests: &HashMap<TestDesc, Instant>) -> Option<Duration> {
-        running_tests.values().min().map(|next_timeout| {
-            let now = Instant::now();
-            if *next_timeout >= now {
-                *next_timeout - now
-            } else {
-                Duration::new(0, 0)
-            }})
+    // Receives the next result of a test which hasn't been given up on,
+    // dropping those of the abandoned tests which finish after all.
+    fn recv_result(rx: &Receiver<MonitorMsg>,
+                   abandoned: &mut HashSet<TestDesc>,
+                   deadline: Option<Instant>) -> Result<MonitorMsg, RecvTimeoutError> {
+        loop {
+            let msg = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    let timeout = if deadline >= now {
+                        deadline - now
+                    } else {
+                        Duration::new(0, 0)
+                    };
+                    rx.recv_timeout(timeout)?
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected)?,
+            };
+            if !abandoned.remove(&msg.0) {
+                return Ok(msg);
+            }
+        }
     };
 
     if concurrency == 1 {
         while !remaining.is_empty() {
             let test = remaining.pop().unwrap();
             callback(TeWait(test.desc.clone(), test.testfn.padding()))?;
+            let desc = test.desc.clone();
+            let start = Instant::now();
+            let deadline = if runs_in_subprocess(opts, &test.desc, &test.testfn) {
+                None
+            } else {
+                test_timeout(opts, &desc).map(|timeout| start + timeout)
+            };
             run_test(opts, !opts.run_tests, test, tx.clone());
-            let (test, result, stdout) = rx.recv().unwrap();
-            callback(TeResult(test, result, stdout))?;
+            let (test, result, stdout) = match recv_result(&rx, &mut abandoned, deadline) {
+                Err(RecvTimeoutError::Timeout) => {
+                    abandoned.insert(desc.clone());
+                    TESTS_ABANDONED.store(true, Ordering::SeqCst);
+                    let result = calc_timeout_result(&desc, test_timeout(opts, &desc).unwrap());
+                    (desc, result, Vec::new())
+                }
+                res => res.unwrap(),
+            };
+            let exec_time = start.elapsed();
+            let result = calc_time_result(opts, &test, result, exec_time);
+            callback(TeResult(test, result, exec_time, stdout))?;
         }
     } else {
         while pending > 0 || !remaining.is_empty() {
             while pending < concurrency && !remaining.is_empty() {
                 let test = remaining.pop().unwrap();
-                let timeout = Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S);
+                let now = Instant::now();
+                let timeout = now + Duration::from_secs(TEST_WARN_TIMEOUT_S);
                 running_tests.insert(test.desc.clone(), timeout);
+                start_times.insert(test.desc.clone(), now);
+                match test_timeout(opts, &test.desc) {
+                    Some(timeout) if !runs_in_subprocess(opts, &test.desc, &test.testfn) => {
+                        deadlines.insert(test.desc.clone(), now + timeout);
+                    }
+                    _ => {}
+                }
                 run_test(opts, !opts.run_tests, test, tx.clone());
                 pending += 1;
             }
 
-            let mut res;
-            loop {
-                if let Some(timeout) = calc_timeout(&running_tests) {
-                    res = rx.recv_timeout(timeout);
-                    for test in get_timed_out_tests(&mut running_tests) {
-                        callback(TeTimeout(test))?;
-                    }
-                    if res != Err(RecvTimeoutError::Timeout) {
-                        break;
-                    }
-                } else {
-                    res = rx.recv().map_err(|_| RecvTimeoutError::Disconnected);
-                    break;
-                }
+            // Wake up for whichever comes first of a result, a warning about a
+            // long running test and a test exceeding its hard timeout.
+            let next_wakeup = running_tests.values().chain(deadlines.values()).min().cloned();
+            let res = recv_result(&rx, &mut abandoned, next_wakeup);
+            if let Ok(ref msg) = res {
+                deadlines.remove(&msg.0);
+            }
+
+            for test in get_timed_out_tests(&mut running_tests) {
+                callback(TeTimeout(test))?;
+            }
+            for desc in get_timed_out_tests(&mut deadlines) {
+                running_tests.remove(&desc);
+                let exec_time = start_times.remove(&desc).unwrap().elapsed();
+                let result = calc_timeout_result(&desc, test_timeout(opts, &desc).unwrap());
+                abandoned.insert(desc.clone());
+                TESTS_ABANDONED.store(true, Ordering::SeqCst);
+
+                callback(TeWait(desc.clone(), PadNone))?;
+                callback(TeResult(desc, result, exec_time, Vec::new()))?;
+                pending -= 1;
             }
 
-            let (desc, result, stdout) = res.unwrap();
+            let (desc, result, stdout) = match res {
+                Err(RecvTimeoutError::Timeout) => continue,
+                res => res.unwrap(),
+            };
             running_tests.remove(&desc);
+            let exec_time = start_times.remove(&desc).unwrap().elapsed();
+            let result = calc_time_result(opts, &desc, result, exec_time);
 
             callback(TeWait(desc.clone(), PadNone))?;
-            callback(TeResult(desc, result, stdout))?;
+            callback(TeResult(desc, result, exec_time, stdout))?;
             pending -= 1;
         }
     }
@@ -1146,9 +1416,12 @@ pub fn run_tests<F>(opts: &TestOpts, tests: Vec<TestDescAndFn>, mut callback: F)
         // All benchmarks run at the end, in serial.
         for b in filtered_benchs {
             callback(TeWait(b.desc.clone(), b.testfn.padding()))?;
+            let start = Instant::now();
             run_test(opts, false, b, tx.clone());
-            let (test, result, stdout) = rx.recv().unwrap();
-            callback(TeResult(test, result, stdout))?;
+            let (test, result, stdout) = recv_result(&rx, &mut abandoned, None).unwrap();
+            let exec_time = start.elapsed();
+            let result = calc_time_result(opts, &test, result, exec_time);
+            callback(TeResult(test, result, exec_time, stdout))?;
         }
     }
     Ok(())
@@ -1326,12 +1599,48 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         filtered.into_iter().filter_map(filter).collect()
     };
 
+    // Only keep the tests which belong to the selected shard
+    if opts.shard_count > 1 {
+        filtered = filtered.into_iter()
+            .filter(|t| shard_for_test(&t.desc.name, opts.shard_count) == opts.shard_index)
+            .collect();
+    }
+
     // Sort the tests alphabetically
     filtered.sort_by(|t1, t2| t1.desc.name.as_slice().cmp(t2.desc.name.as_slice()));
 
     filtered
 }
 
+// Assigns a test to a shard based on an FNV-1a hash of its name, which unlike
+// `DefaultHasher` is guaranteed to be the same on every platform and release.
+fn shard_for_test(name: &TestName, shard_count: usize) -> usize {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in name.as_slice().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    (hash % shard_count as u64) as usize
+}
+
+// Fisher-Yates shuffle driven by a splitmix64 generator, so that a given seed
+// always produces the same order.
+fn shuffle_tests(seed: u64, tests: &mut [TestDescAndFn]) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+
+    for i in (1..tests.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        tests.swap(i, j);
+    }
+}
+
 pub fn convert_benchmarks_to_tests(tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
     // convert benchmarks to tests, if we're not benchmarking them
     tests.into_iter().map(|x| {
@@ -1414,9 +1723,11 @@ pub fn run_test(opts: &TestOpts,
                 io::set_panic(panicio);
             };
 
-            let test_result = calc_result(&desc, result);
+            let test_result = calc_result(&desc, result.as_ref().map(|_| ()).map_err(|e| &**e));
             let stdout = data.lock().unwrap().to_vec();
-            monitor_ch.send((desc.clone(), test_result, stdout)).unwrap();
+            // The receiving end is gone if this test exceeded its timeout and
+            // the test run has finished in the meantime.
+            let _ = monitor_ch.send((desc.clone(), test_result, stdout));
         };
 
 
@@ -1437,6 +1748,12 @@ pub fn run_test(opts: &TestOpts,
         }
     }
 
+    if runs_in_subprocess(opts, &desc, &testfn) {
+        let timeout = test_timeout(opts, &desc);
+        spawn_test_subprocess(desc, monitor_ch, opts.nocapture, timeout);
+        return;
+    }
+
     match testfn {
         DynBenchFn(bencher) => {
             let bs = ::bench::benchmark(|harness| bencher.run(harness));
@@ -1466,7 +1783,151 @@ fn __rust_begin_short_backtrace<F: FnOnce()>(f: F) {
     f()
 }
 
-fn calc_result(desc: &TestDesc, task_result: Result<(), Box<Any + Send>>) -> TestResult {
+// Whether `run_test` runs the test in a child process: to isolate it with
+// `--process-per-test`, or to be able to kill it once it exceeds its timeout.
+// That takes a harness which can run the test in a copy of itself.
+fn runs_in_subprocess(opts: &TestOpts, desc: &TestDesc, testfn: &TestFn) -> bool {
+    let supports_processes =
+        !cfg!(target_os = "emscripten") &&
+        !cfg!(target_arch = "wasm32");
+    let is_bench = match *testfn {
+        StaticBenchFn(_) | DynBenchFn(_) => true,
+        StaticTestFn(_) | DynTestFn(_) => false,
+    };
+    opts.options.harness && supports_processes && !is_bench &&
+        (opts.process_per_test || test_timeout(opts, desc).is_some())
+}
+
+fn spawn_test_subprocess(desc: TestDesc,
+                         monitor_ch: Sender<MonitorMsg>,
+                         nocapture: bool,
+                         timeout: Option<Duration>) {
+    let name = desc.name.clone();
+    let runtest = move || {
+        let (result, output) = match run_test_subprocess(&desc, nocapture, timeout) {
+            Ok(res) => res,
+            Err(e) => {
+                let msg = format!("failed to spawn a process for the test: {}", e);
+                (TrFailedMsg(msg), Vec::new())
+            }
+        };
+        let _ = monitor_ch.send((desc, result, output));
+    };
+    let cfg = thread::Builder::new().name(match name {
+        DynTestName(ref name) => name.clone(),
+        StaticTestName(name) => name.to_owned(),
+    });
+    cfg.spawn(runtest).unwrap();
+}
+
+// Runs the test in a new process of the current executable, which picks the
+// test to run from `SECONDARY_TEST_INVOKER_VAR` and reports its result through
+// its exit code. The process is killed once the test exceeds `timeout`.
+fn run_test_subprocess(desc: &TestDesc,
+                       nocapture: bool,
+                       timeout: Option<Duration>) -> io::Result<(TestResult, Vec<u8>)> {
+    let mut command = Command::new(env::current_exe()?);
+    command.env(SECONDARY_TEST_INVOKER_VAR, desc.name.as_slice());
+    if nocapture {
+        command.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+    } else {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+
+    // Read the output on separate threads so that a test writing lots of it
+    // can't block on a full pipe while we wait for it to exit.
+    fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut output = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut output);
+            }
+            output
+        })
+    }
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let status = match timeout {
+        Some(timeout) => wait_with_timeout(&mut child, timeout)?,
+        None => Some(child.wait()?),
+    };
+    let mut output = stdout.join().unwrap_or_default();
+    output.extend(stderr.join().unwrap_or_default());
+
+    let result = match status {
+        Some(status) => calc_subprocess_result(desc, status),
+        None => calc_timeout_result(desc, timeout.unwrap()),
+    };
+    Ok((result, output))
+}
+
+// Waits for the child to exit, or kills it and returns `None` if it is still
+// running after `timeout`.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn calc_subprocess_result(desc: &TestDesc, status: ExitStatus) -> TestResult {
+    match status.code() {
+        Some(TR_OK) => TrOk,
+        _ if desc.allow_fail => TrAllowedFail,
+        Some(TR_FAILED) => TrFailed,
+        _ => TrFailedMsg(format!("test process terminated abnormally ({})", status)),
+    }
+}
+
+// Runs the test picked by the parent process and exits with its result. The
+// result is worked out from a panic hook rather than with `catch_unwind`, so
+// that `#[should_panic]` tests also work with `-C panic=abort`.
+fn run_test_in_spawned_subprocess(test: TestDescAndFn) -> ! {
+    let TestDescAndFn { desc, testfn } = convert_benchmarks_to_tests(vec![test]).pop().unwrap();
+
+    // Panics on other threads don't fail the test unless they make it to the
+    // test's own thread, just like when it runs in the harness process.
+    let test_thread = thread::current().id();
+    let builtin_panic_hook = panic::take_hook();
+    let hook_desc = desc.clone();
+    panic::set_hook(Box::new(move |info| {
+        builtin_panic_hook(info);
+        if thread::current().id() != test_thread {
+            return;
+        }
+        let result = calc_result(&hook_desc, Err(info.payload()));
+        if let TrFailedMsg(ref msg) = result {
+            eprintln!("{}", msg);
+        }
+        process::exit(if result == TrOk { TR_OK } else { TR_FAILED });
+    }));
+
+    match testfn {
+        StaticTestFn(f) => __rust_begin_short_backtrace(f),
+        DynTestFn(f) => __rust_begin_short_backtrace(|| f.call_box(())),
+        StaticBenchFn(_) | DynBenchFn(_) => unreachable!(),
+    }
+
+    let result = calc_result(&desc, Ok(()));
+    if result == TrOk {
+        process::exit(TR_OK);
+    } else {
+        eprintln!("test did not panic as expected");
+        process::exit(TR_FAILED);
+    }
+}
+
+fn calc_result(desc: &TestDesc, task_result: Result<(), &(Any + Send)>) -> TestResult {
     match (&desc.should_panic, task_result) {
         (&ShouldPanic::No, Ok(())) |
         (&ShouldPanic::Yes, Err(_)) => TrOk,
@@ -1489,6 +1950,41 @@ fn calc_result(desc: &TestDesc, task_result: Result<(), Box<Any + Send>>) -> Tes
     }
 }
 
+// The hard time limit of a test: its own `#[test_timeout]` attribute, or else the
+// `--test-timeout` given on the command line.
+fn test_timeout(opts: &TestOpts, desc: &TestDesc) -> Option<Duration> {
+    desc.timeout.map(Duration::from_secs).or(opts.test_timeout)
+}
+
+fn calc_timeout_result(desc: &TestDesc, timeout: Duration) -> TestResult {
+    if desc.allow_fail {
+        TrAllowedFail
+    } else {
+        TrFailedMsg(format!("test did not finish within its timeout of {}s", timeout.as_secs()))
+    }
+}
+
+// With `--fail-slow`, turns a passing test which ran for longer than the slow
+// test threshold into a failure.
+fn calc_time_result(opts: &TestOpts,
+                    desc: &TestDesc,
+                    result: TestResult,
+                    exec_time: Duration) -> TestResult {
+    match (opts.slow_threshold, result) {
+        (Some(threshold), TrOk) if opts.fail_slow && exec_time > threshold => {
+            if desc.allow_fail {
+                TrAllowedFail
+            } else {
+                TrFailedMsg(format!("test ran for {:.3}s, exceeding the slow test threshold \
+                                     of {:.3}s",
+                                    secs_from_dur(exec_time),
+                                    secs_from_dur(threshold)))
+            }
+        }
+        (_, result) => result,
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct MetricMap(BTreeMap<String, Metric>);
 
@@ -1574,6 +2070,10 @@ fn ns_from_dur(dur: Duration) -> u64 {
     dur.as_secs() * 1_000_000_000 + (dur.subsec_nanos() as u64)
 }
 
+fn secs_from_dur(dur: Duration) -> f64 {
+    dur.as_secs() as f64 + dur.subsec_nanos() as f64 / 1_000_000_000.0
+}
+
 fn ns_iter_inner<T, F>(inner: &mut F, k: u64) -> u64
     where F: FnMut() -> T
 {
@@ -1585,6 +2085,9 @@ fn ns_iter_inner<T, F>(inner: &mut F, k: u64) -> u64
 }
 
 
+// The number of samples each benchmark summary is computed from
+const BENCH_SAMPLES: usize = 50;
+
 pub fn iter<T, F>(inner: &mut F) -> stats::Summary
     where F: FnMut() -> T
 {
@@ -1604,7 +2107,7 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
     n = cmp::max(1, n);
 
     let mut total_run = Duration::new(0, 0);
-    let samples: &mut [f64] = &mut [0.0_f64; 50];
+    let samples: &mut [f64] = &mut [0.0_f64; BENCH_SAMPLES];
     loop {
         let loop_start = Instant::now();
 
@@ -1654,7 +2157,7 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
 pub mod bench {
     use std::cmp;
     use stats;
-    use super::{Bencher, BenchSamples, BenchMode};
+    use super::{Bencher, BenchSamples, BenchMode, BENCH_SAMPLES};
 
     pub fn benchmark<F>(f: F) -> BenchSamples
         where F: FnMut(&mut Bencher)
@@ -1672,6 +2175,7 @@ pub mod bench {
 
                 BenchSamples {
                     ns_iter_summ,
+                    samples: BENCH_SAMPLES,
                     mb_s: mb_s as usize,
                 }
             }
@@ -1681,6 +2185,7 @@ pub mod bench {
                 let samples: &mut [f64] = &mut [0.0_f64; 1];
                 BenchSamples {
                     ns_iter_summ: stats::Summary::new(samples),
+                    samples: 0,
                     mb_s: 0,
                 }
             }
@@ -1703,10 +2208,15 @@ pub mod bench {
 mod tests {
     use test::{TrFailed, TrFailedMsg, TrIgnored, TrOk, filter_tests, parse_opts, TestDesc,
                TestDescAndFn, TestOpts, run_test, MetricMap, StaticTestName, DynTestName,
-               DynTestFn, ShouldPanic};
+               DynTestFn, ShouldPanic, OutputFormat};
     use std::sync::mpsc::channel;
+    use std::thread;
     use bench;
     use Bencher;
+    use {Raw, Pretty};
+    use formatters::{OutputFormatter, HumanFormatter, JsonFormatter, JunitFormatter};
+    use std::time::Duration;
+    use {ConsoleTestState, calc_time_result, shuffle_tests, run_tests, TeResult};
 
     #[test]
     pub fn do_not_run_ignored_tests() {
@@ -1719,6 +2229,7 @@ mod tests {
                 ignore: true,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move |()| f())),
         };
@@ -1737,6 +2248,7 @@ mod tests {
                 ignore: true,
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move |()| f())),
         };
@@ -1757,6 +2269,7 @@ mod tests {
                 ignore: false,
                 should_panic: ShouldPanic::Yes,
                 allow_fail: false,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move |()| f())),
         };
@@ -1777,6 +2290,7 @@ mod tests {
                 ignore: false,
                 should_panic: ShouldPanic::YesWithMessage("error message"),
                 allow_fail: false,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move |()| f())),
         };
@@ -1799,6 +2313,7 @@ mod tests {
                 ignore: false,
                 should_panic: ShouldPanic::YesWithMessage(expected),
                 allow_fail: false,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move |()| f())),
         };
@@ -1817,6 +2332,7 @@ mod tests {
                 ignore: false,
                 should_panic: ShouldPanic::Yes,
                 allow_fail: false,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move |()| f())),
         };
@@ -1826,6 +2342,104 @@ mod tests {
         assert!(res == TrFailed);
     }
 
+    #[test]
+    fn hung_tests_time_out() {
+        use std::sync::{Arc, Mutex};
+
+        // One test per test thread which doesn't return before the run is
+        // over, so the last test only gets a thread if the hung ones are
+        // given up on.
+        fn tests(threads: usize, hold: &Arc<Mutex<()>>) -> Vec<TestDescAndFn> {
+            let mut tests = (0..threads).map(|i| {
+                let hold = hold.clone();
+                TestDescAndFn {
+                    desc: TestDesc {
+                        name: DynTestName(format!("hangs{}", i)),
+                        ignore: false,
+                        should_panic: ShouldPanic::No,
+                        allow_fail: false,
+                        timeout: None,
+                    },
+                    testfn: DynTestFn(Box::new(move |()| drop(hold.lock()))),
+                }
+            }).collect::<Vec<_>>();
+            tests.push(TestDescAndFn {
+                desc: TestDesc {
+                    name: StaticTestName("passes"),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                    timeout: None,
+                },
+                testfn: DynTestFn(Box::new(move |()| {})),
+            });
+            tests
+        }
+
+        for &threads in &[1, 2] {
+            let opts = TestOpts {
+                run_tests: true,
+                test_threads: Some(threads),
+                test_timeout: Some(Duration::from_millis(100)),
+                ..TestOpts::new()
+            };
+            let hold = Arc::new(Mutex::new(()));
+            let guard = hold.lock().unwrap();
+            let mut results = Vec::new();
+            run_tests(&opts, tests(threads, &hold), |event| {
+                if let TeResult(desc, result, _, _) = event {
+                    results.push((desc.name.to_string(), result));
+                }
+                Ok(())
+            }).unwrap();
+            drop(guard);
+            results.sort_by(|a, b| a.0.cmp(&b.0));
+
+            assert_eq!(results.len(), threads + 1);
+            for &(_, ref result) in &results[..threads] {
+                assert!(match *result {
+                    TrFailedMsg(ref msg) => msg.contains("timeout"),
+                    _ => false,
+                });
+            }
+            assert!(results[threads].1 == TrOk);
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn subprocess_exit_status_maps_to_result() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+        use {calc_subprocess_result, TrAllowedFail, TR_OK, TR_FAILED};
+
+        let desc = TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            timeout: None,
+        };
+        // Raw wait statuses: the exit code is in the second byte, while a low
+        // byte of 6 means the process was killed by SIGABRT.
+        let exited = |code: i32| ExitStatus::from_raw(code << 8);
+        let aborted = ExitStatus::from_raw(6);
+
+        assert!(calc_subprocess_result(&desc, exited(TR_OK)) == TrOk);
+        assert!(calc_subprocess_result(&desc, exited(TR_FAILED)) == TrFailed);
+        assert!(match calc_subprocess_result(&desc, exited(0)) {
+            TrFailedMsg(_) => true,
+            _ => false,
+        });
+        assert!(match calc_subprocess_result(&desc, aborted) {
+            TrFailedMsg(_) => true,
+            _ => false,
+        });
+
+        let desc = TestDesc { allow_fail: true, ..desc };
+        assert!(calc_subprocess_result(&desc, aborted) == TrAllowedFail);
+    }
+
     #[test]
     fn parse_ignored_flag() {
         let args = vec!["progname".to_string(), "filter".to_string(), "--ignored".to_string()];
@@ -1836,6 +2450,151 @@ mod tests {
         assert!((opts.run_ignored));
     }
 
+    #[test]
+    fn parse_format_flag() {
+        let args = vec!["progname".to_string(), "--format".to_string(), "json".to_string()];
+        let opts = match parse_opts(&args) {
+            Some(Ok(o)) => o,
+            _ => panic!("Malformed arg in parse_format_flag"),
+        };
+        assert_eq!(opts.format, OutputFormat::Json);
+
+        let args = vec!["progname".to_string(), "-q".to_string()];
+        let opts = match parse_opts(&args) {
+            Some(Ok(o)) => o,
+            _ => panic!("Malformed arg in parse_format_flag"),
+        };
+        assert_eq!(opts.format, OutputFormat::Terse);
+
+        let args = vec!["progname".to_string(), "--format".to_string(), "xml".to_string()];
+        assert!(match parse_opts(&args) {
+            Some(Err(_)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn json_format_escapes_and_reports_stdout() {
+        let desc = TestDesc {
+            name: StaticTestName("a \"quoted\" test"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            timeout: None,
+        };
+        let st = ConsoleTestState::new(&TestOpts::new()).unwrap();
+        let mut out = JsonFormatter::new(Raw(Vec::new()));
+        out.write_result(&desc,
+                         &TrFailedMsg("bad\nthing".to_string()),
+                         Duration::from_millis(5),
+                         b"line\n",
+                         &st).unwrap();
+        let s = match out.output_location() {
+            &Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+            &Pretty(_) => unreachable!(),
+        };
+        assert_eq!(s, "{ \"type\": \"test\", \"name\": \"a \\\"quoted\\\" test\", \
+                       \"event\": \"failed\", \"stdout\": \"line\\n\", \
+                       \"message\": \"bad\\nthing\" }\n");
+    }
+
+    #[test]
+    fn junit_report_maps_results_to_testcases() {
+        fn desc(name: &'static str) -> TestDesc {
+            TestDesc {
+                name: StaticTestName(name),
+                ignore: false,
+                should_panic: ShouldPanic::No,
+                allow_fail: false,
+                timeout: None,
+            }
+        }
+
+        let mut st = ConsoleTestState::new(&TestOpts::new()).unwrap();
+        st.total = 3;
+        st.passed = 1;
+        st.failed = 1;
+        st.ignored = 1;
+
+        let mut out = JunitFormatter::new(Raw(Vec::new()), "suite".to_string());
+        out.write_result(&desc("a::passes"), &TrOk, Duration::from_millis(1500), b"", &st)
+           .unwrap();
+        out.write_result(&desc("a::fails"),
+                         &TrFailedMsg("<oops>".to_string()),
+                         Duration::from_millis(2),
+                         b"out & about",
+                         &st).unwrap();
+        out.write_result(&desc("top_level"), &TrIgnored, Duration::new(0, 0), b"", &st)
+           .unwrap();
+        assert!(!out.write_run_finish(&st).unwrap());
+
+        let s = match out.output_location() {
+            &Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+            &Pretty(_) => unreachable!(),
+        };
+        assert!(s.contains("<testsuite name=\"suite\" errors=\"0\" failures=\"1\" \
+                            skipped=\"1\" tests=\"3\">"));
+        assert!(s.contains(r#"<testcase classname="a" name="passes" time="1.500">"#));
+        assert!(s.contains(r#"<failure type="assert" message="&lt;oops&gt;"/>"#));
+        assert!(s.contains("<system-out>out &amp; about</system-out>"));
+        assert!(s.contains(r#"<testcase classname="suite" name="top_level" time="0.000">
+<skipped/>"#));
+    }
+
+    #[test]
+    fn parse_slow_threshold() {
+        let args = vec!["progname".to_string(),
+                        "--slow-threshold".to_string(),
+                        "250".to_string(),
+                        "--fail-slow".to_string()];
+        let opts = match parse_opts(&args) {
+            Some(Ok(o)) => o,
+            _ => panic!("Malformed arg in parse_slow_threshold"),
+        };
+        assert_eq!(opts.slow_threshold, Some(Duration::from_millis(250)));
+        assert!(opts.fail_slow);
+
+        let args = vec!["progname".to_string(), "--fail-slow".to_string()];
+        assert!(match parse_opts(&args) {
+            Some(Err(_)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn slow_tests_are_reported_and_optionally_failed() {
+        let desc = TestDesc {
+            name: StaticTestName("slow"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            timeout: None,
+        };
+        let opts = TestOpts {
+            report_time: true,
+            slow_threshold: Some(Duration::from_millis(100)),
+            ..TestOpts::new()
+        };
+
+        let st = ConsoleTestState::new(&opts).unwrap();
+        assert!(st.is_slow(&TrOk, Duration::from_millis(150)));
+        assert!(!st.is_slow(&TrOk, Duration::from_millis(50)));
+        assert!(!st.is_slow(&TrIgnored, Duration::from_millis(150)));
+
+        let mut out = HumanFormatter::new(Raw(Vec::new()), false, false, 0);
+        out.write_result(&desc, &TrOk, Duration::from_millis(150), b"", &st).unwrap();
+        let s = match out.output_location() {
+            &Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+            &Pretty(_) => unreachable!(),
+        };
+        assert_eq!(s, "ok <0.150s> (slow)\n");
+
+        assert!(calc_time_result(&opts, &desc, TrOk, Duration::from_millis(150)) == TrOk);
+        let opts = TestOpts { fail_slow: true, ..opts };
+        assert!(calc_time_result(&opts, &desc, TrOk, Duration::from_millis(50)) == TrOk);
+        assert!(calc_time_result(&opts, &desc, TrOk, Duration::from_millis(150)) != TrOk);
+    }
+
     #[test]
     pub fn filter_for_ignored_option() {
         // When we run ignored tests the test filter should filter out all the
@@ -1851,6 +2610,7 @@ mod tests {
                                  ignore: true,
                                  should_panic: ShouldPanic::No,
                                  allow_fail: false,
+                                 timeout: None,
                              },
                              testfn: DynTestFn(Box::new(move |()| {})),
                          },
@@ -1860,6 +2620,7 @@ mod tests {
                                  ignore: false,
                                  should_panic: ShouldPanic::No,
                                  allow_fail: false,
+                                 timeout: None,
                              },
                              testfn: DynTestFn(Box::new(move |()| {})),
                          }];
@@ -1884,6 +2645,7 @@ mod tests {
                     ignore: false,
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
+                    timeout: None,
                 },
                 testfn: DynTestFn(Box::new(move |()| {}))
             })
@@ -1942,6 +2704,96 @@ mod tests {
         assert_eq!(exact.len(), 1);
     }
 
+    #[test]
+    pub fn shard_filter_partitions_tests() {
+        fn tests() -> Vec<TestDescAndFn> {
+            (0..50).map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("module::test_{}", i)),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                    timeout: None,
+                },
+                testfn: DynTestFn(Box::new(move |()| {}))
+            })
+            .collect()
+        }
+
+        let mut seen = Vec::new();
+        for shard_index in 0..3 {
+            let shard = filter_tests(&TestOpts {
+                    filter: Some("module".into()),
+                    shard_index,
+                    shard_count: 3,
+                    ..TestOpts::new()
+                }, tests());
+            assert!(!shard.is_empty());
+            seen.extend(shard.into_iter().map(|t| t.desc.name.to_string()));
+        }
+        seen.sort();
+        let mut all = tests().into_iter().map(|t| t.desc.name.to_string()).collect::<Vec<_>>();
+        all.sort();
+        assert_eq!(seen, all);
+    }
+
+    #[test]
+    fn parse_shard_and_shuffle_flags() {
+        let args = vec!["progname".to_string(),
+                        "--shard-count=4".to_string(),
+                        "--shard-index=3".to_string(),
+                        "--shuffle=42".to_string()];
+        let opts = match parse_opts(&args) {
+            Some(Ok(o)) => o,
+            _ => panic!("Malformed arg in parse_shard_and_shuffle_flags"),
+        };
+        assert_eq!((opts.shard_index, opts.shard_count), (3, 4));
+        assert_eq!(opts.shuffle_seed, Some(42));
+
+        let args = vec!["progname".to_string(), "--shuffle".to_string()];
+        let opts = match parse_opts(&args) {
+            Some(Ok(o)) => o,
+            _ => panic!("Malformed arg in parse_shard_and_shuffle_flags"),
+        };
+        assert!(opts.shuffle_seed.is_some());
+
+        let args = vec!["progname".to_string(),
+                        "--shard-count=4".to_string(),
+                        "--shard-index=4".to_string()];
+        assert!(match parse_opts(&args) {
+            Some(Err(_)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn shuffle_is_deterministic_for_a_seed() {
+        fn shuffled(seed: u64) -> Vec<String> {
+            let mut tests = (0..20).map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("test_{}", i)),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                    timeout: None,
+                },
+                testfn: DynTestFn(Box::new(move |()| {}))
+            })
+            .collect::<Vec<_>>();
+            shuffle_tests(seed, &mut tests);
+            tests.into_iter().map(|t| t.desc.name.to_string()).collect()
+        }
+
+        assert_eq!(shuffled(1234), shuffled(1234));
+        assert!(shuffled(1234) != shuffled(4321));
+
+        let mut names = shuffled(1234);
+        names.sort();
+        let mut expected = (0..20).map(|i| format!("test_{}", i)).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(names, expected);
+    }
+
     #[test]
     pub fn sort_tests() {
         let mut opts = TestOpts::new();
@@ -1966,6 +2818,7 @@ mod tests {
                         ignore: false,
                         should_panic: ShouldPanic::No,
                         allow_fail: false,
+                        timeout: None,
                     },
                     testfn: DynTestFn(Box::new(move |()| testfn())),
                 };
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+/// See [`Missing`](struct.Missing.html), which isn't there,
+/// and [`Gone`](struct.Gone.html), which isn't either.
+pub struct Foo;
+
+/// Made with [`Bar::new`](struct.Bar.html#method.new).
+pub struct Bar;
+
+impl Bar {
+    /// Unlike [`Bar::missing`](struct.Bar.html#method.missing), which isn't there, or
+    /// [`Vec`](../std/vec/struct.Vec.html), which is another crate's.
+    pub fn new() -> Bar { Bar }
+}
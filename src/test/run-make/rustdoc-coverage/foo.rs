@@ -0,0 +1,48 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The crate is documented.
+
+#![crate_name = "foo"]
+
+/// Documented, with an example:
+///
+/// ```
+/// foo::documented();
+/// ```
+pub fn documented() {}
+
+pub fn undocumented() {}
+
+/// Documented, but text isn't an example:
+///
+/// ```text
+/// Struct { field: 1 }
+/// ```
+// Trait impls aren't counted
+#[derive(Clone)]
+pub struct Struct {
+    /// A documented field.
+    pub field: u32,
+}
+
+impl Struct {
+    pub fn undocumented_method(&self) {}
+}
+
+#[doc(hidden)]
+pub fn hidden() {}
+
+fn private() {}
+
+pub mod inner {
+    /// Documented.
+    pub struct Inner;
+}
@@ -0,0 +1,57 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Adds two numbers.
+///
+/// ```
+/// assert_eq!(foo::add(1, 2), 3);
+/// ```
+///
+/// ```
+/// let sum = foo::add(2, 2);
+/// assert_eq!((file!(), line!()), ("foo.rs", 5));
+/// ```
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// ```
+/// let mut counter = foo::Counter::default();
+/// counter.count();
+/// assert_eq!(counter.0, 1);
+/// ```
+///
+/// ```should_panic
+/// let mut counter = foo::Counter(u32::max_value());
+/// counter.count();
+/// counter.count();
+/// ```
+///
+/// ```compile_fail
+/// let counter = foo::Counter::default();
+/// counter.count();
+/// ```
+#[derive(Default)]
+pub struct Counter(pub u32);
+
+impl Counter {
+    pub fn count(&mut self) {
+        self.0 = self.0.checked_add(1).unwrap();
+    }
+}
+
+/// ```
+/// #[cfg(test)]
+/// let in_harness = true;
+/// #[cfg(not(test))]
+/// let in_harness = false;
+/// assert!(!in_harness);
+/// ```
+pub fn alone() {}
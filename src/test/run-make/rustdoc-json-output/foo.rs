@@ -0,0 +1,39 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![stable(feature = "foo", since = "1.0.0")]
+
+/// A documented struct.
+#[stable(feature = "foo", since = "1.0.0")]
+pub struct Foo<T: Clone> {
+    #[stable(feature = "foo", since = "1.0.0")]
+    pub field: Vec<T>,
+}
+
+#[stable(feature = "foo", since = "1.0.0")]
+pub trait Trait {
+    #[stable(feature = "foo", since = "1.0.0")]
+    fn method(&self) -> Option<&str> where Self: Sized;
+}
+
+#[stable(feature = "foo", since = "1.0.0")]
+impl<T: Clone> Trait for Foo<T> {
+    fn method(&self) -> Option<&str> {
+        None
+    }
+}
+
+#[stable(feature = "foo", since = "1.0.0")]
+pub mod bar {
+    #[stable(feature = "foo", since = "1.0.0")]
+    pub struct Bar;
+}
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Frobnicates the input.
+pub fn frobnicate(x: u32) -> u32 {
+    x + 1
+}
+
+/// Nothing calls this.
+pub fn unused() {}
+
+pub struct Widget;
+
+impl Widget {
+    /// Turns the widget.
+    pub fn turn(&self) {}
+}
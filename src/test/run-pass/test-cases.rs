@@ -0,0 +1,63 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// compile-flags: --test
+
+#![feature(parameterized_tests)]
+
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+static TEARDOWNS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+struct Fixture {
+    base: u32,
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn setup() -> Fixture {
+    Fixture { base: 10 }
+}
+
+const THREE: u32 = 3;
+
+#[test]
+#[test_case(1, 2, 3)]
+#[test_case(THREE, 0, THREE)]
+#[test_case("ab".len() as u32, 2, 4)]
+fn add(a: u32, b: u32, sum: u32) {
+    assert_eq!(a + b, sum);
+}
+
+#[test]
+#[test_fixture(setup)]
+#[test_case(5, 15)]
+#[test_case(0, 10)]
+fn with_fixture(fixture: Fixture, n: u32, expected: u32) {
+    assert_eq!(fixture.base + n, expected);
+}
+
+#[test]
+#[test_fixture(setup)]
+fn fixture_only(fixture: Fixture) {
+    assert_eq!(fixture.base, 10);
+}
+
+#[test]
+#[should_panic]
+#[test_case(vec![1, 2])]
+fn panicking_case(v: Vec<u32>) {
+    let _ = v[2];
+}
@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --test -C panic=abort
+// run-flags: --process-per-test
+// no-prefer-dynamic
+// ignore-emscripten no processes
+// ignore-wasm32-bare no processes
+
+#[test]
+fn passes() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn panics_under_abort() {
+    panic!("boom");
+}
@@ -0,0 +1,31 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+// @has foo/trait.Local.html
+// @has - '//h2[@id="foreign-impls"]' 'Implementations on Foreign Types'
+// @has - '//h3[@class="impl"]//code' 'impl Local for u32'
+// @has - '//h3[@class="impl"]//code' 'impl Local for (u8, u8)'
+// @has - '//h3[@class="impl"]//code' 'impl Local for String'
+// @has - '//*[@class="sidebar-title"][@href="#foreign-impls"]' \
+//      'Implementations on Foreign Types'
+// @has - '//*[@id="implementors-list"]//code' 'impl Local for Mine'
+// @has - '//*[@id="implementors-list"]//code' "impl<'a, T: Local> Local for &'a T"
+// @!has - '//*[@id="implementors-list"]//code' 'impl Local for u32'
+pub trait Local {}
+
+pub struct Mine;
+
+impl Local for Mine {}
+impl Local for u32 {}
+impl Local for (u8, u8) {}
+impl Local for String {}
+impl<'a, T: Local> Local for &'a T {}
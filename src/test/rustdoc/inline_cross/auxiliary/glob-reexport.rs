@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub mod inner {
+    pub struct Thing;
+
+    impl Thing {
+        pub fn new() -> Thing { Thing }
+    }
+
+    pub trait Frob {
+        fn frob(&self);
+    }
+
+    pub fn frobnicate<T: Frob>(t: &T) { t.frob() }
+
+    pub const ANSWER: u32 = 42;
+}
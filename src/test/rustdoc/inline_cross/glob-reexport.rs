@@ -0,0 +1,39 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:glob-reexport.rs
+// aux-build:glob-reexport-impls.rs
+// build-aux-docs
+// ignore-cross-compile
+
+#![crate_name = "foo"]
+
+extern crate glob_reexport;
+extern crate glob_reexport_impls;
+
+// @!has foo/index.html '//code' 'pub use glob_reexport::inner::*;'
+// @has foo/index.html '//a/@href' 'struct.Thing.html'
+// @has - '//a/@href' 'trait.Frob.html'
+// @has - '//a/@href' 'fn.frobnicate.html'
+// @has - '//a/@href' 'constant.ANSWER.html'
+
+// @has foo/struct.Thing.html
+// @has - '//*[@id="method.new"]' 'pub fn new() -> Thing'
+// @has - '//*[@class="impl"]//code' 'impl Describe for Thing'
+// @has foo/trait.Frob.html '//*[@id="tymethod.frob"]' 'fn frob(&self)'
+pub use glob_reexport::inner::*;
+
+// @has foo/nope/index.html
+// @has - '//code' 'pub use glob_reexport::inner::*;'
+// @!has foo/nope/struct.Thing.html
+pub mod nope {
+    #[doc(no_inline)]
+    pub use glob_reexport::inner::*;
+}
@@ -0,0 +1,72 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// @has intra_links/index.html
+// @has - '//a/@href' '../intra_links/struct.ThisType.html'
+// @has - '//a/@href' '../intra_links/struct.ThisType.html#method.this_method'
+// @has - '//a/@href' '../intra_links/struct.ThisType.html#structfield.field'
+// @has - '//a/@href' '../intra_links/enum.ThisEnum.html'
+// @has - '//a/@href' '../intra_links/enum.ThisEnum.html#variant.ThisVariant'
+// @has - '//a/@href' '../intra_links/trait.ThisTrait.html'
+// @has - '//a/@href' '../intra_links/trait.ThisTrait.html#tymethod.this_method'
+// @has - '//a/@href' '../intra_links/type.ThisAlias.html'
+// @has - '//a/@href' '../intra_links/fn.this_function.html'
+// @has - '//a/@href' '../intra_links/constant.THIS_CONST.html'
+// @has - '//a/@href' '../intra_links/static.THIS_STATIC.html'
+// @has - '//a/@href' '../intra_links/macro.this_macro.html'
+// @has - '//a/@href' '../intra_links/inner/struct.InnerType.html'
+//! In this crate we would like to link you to:
+//!
+//! * [`ThisType`], with its method [`ThisType::this_method`] and
+//!   [field](ThisType::field)
+//! * [`ThisEnum`] and its variant [`ThisEnum::ThisVariant`]
+//! * [the trait](trait@ThisTrait) and [`ThisTrait::this_method`]
+//! * [`ThisAlias`], [`this_function()`], [`THIS_CONST`] and [`THIS_STATIC`]
+//! * [`this_macro!`]
+//! * [`inner::InnerType`]
+//!
+//! but not to `[ThisType]`, which is in code, nor to [a URL](https://example.com).
+
+#[macro_export]
+macro_rules! this_macro {
+    () => {};
+}
+
+pub struct ThisType {
+    pub field: u8,
+}
+
+impl ThisType {
+    pub fn this_method() {}
+}
+
+pub enum ThisEnum {
+    ThisVariant,
+}
+
+pub trait ThisTrait {
+    fn this_method();
+}
+
+pub type ThisAlias = Result<(), ()>;
+
+pub fn this_function() {}
+
+pub const THIS_CONST: usize = 5usize;
+
+pub static THIS_STATIC: usize = 5usize;
+
+pub mod inner {
+    // @has intra_links/inner/struct.InnerType.html
+    // @has - '//a/@href' '../../intra_links/struct.ThisType.html'
+    /// Paths are resolved from the enclosing module, so this goes to
+    /// [`super::ThisType`] and this to [`InnerType`] itself.
+    pub struct InnerType;
+}
@@ -0,0 +1,37 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+use std::path::Path;
+
+// @has 'search-index.js' '"aliases":{"frobnicate":['
+// @has - '"inputs":[{"generics":["path"],"name":"asref"}]'
+// @has - '"output":{"generics":["_"],"name":"option"}'
+// @has - '"inputs":[{"name":"widget"}],"output":{"name":"usize"}'
+
+/// Opens the thing at `path`.
+#[doc(alias = "frobnicate")]
+pub fn open<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref().exists()
+}
+
+/// Gets the first element of `v`.
+pub fn first<T>(v: &[T]) -> Option<&T> {
+    v.first()
+}
+
+pub struct Widget;
+
+impl Widget {
+    pub fn len(&self) -> usize {
+        0
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+use std::fmt;
+use std::rc::Rc;
+
+// @has foo/struct.Handle.html
+// @has - '//h2[@id="synthetic-implementations"]' 'Auto Trait Implementations'
+// @has - '//*[@class="impl"]//code' 'impl Send for Handle'
+// @has - '//*[@class="impl"]//code' 'impl Sync for Handle'
+// @has - '//*[@class="sidebar-title"][@href="#synthetic-implementations"]' \
+//      'Auto Trait Implementations'
+// @has - '//h2[@id="blanket-implementations"]' 'Blanket Implementations'
+// @has - '//*[@class="impl"]//code' 'impl<T> ToString for T'
+// @has - '//*[@class="impl"]//code' 'impl<T> Describe for T'
+// @has - '//*[@class="sidebar-title"][@href="#blanket-implementations"]' \
+//      'Blanket Implementations'
+pub struct Handle {
+    _id: u32,
+}
+
+impl fmt::Display for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "handle {}", self._id)
+    }
+}
+
+// @has foo/struct.Shared.html
+// @has - '//*[@class="impl"]//code' 'impl !Send for Shared'
+// @has - '//*[@class="impl"]//code' 'impl !Sync for Shared'
+// @!has - '//*[@class="impl"]//code' 'impl<T> ToString for T'
+pub struct Shared {
+    _inner: Rc<u32>,
+}
+
+// @has foo/struct.Wrapper.html
+// @has - '//*[@class="impl"]//code' 'impl<T> Send for Wrapper<T>'
+// @has - '//*[@class="impl"]//code/span[@class="where fmt-newline"]' 'T: Send'
+// @has - '//*[@class="impl"]//code' 'impl<T> Sync for Wrapper<T>'
+// @has - '//*[@class="impl"]//code/span[@class="where fmt-newline"]' 'T: Sync'
+pub struct Wrapper<T> {
+    _inner: Vec<T>,
+}
+
+// @has foo/struct.Manual.html
+// @has - '//*[@class="impl"]//code' 'impl Send for Manual'
+// @has - '//*[@class="impl"]//code' 'impl !Sync for Manual'
+pub struct Manual {
+    _ptr: *const u8,
+}
+
+unsafe impl Send for Manual {}
+
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: fmt::Display> Describe for T {
+    fn describe(&self) -> String {
+        format!("<{}>", self)
+    }
+}
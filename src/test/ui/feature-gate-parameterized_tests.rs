@@ -0,0 +1,20 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// check that #[test_case] and #[test_fixture] are feature-gated
+
+#[test_case(1)] //~ ERROR the test_case attribute is currently unstable
+#[test_fixture(setup)] //~ ERROR the test_fixture attribute is currently unstable
+fn case(_: (), _: u32) {
+}
+
+fn setup() {
+}
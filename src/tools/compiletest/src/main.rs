@@ -485,17 +485,29 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         filter: config.filter.clone(),
         filter_exact: config.filter_exact,
         run_ignored: config.run_ignored,
-        quiet: config.quiet,
+        format: if config.quiet { test::OutputFormat::Terse } else { test::OutputFormat::Pretty },
         logfile: config.logfile.clone(),
+        report: None,
+        report_format: test::ReportFormat::Junit,
         run_tests: true,
         bench_benchmarks: true,
+        baseline: None,
+        save_baseline: None,
         nocapture: match env::var("RUST_TEST_NOCAPTURE") {
             Ok(val) => &val != "0",
             Err(_) => false,
         },
         color: config.color,
         test_threads: None,
+        test_timeout: None,
         skip: vec![],
+        shard_index: 0,
+        shard_count: 1,
+        shuffle_seed: None,
+        report_time: false,
+        slow_threshold: None,
+        fail_slow: false,
+        process_per_test: false,
         list: false,
         options: test::Options::new(),
     }
@@ -623,6 +635,7 @@ pub fn make_test(config: &Config, testpaths: &TestPaths) -> test::TestDescAndFn
             ignore,
             should_panic,
             allow_fail: false,
+            timeout: None,
         },
         testfn: make_test_closure(config, testpaths),
     }
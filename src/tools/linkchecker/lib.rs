@@ -0,0 +1,431 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checking the validity of `href` links in HTML documentation.
+//!
+//! In the past we've been quite error prone to writing in broken links as most
+//! of them are manually rather than automatically added. As files move over
+//! time or apis change old links become stale or broken. The purpose of this
+//! library is to check all relative links in documentation to make sure they
+//! actually point to a valid place. It is used by the `linkchecker` tool on
+//! our own documentation and by rustdoc's `--check-links`.
+//!
+//! Currently this doesn't actually do any HTML parsing or anything fancy like
+//! that, it just has a simple "regex" to search for `href` and `id` tags.
+//! These values are then translated to file URLs if possible and then the
+//! destination is asserted to exist.
+
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf, Component};
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+
+use Redirect::*;
+
+macro_rules! t {
+    ($e:expr) => (match $e {
+        Ok(e) => e,
+        Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+    })
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    IOError(std::io::Error),
+    BrokenRedirect(PathBuf, std::io::Error),
+    IsRedirect,
+}
+
+enum Redirect {
+    SkipRedirect,
+    FromRedirect(bool),
+}
+
+struct FileEntry {
+    source: String,
+    ids: HashSet<String>,
+}
+
+type Cache = HashMap<PathBuf, FileEntry>;
+
+/// A broken link or a duplicate id found in a file.
+#[derive(Debug)]
+pub struct LinkError {
+    /// The file, relative to the root of the documentation
+    pub file: PathBuf,
+    /// The line of the file, starting at 1
+    pub line: usize,
+    pub kind: LinkErrorKind,
+}
+
+#[derive(Debug)]
+pub enum LinkErrorKind {
+    /// The id is used more than once in the file
+    DuplicateId(String),
+    /// The link points to a directory rather than a file
+    DirectoryLink { url: String, target: PathBuf },
+    /// The link points to a redirect whose target doesn't exist
+    BrokenRedirect { url: String, target: PathBuf },
+    /// The link points to a file which has no element with the id of its
+    /// fragment
+    BrokenFragment { url: String, fragment: String, target: PathBuf },
+    /// The link points to a file which doesn't exist
+    BrokenLink { url: String, target: PathBuf },
+}
+
+impl LinkError {
+    /// The link as it is written in the file, unless this is about an id.
+    pub fn url(&self) -> Option<&str> {
+        match self.kind {
+            LinkErrorKind::DuplicateId(..) => None,
+            LinkErrorKind::DirectoryLink { ref url, .. } |
+            LinkErrorKind::BrokenRedirect { ref url, .. } |
+            LinkErrorKind::BrokenFragment { ref url, .. } |
+            LinkErrorKind::BrokenLink { ref url, .. } => Some(url),
+        }
+    }
+
+    /// The file the link points to, relative to the root of the
+    /// documentation if it is inside of it, unless this is about an id.
+    pub fn target(&self) -> Option<&Path> {
+        match self.kind {
+            LinkErrorKind::DuplicateId(..) => None,
+            LinkErrorKind::DirectoryLink { ref target, .. } |
+            LinkErrorKind::BrokenRedirect { ref target, .. } |
+            LinkErrorKind::BrokenFragment { ref target, .. } |
+            LinkErrorKind::BrokenLink { ref target, .. } => Some(target),
+        }
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: ", self.file.display(), self.line)?;
+        match self.kind {
+            LinkErrorKind::DuplicateId(ref id) => write!(f, "id is not unique: `{}`", id),
+            LinkErrorKind::DirectoryLink { ref target, .. } => {
+                write!(f, "directory link - {}", target.display())
+            }
+            LinkErrorKind::BrokenRedirect { ref target, .. } => {
+                write!(f, "broken redirect to {}", target.display())
+            }
+            LinkErrorKind::BrokenFragment { ref fragment, ref target, .. } => {
+                write!(f, "broken link fragment `#{}` pointing to `{}`",
+                       fragment, target.display())
+            }
+            LinkErrorKind::BrokenLink { ref target, .. } => {
+                write!(f, "broken link - {}", target.display())
+            }
+        }
+    }
+}
+
+fn small_url_encode(s: &str) -> String {
+    s.replace("<", "%3C")
+     .replace(">", "%3E")
+     .replace(" ", "%20")
+     .replace("?", "%3F")
+     .replace("'", "%27")
+     .replace("&", "%26")
+     .replace(",", "%2C")
+     .replace(":", "%3A")
+     .replace(";", "%3B")
+     .replace("[", "%5B")
+     .replace("]", "%5D")
+     .replace("\"", "%22")
+}
+
+impl FileEntry {
+    fn parse_ids(&mut self, file: &Path, contents: &str, errors: &mut Vec<LinkError>) {
+        if self.ids.is_empty() {
+            with_attrs_in_source(contents, " id", |fragment, i, _| {
+                let frag = fragment.trim_left_matches("#").to_owned();
+                let encoded = small_url_encode(&frag);
+                if !self.ids.insert(frag) {
+                    errors.push(LinkError {
+                        file: file.to_path_buf(),
+                        line: i + 1,
+                        kind: LinkErrorKind::DuplicateId(fragment.to_owned()),
+                    });
+                }
+                // Just in case, we also add the encoded id.
+                self.ids.insert(encoded);
+            });
+        }
+    }
+}
+
+/// Checks the links in the HTML files of a directory of documentation,
+/// remembering the files and ids it has seen across calls.
+pub struct Checker {
+    root: PathBuf,
+    cache: Cache,
+}
+
+impl Checker {
+    pub fn new(root: &Path) -> Checker {
+        Checker {
+            root: root.to_path_buf(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Checks all the HTML files in `dir` and the directories in it, except
+    /// for those `skip` returns true for.
+    pub fn walk<F>(&mut self, dir: &Path, skip: &mut F, errors: &mut Vec<LinkError>)
+        where F: FnMut(&Path) -> bool
+    {
+        for entry in t!(dir.read_dir()).map(|e| t!(e)) {
+            let path = entry.path();
+            let kind = t!(entry.file_type());
+            if kind.is_dir() {
+                self.walk(&path, skip, errors);
+            } else if !skip(&path) {
+                self.check(&path, errors);
+            }
+        }
+    }
+
+    /// Checks the links in `file`, unless it isn't an HTML file.
+    pub fn check(&mut self, file: &Path, errors: &mut Vec<LinkError>) {
+        let pretty_path = check(&mut self.cache, &self.root, file, errors);
+        if let Some(pretty_path) = pretty_path {
+            let entry = self.cache.get_mut(&pretty_path).unwrap();
+            // we don't need the source anymore,
+            // so drop to reduce memory-usage
+            entry.source = String::new();
+        }
+    }
+}
+
+fn check(cache: &mut Cache,
+         root: &Path,
+         file: &Path,
+         errors: &mut Vec<LinkError>)
+         -> Option<PathBuf> {
+    // Ignore none HTML files.
+    if file.extension().and_then(|s| s.to_str()) != Some("html") {
+        return None;
+    }
+
+    let res = load_file(cache, root, file, SkipRedirect);
+    let (pretty_file, contents) = match res {
+        Ok(res) => res,
+        Err(_) => return None,
+    };
+    {
+        cache.get_mut(&pretty_file)
+             .unwrap()
+             .parse_ids(&pretty_file, &contents, errors);
+    }
+
+    // Search for anything that's the regex 'href[ ]*=[ ]*".*?"'
+    with_attrs_in_source(&contents, " href", |full_url, i, base| {
+        // Ignore external URLs
+        if full_url.starts_with("http:") || full_url.starts_with("https:") ||
+           full_url.starts_with("javascript:") || full_url.starts_with("ftp:") ||
+           full_url.starts_with("irc:") || full_url.starts_with("data:") ||
+           full_url.starts_with("mailto:") {
+            return;
+        }
+        let mut parts = full_url.splitn(2, "#");
+        let url = parts.next().unwrap();
+        let fragment = parts.next();
+        let mut parts = url.splitn(2, "?");
+        let url = parts.next().unwrap();
+
+        // Once we've plucked out the URL, parse it using our base url and
+        // then try to extract a file path.
+        let mut path = file.to_path_buf();
+        if !base.is_empty() || !url.is_empty() {
+            path.pop();
+            for part in Path::new(base).join(url).components() {
+                match part {
+                    // There's no telling where absolute links end up once
+                    // the documentation is published
+                    Component::Prefix(_) |
+                    Component::RootDir => return,
+                    Component::CurDir => {}
+                    Component::ParentDir => { path.pop(); }
+                    Component::Normal(s) => { path.push(s); }
+                }
+            }
+        }
+        let pretty_path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        let link_error = |kind| LinkError { file: pretty_file.clone(), line: i + 1, kind };
+
+        // Alright, if we've found a file name then this file had better
+        // exist! If it doesn't then we register an error.
+        if path.exists() {
+            if path.is_dir() {
+                // Links to directories show as directory listings when viewing
+                // the docs offline so it's best to avoid them.
+                errors.push(link_error(LinkErrorKind::DirectoryLink {
+                    url: full_url.to_owned(),
+                    target: pretty_path,
+                }));
+                return;
+            }
+            if let Some(extension) = path.extension() {
+                // Ignore none HTML files.
+                if extension != "html" {
+                    return;
+                }
+            }
+            let res = load_file(cache, root, &path, FromRedirect(false));
+            let (pretty_path, contents) = match res {
+                Ok(res) => res,
+                Err(LoadError::IOError(err)) => {
+                    panic!("error loading {}: {}", path.display(), err);
+                }
+                Err(LoadError::BrokenRedirect(target, _)) => {
+                    let target = target.strip_prefix(root).unwrap_or(&target).to_path_buf();
+                    errors.push(link_error(LinkErrorKind::BrokenRedirect {
+                        url: full_url.to_owned(),
+                        target,
+                    }));
+                    return;
+                }
+                Err(LoadError::IsRedirect) => unreachable!(),
+            };
+
+            if let Some(ref fragment) = fragment {
+                // Fragments like `#1-6` are most likely line numbers to be
+                // interpreted by javascript, so we're ignoring these
+                if fragment.splitn(2, '-')
+                           .all(|f| f.chars().all(|c| c.is_numeric())) {
+                    return;
+                }
+
+                let entry = &mut cache.get_mut(&pretty_path).unwrap();
+                entry.parse_ids(&pretty_path, &contents, errors);
+
+                if !entry.ids.contains(*fragment) {
+                    errors.push(link_error(LinkErrorKind::BrokenFragment {
+                        url: full_url.to_owned(),
+                        fragment: fragment.to_string(),
+                        target: pretty_path.clone(),
+                    }));
+                }
+            }
+        } else {
+            errors.push(link_error(LinkErrorKind::BrokenLink {
+                url: full_url.to_owned(),
+                target: pretty_path,
+            }));
+        }
+    });
+    Some(pretty_file)
+}
+
+fn load_file(cache: &mut Cache,
+             root: &Path,
+             file: &Path,
+             redirect: Redirect)
+             -> Result<(PathBuf, String), LoadError> {
+    let mut contents = String::new();
+    let pretty_file = PathBuf::from(file.strip_prefix(root).unwrap_or(&file));
+
+    let maybe_redirect = match cache.entry(pretty_file.clone()) {
+        Entry::Occupied(entry) => {
+            contents = entry.get().source.clone();
+            None
+        }
+        Entry::Vacant(entry) => {
+            let mut fp = File::open(file).map_err(|err| {
+                if let FromRedirect(true) = redirect {
+                    LoadError::BrokenRedirect(file.to_path_buf(), err)
+                } else {
+                    LoadError::IOError(err)
+                }
+            })?;
+            fp.read_to_string(&mut contents).map_err(|err| LoadError::IOError(err))?;
+
+            let maybe = maybe_redirect(&contents);
+            if maybe.is_some() {
+                if let SkipRedirect = redirect {
+                    return Err(LoadError::IsRedirect);
+                }
+            } else {
+                entry.insert(FileEntry {
+                    source: contents.clone(),
+                    ids: HashSet::new(),
+                });
+            }
+            maybe
+        }
+    };
+    match maybe_redirect.map(|url| file.parent().unwrap().join(url)) {
+        Some(redirect_file) => {
+            load_file(cache, root, &redirect_file, FromRedirect(true))
+        }
+        None => Ok((pretty_file, contents)),
+    }
+}
+
+fn maybe_redirect(source: &str) -> Option<String> {
+    const REDIRECT: &'static str = "<p>Redirecting to <a href=";
+
+    let mut lines = source.lines();
+    let redirect_line = match lines.nth(6) {
+        Some(l) => l,
+        None => return None,
+    };
+
+    redirect_line.find(REDIRECT).map(|i| {
+        let rest = &redirect_line[(i + REDIRECT.len() + 1)..];
+        let pos_quote = rest.find('"').unwrap();
+        rest[..pos_quote].to_owned()
+    })
+}
+
+fn with_attrs_in_source<F: FnMut(&str, usize, &str)>(contents: &str, attr: &str, mut f: F) {
+    let mut base = "";
+    for (i, mut line) in contents.lines().enumerate() {
+        while let Some(j) = line.find(attr) {
+            let rest = &line[j + attr.len()..];
+            // The base tag should always be the first link in the document so
+            // we can get away with using one pass.
+            let is_base = line[..j].ends_with("<base");
+            line = rest;
+            let pos_equals = match rest.find("=") {
+                Some(i) => i,
+                None => continue,
+            };
+            if rest[..pos_equals].trim_left_matches(" ") != "" {
+                continue;
+            }
+
+            let rest = &rest[pos_equals + 1..];
+
+            let pos_quote = match rest.find(&['"', '\''][..]) {
+                Some(i) => i,
+                None => continue,
+            };
+            let quote_delim = rest.as_bytes()[pos_quote] as char;
+
+            if rest[..pos_quote].trim_left_matches(" ") != "" {
+                continue;
+            }
+            let rest = &rest[pos_quote + 1..];
+            let url = match rest.find(quote_delim) {
+                Some(i) => &rest[..i],
+                None => continue,
+            };
+            if is_base {
+                base = url;
+                continue;
+            }
+            f(url, i, base)
+        }
+    }
+}